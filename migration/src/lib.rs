@@ -2,6 +2,27 @@ mod m20251016_092534_create_users_table;
 mod m20251016_173133_create_api_keys_table;
 mod m20251108_171410_create_sites_table;
 mod m20251110_122652_create_posts_table;
+mod m20261017_090000_add_fetch_mode_to_sites;
+mod m20261017_100000_create_crawl_runs_table;
+mod m20261017_110000_add_error_tracking_to_sites;
+mod m20261017_120000_add_schedule_to_sites;
+mod m20261017_130000_add_revision_tracking;
+mod m20261017_140000_create_post_revisions_table;
+mod m20261017_150000_add_screenshot_to_posts;
+mod m20261017_160000_add_metadata_to_posts;
+mod m20261017_170000_add_publication_fields;
+mod m20261017_180000_create_post_media_table;
+mod m20261017_190000_add_media_mirroring;
+mod m20261017_200000_add_readable_body_to_posts;
+mod m20261017_210000_add_auto_content_to_sites;
+mod m20261017_220000_add_pagination_to_sites;
+mod m20261017_230000_add_source_type_to_sites;
+mod m20261017_240000_add_feed_token_to_api_key;
+mod m20261017_250000_create_webhooks_tables;
+mod m20261017_260000_create_posts_fts;
+mod m20261017_270000_add_posts_processed_to_crawl_runs;
+mod m20261017_280000_add_body_source_to_posts;
+mod m20261017_290000_add_mirror_retries_to_post_media;
 
 pub use sea_orm_migration::prelude::*;
 
@@ -15,6 +36,27 @@ impl MigratorTrait for Migrator {
             Box::new(m20251016_173133_create_api_keys_table::Migration),
             Box::new(m20251108_171410_create_sites_table::Migration),
             Box::new(m20251110_122652_create_posts_table::Migration),
+            Box::new(m20261017_090000_add_fetch_mode_to_sites::Migration),
+            Box::new(m20261017_100000_create_crawl_runs_table::Migration),
+            Box::new(m20261017_110000_add_error_tracking_to_sites::Migration),
+            Box::new(m20261017_120000_add_schedule_to_sites::Migration),
+            Box::new(m20261017_130000_add_revision_tracking::Migration),
+            Box::new(m20261017_140000_create_post_revisions_table::Migration),
+            Box::new(m20261017_150000_add_screenshot_to_posts::Migration),
+            Box::new(m20261017_160000_add_metadata_to_posts::Migration),
+            Box::new(m20261017_170000_add_publication_fields::Migration),
+            Box::new(m20261017_180000_create_post_media_table::Migration),
+            Box::new(m20261017_190000_add_media_mirroring::Migration),
+            Box::new(m20261017_200000_add_readable_body_to_posts::Migration),
+            Box::new(m20261017_210000_add_auto_content_to_sites::Migration),
+            Box::new(m20261017_220000_add_pagination_to_sites::Migration),
+            Box::new(m20261017_230000_add_source_type_to_sites::Migration),
+            Box::new(m20261017_240000_add_feed_token_to_api_key::Migration),
+            Box::new(m20261017_250000_create_webhooks_tables::Migration),
+            Box::new(m20261017_260000_create_posts_fts::Migration),
+            Box::new(m20261017_270000_add_posts_processed_to_crawl_runs::Migration),
+            Box::new(m20261017_280000_add_body_source_to_posts::Migration),
+            Box::new(m20261017_290000_add_mirror_retries_to_post_media::Migration),
         ]
     }
 }
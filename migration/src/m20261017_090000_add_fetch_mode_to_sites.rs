@@ -0,0 +1,41 @@
+use crate::m20251108_171410_create_sites_table::Site;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Site::Table)
+                    .add_column(
+                        ColumnDef::new(SiteFetch::FetchMode)
+                            .string()
+                            .not_null()
+                            .default("browser")
+                            .check(Expr::col(SiteFetch::FetchMode).is_in(["browser", "http"])),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Site::Table)
+                    .drop_column(SiteFetch::FetchMode)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SiteFetch {
+    FetchMode,
+}
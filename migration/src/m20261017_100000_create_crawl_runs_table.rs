@@ -0,0 +1,105 @@
+use crate::m20251108_171410_create_sites_table::Site;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CrawlRuns::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CrawlRuns::Id)
+                            .big_integer()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(CrawlRuns::Job).string().not_null())
+                    .col(ColumnDef::new(CrawlRuns::SiteId).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(CrawlRuns::LinksFound)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(CrawlRuns::PostsCreated)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(CrawlRuns::PostsUpdated)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(CrawlRuns::Duplicates)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(CrawlRuns::Failures)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(CrawlRuns::Error).text().null())
+                    .col(
+                        ColumnDef::new(CrawlRuns::StartedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(CrawlRuns::FinishedAt).date_time().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CrawlRuns::Table, CrawlRuns::SiteId)
+                            .to(Site::Table, Site::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_crawl_runs_site_id")
+                    .table(CrawlRuns::Table)
+                    .col(CrawlRuns::SiteId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CrawlRuns::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum CrawlRuns {
+    Table,
+    Id,
+    Job,
+    SiteId,
+    LinksFound,
+    PostsCreated,
+    PostsUpdated,
+    Duplicates,
+    Failures,
+    Error,
+    StartedAt,
+    FinishedAt,
+}
@@ -0,0 +1,86 @@
+use crate::m20251108_171410_create_sites_table::Site;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only accepts one column per ALTER TABLE statement.
+        let columns = [
+            ColumnDef::new(SiteErrors::ErrorCount)
+                .integer()
+                .not_null()
+                .default(0)
+                .to_owned(),
+            ColumnDef::new(SiteErrors::ErrorThreshold)
+                .integer()
+                .null()
+                .to_owned(),
+            ColumnDef::new(SiteErrors::LastError)
+                .text()
+                .null()
+                .to_owned(),
+            ColumnDef::new(SiteErrors::LastErrorAt)
+                .date_time()
+                .null()
+                .to_owned(),
+            ColumnDef::new(SiteErrors::DisabledReason)
+                .text()
+                .null()
+                .to_owned(),
+            ColumnDef::new(SiteErrors::DisabledAt)
+                .date_time()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let columns = [
+            SiteErrors::ErrorCount,
+            SiteErrors::ErrorThreshold,
+            SiteErrors::LastError,
+            SiteErrors::LastErrorAt,
+            SiteErrors::DisabledReason,
+            SiteErrors::DisabledAt,
+        ];
+
+        for column in columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SiteErrors {
+    ErrorCount,
+    ErrorThreshold,
+    LastError,
+    LastErrorAt,
+    DisabledReason,
+    DisabledAt,
+}
@@ -0,0 +1,86 @@
+use crate::m20251108_171410_create_sites_table::Site;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only accepts one column per ALTER TABLE statement.
+        let columns = [
+            ColumnDef::new(SiteSchedule::CrawlIntervalMinutes)
+                .integer()
+                .null()
+                .to_owned(),
+            ColumnDef::new(SiteSchedule::CrawlCron)
+                .string()
+                .null()
+                .to_owned(),
+            ColumnDef::new(SiteSchedule::NextCrawlAt)
+                .date_time()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_site_next_crawl_at")
+                    .table(Site::Table)
+                    .col(SiteSchedule::NextCrawlAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_site_next_crawl_at")
+                    .table(Site::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        let columns = [
+            SiteSchedule::CrawlIntervalMinutes,
+            SiteSchedule::CrawlCron,
+            SiteSchedule::NextCrawlAt,
+        ];
+
+        for column in columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SiteSchedule {
+    CrawlIntervalMinutes,
+    CrawlCron,
+    NextCrawlAt,
+}
@@ -0,0 +1,123 @@
+use crate::m20251108_171410_create_sites_table::Site;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only accepts one column per ALTER TABLE statement.
+        let site_columns = [
+            ColumnDef::new(SiteRecheck::RecheckWindowHours)
+                .integer()
+                .null()
+                .to_owned(),
+            ColumnDef::new(SiteRecheck::RecheckIntervalMinutes)
+                .integer()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in site_columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        let post_columns = [
+            ColumnDef::new(PostRevision::ContentHash)
+                .string()
+                .null()
+                .to_owned(),
+            ColumnDef::new(PostRevision::Revision)
+                .integer()
+                .not_null()
+                .default(0)
+                .to_owned(),
+            ColumnDef::new(PostRevision::UpdatedAt)
+                .date_time()
+                .null()
+                .to_owned(),
+            ColumnDef::new(PostRevision::CheckedAt)
+                .date_time()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in post_columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Posts::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let post_columns = [
+            PostRevision::ContentHash,
+            PostRevision::Revision,
+            PostRevision::UpdatedAt,
+            PostRevision::CheckedAt,
+        ];
+
+        for column in post_columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Posts::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        let site_columns = [
+            SiteRecheck::RecheckWindowHours,
+            SiteRecheck::RecheckIntervalMinutes,
+        ];
+
+        for column in site_columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum SiteRecheck {
+    RecheckWindowHours,
+    RecheckIntervalMinutes,
+}
+
+#[derive(DeriveIden)]
+enum PostRevision {
+    ContentHash,
+    Revision,
+    UpdatedAt,
+    CheckedAt,
+}
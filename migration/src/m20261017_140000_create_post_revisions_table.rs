@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostRevisions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostRevisions::Id)
+                            .big_integer()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PostRevisions::PostId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PostRevisions::Revision).integer().not_null())
+                    .col(ColumnDef::new(PostRevisions::Title).string().null())
+                    .col(ColumnDef::new(PostRevisions::Body).text().null())
+                    .col(ColumnDef::new(PostRevisions::Image).string().null())
+                    .col(ColumnDef::new(PostRevisions::Video).string().null())
+                    .col(
+                        ColumnDef::new(PostRevisions::ContentHash)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PostRevisions::ExtractedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PostRevisions::Table, PostRevisions::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_revisions_post_revision")
+                    .table(PostRevisions::Table)
+                    .col(PostRevisions::PostId)
+                    .col(PostRevisions::Revision)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostRevisions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PostRevisions {
+    Table,
+    Id,
+    PostId,
+    Revision,
+    Title,
+    Body,
+    Image,
+    Video,
+    ContentHash,
+    ExtractedAt,
+}
@@ -0,0 +1,117 @@
+use crate::m20251108_171410_create_sites_table::Site;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only accepts one column per ALTER TABLE statement.
+        let site_columns = [
+            ColumnDef::new(SitePublication::PathDate)
+                .string()
+                .null()
+                .to_owned(),
+            ColumnDef::new(SitePublication::PathAuthor)
+                .string()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in site_columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        let post_columns = [
+            ColumnDef::new(PostPublication::PublishedAt)
+                .date_time()
+                .null()
+                .to_owned(),
+            ColumnDef::new(PostPublication::Author)
+                .string()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in post_columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Posts::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posts_published_at")
+                    .table(Posts::Table)
+                    .col(PostPublication::PublishedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_posts_published_at")
+                    .table(Posts::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [PostPublication::PublishedAt, PostPublication::Author] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Posts::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        for column in [SitePublication::PathDate, SitePublication::PathAuthor] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum SitePublication {
+    PathDate,
+    PathAuthor,
+}
+
+#[derive(DeriveIden)]
+enum PostPublication {
+    PublishedAt,
+    Author,
+}
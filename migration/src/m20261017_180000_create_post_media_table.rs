@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostMedia::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostMedia::Id)
+                            .big_integer()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PostMedia::PostId).big_integer().not_null())
+                    .col(ColumnDef::new(PostMedia::Kind).string().not_null())
+                    .col(ColumnDef::new(PostMedia::Url).string().not_null())
+                    .col(ColumnDef::new(PostMedia::Width).integer().null())
+                    .col(ColumnDef::new(PostMedia::Height).integer().null())
+                    .col(ColumnDef::new(PostMedia::Alt).string().null())
+                    .col(ColumnDef::new(PostMedia::Position).integer().not_null())
+                    .col(
+                        ColumnDef::new(PostMedia::CreatedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PostMedia::Table, PostMedia::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_media_post_position")
+                    .table(PostMedia::Table)
+                    .col(PostMedia::PostId)
+                    .col(PostMedia::Position)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostMedia::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+pub enum PostMedia {
+    Table,
+    Id,
+    PostId,
+    Kind,
+    Url,
+    Width,
+    Height,
+    Alt,
+    Position,
+    CreatedAt,
+}
@@ -0,0 +1,134 @@
+use crate::m20251108_171410_create_sites_table::Site;
+use crate::m20261017_180000_create_post_media_table::PostMedia;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only accepts one column per ALTER TABLE statement.
+        let site_columns = [
+            ColumnDef::new(SiteMirror::MirrorMedia)
+                .boolean()
+                .null()
+                .to_owned(),
+            ColumnDef::new(SiteMirror::MirrorVideos)
+                .boolean()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in site_columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        let media_columns = [
+            ColumnDef::new(PostMediaMirror::OriginalUrl)
+                .string()
+                .null()
+                .to_owned(),
+            ColumnDef::new(PostMediaMirror::Checksum)
+                .string()
+                .null()
+                .to_owned(),
+            ColumnDef::new(PostMediaMirror::Size)
+                .big_integer()
+                .null()
+                .to_owned(),
+            ColumnDef::new(PostMediaMirror::Mime)
+                .string()
+                .null()
+                .to_owned(),
+            ColumnDef::new(PostMediaMirror::MirrorError)
+                .text()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in media_columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(PostMedia::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_media_checksum")
+                    .table(PostMedia::Table)
+                    .col(PostMediaMirror::Checksum)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_post_media_checksum")
+                    .table(PostMedia::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [
+            PostMediaMirror::OriginalUrl,
+            PostMediaMirror::Checksum,
+            PostMediaMirror::Size,
+            PostMediaMirror::Mime,
+            PostMediaMirror::MirrorError,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(PostMedia::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        for column in [SiteMirror::MirrorMedia, SiteMirror::MirrorVideos] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SiteMirror {
+    MirrorMedia,
+    MirrorVideos,
+}
+
+#[derive(DeriveIden)]
+enum PostMediaMirror {
+    OriginalUrl,
+    Checksum,
+    Size,
+    Mime,
+    MirrorError,
+}
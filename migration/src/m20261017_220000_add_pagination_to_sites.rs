@@ -0,0 +1,92 @@
+use crate::m20251108_171410_create_sites_table::Site;
+use crate::m20261017_100000_create_crawl_runs_table::CrawlRuns;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only accepts one column per ALTER TABLE statement.
+        let site_columns = [
+            ColumnDef::new(SitePagination::PathNextPage)
+                .string()
+                .null()
+                .to_owned(),
+            ColumnDef::new(SitePagination::PageUrlTemplate)
+                .string()
+                .null()
+                .to_owned(),
+            ColumnDef::new(SitePagination::MaxPages)
+                .integer()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in site_columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CrawlRuns::Table)
+                    .add_column(
+                        ColumnDef::new(CrawlRunPages::PagesCrawled)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CrawlRuns::Table)
+                    .drop_column(CrawlRunPages::PagesCrawled)
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [
+            SitePagination::PathNextPage,
+            SitePagination::PageUrlTemplate,
+            SitePagination::MaxPages,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SitePagination {
+    PathNextPage,
+    PageUrlTemplate,
+    MaxPages,
+}
+
+#[derive(DeriveIden)]
+enum CrawlRunPages {
+    PagesCrawled,
+}
@@ -0,0 +1,58 @@
+use crate::m20251108_171410_create_sites_table::Site;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only accepts one column per ALTER TABLE statement.
+        let columns = [
+            ColumnDef::new(SiteSource::SourceType)
+                .string()
+                .not_null()
+                .default("listing")
+                .check(Expr::col(SiteSource::SourceType).is_in(["listing", "sitemap", "feed"]))
+                .to_owned(),
+            ColumnDef::new(SiteSource::MaxAgeHours)
+                .integer()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [SiteSource::SourceType, SiteSource::MaxAgeHours] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Site::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SiteSource {
+    SourceType,
+    MaxAgeHours,
+}
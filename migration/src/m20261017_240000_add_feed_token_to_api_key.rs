@@ -0,0 +1,60 @@
+use crate::m20251016_173133_create_api_keys_table::ApiKey;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ApiKey::Table)
+                    .add_column(
+                        ColumnDef::new(ApiKeyFeed::FeedTokenHash)
+                            .string_len(128)
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // SQLite can't add a UNIQUE column, so uniqueness comes from the index.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_api_key_feed_token_hash")
+                    .table(ApiKey::Table)
+                    .col(ApiKeyFeed::FeedTokenHash)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_api_key_feed_token_hash")
+                    .table(ApiKey::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ApiKey::Table)
+                    .drop_column(ApiKeyFeed::FeedTokenHash)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ApiKeyFeed {
+    FeedTokenHash,
+}
@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Full-text index over `posts.title` and `posts.body_text`. It is an external-content
+/// table, so the text is not stored twice; the triggers keep it in step with every insert,
+/// update and delete of a post, including cascades and the cleanup job's bulk deletes.
+const UP: &[&str] = &[
+    "CREATE VIRTUAL TABLE IF NOT EXISTS posts_fts USING fts5(
+        title,
+        body_text,
+        content = 'posts',
+        content_rowid = 'id',
+        tokenize = 'unicode61 remove_diacritics 2'
+    )",
+    "CREATE TRIGGER IF NOT EXISTS posts_fts_insert AFTER INSERT ON posts BEGIN
+        INSERT INTO posts_fts (rowid, title, body_text)
+        VALUES (new.id, new.title, new.body_text);
+    END",
+    "CREATE TRIGGER IF NOT EXISTS posts_fts_delete AFTER DELETE ON posts BEGIN
+        INSERT INTO posts_fts (posts_fts, rowid, title, body_text)
+        VALUES ('delete', old.id, old.title, old.body_text);
+    END",
+    "CREATE TRIGGER IF NOT EXISTS posts_fts_update AFTER UPDATE OF title, body_text ON posts BEGIN
+        INSERT INTO posts_fts (posts_fts, rowid, title, body_text)
+        VALUES ('delete', old.id, old.title, old.body_text);
+        INSERT INTO posts_fts (rowid, title, body_text)
+        VALUES (new.id, new.title, new.body_text);
+    END",
+    // Rank title matches above body matches.
+    "INSERT INTO posts_fts (posts_fts, rank) VALUES ('rank', 'bm25(5.0, 1.0)')",
+    // Index the posts stored before search existed.
+    "INSERT INTO posts_fts (posts_fts) VALUES ('rebuild')",
+];
+
+const DOWN: &[&str] = &[
+    "DROP TRIGGER IF EXISTS posts_fts_update",
+    "DROP TRIGGER IF EXISTS posts_fts_delete",
+    "DROP TRIGGER IF EXISTS posts_fts_insert",
+    "DROP TABLE IF EXISTS posts_fts",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for sql in UP {
+            db.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for sql in DOWN {
+            db.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+}
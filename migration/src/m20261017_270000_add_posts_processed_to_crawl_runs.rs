@@ -0,0 +1,40 @@
+use crate::m20261017_100000_create_crawl_runs_table::CrawlRuns;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CrawlRuns::Table)
+                    .add_column(
+                        ColumnDef::new(CrawlRunPosts::PostsProcessed)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CrawlRuns::Table)
+                    .drop_column(CrawlRunPosts::PostsProcessed)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CrawlRunPosts {
+    PostsProcessed,
+}
@@ -0,0 +1,60 @@
+use crate::m20261017_180000_create_post_media_table::PostMedia;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only accepts one column per ALTER TABLE statement.
+        let columns = [
+            ColumnDef::new(PostMediaRetry::MirrorAttempts)
+                .integer()
+                .not_null()
+                .default(0)
+                .to_owned(),
+            ColumnDef::new(PostMediaRetry::MirrorRetryAt)
+                .date_time()
+                .null()
+                .to_owned(),
+        ];
+
+        for column in columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(PostMedia::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            PostMediaRetry::MirrorAttempts,
+            PostMediaRetry::MirrorRetryAt,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(PostMedia::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum PostMediaRetry {
+    MirrorAttempts,
+    MirrorRetryAt,
+}
@@ -19,11 +19,28 @@ pub struct Config {
     pub database_url: String,
     pub hmac: String,
     pub max_retry_post: u8,
+    pub site_error_threshold: u32,
     pub post_check_interval_minutes: u32,
+    pub scheduler_tick_seconds: u64,
+    pub post_extract_interval_minutes: u32,
+    pub cron_timezone: String,
+    pub post_cleanup_cron: String,
     pub post_keep_latest: u64,
     pub post_concurrency: usize,
     pub post_timeout_seconds: u64,
     pub browser_start_timeout_seconds: u64,
+    pub browser_max_tabs: usize,
+    pub media_directory: String,
+    pub screenshot_format: String,
+    pub screenshot_full_page: bool,
+    pub media_max_image_bytes: u64,
+    pub media_max_video_bytes: u64,
+    pub sanitize_allowed_tags: String,
+    pub sanitize_allowed_attributes: String,
+    pub webhook_tick_seconds: u64,
+    pub webhook_timeout_seconds: u64,
+    pub webhook_max_attempts: i32,
+    pub webhook_allow_private_networks: bool,
     pub log_directory: String,
     pub log_retention_days: u64,
 }
@@ -100,11 +117,28 @@ impl Config {
             final_domain: Self::final_domain(),
             hmac: Self::app_hmac(),
             max_retry_post: Self::app_max_retry_post(),
+            site_error_threshold: Self::app_site_error_threshold(),
             post_check_interval_minutes: Self::app_post_check_interval_minutes(),
+            scheduler_tick_seconds: Self::scheduler_tick_seconds(),
+            post_extract_interval_minutes: Self::post_extract_interval_minutes(),
+            cron_timezone: Self::cron_timezone(),
+            post_cleanup_cron: Self::post_cleanup_cron(),
             post_keep_latest: Self::app_post_keep_latest(),
             post_concurrency: Self::post_processing_concurrency(),
             post_timeout_seconds: Self::post_processing_timeout_seconds(),
             browser_start_timeout_seconds: Self::browser_start_timeout_seconds(),
+            browser_max_tabs: Self::browser_max_tabs(),
+            media_directory: Self::media_directory(),
+            screenshot_format: Self::screenshot_format(),
+            screenshot_full_page: Self::screenshot_full_page(),
+            media_max_image_bytes: Self::media_max_image_bytes(),
+            media_max_video_bytes: Self::media_max_video_bytes(),
+            sanitize_allowed_tags: Self::sanitize_allowed_tags(),
+            sanitize_allowed_attributes: Self::sanitize_allowed_attributes(),
+            webhook_tick_seconds: Self::webhook_tick_seconds(),
+            webhook_timeout_seconds: Self::webhook_timeout_seconds(),
+            webhook_max_attempts: Self::webhook_max_attempts(),
+            webhook_allow_private_networks: Self::webhook_allow_private_networks(),
             log_directory: Self::log_directory(),
             log_retention_days: Self::log_retention_days(),
         }
@@ -128,6 +162,14 @@ impl Config {
             .unwrap_or(3)
     }
 
+    fn app_site_error_threshold() -> u32 {
+        env::var("SITE_ERROR_THRESHOLD")
+            .ok()
+            .and_then(|value| value.parse::<u32>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(5)
+    }
+
     fn app_post_check_interval_minutes() -> u32 {
         env::var("POST_CHECK_INTERVAL_MINUTES")
             .unwrap_or(String::from("15"))
@@ -135,6 +177,32 @@ impl Config {
             .unwrap_or(15)
     }
 
+    fn scheduler_tick_seconds() -> u64 {
+        env::var("CRAWLER_SCHEDULER_TICK_SECONDS")
+            .ok()
+            .and_then(|value| value.parse::<u64>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(60)
+    }
+
+    /// Defaults to `POST_CHECK_INTERVAL_MINUTES`, the cadence extraction had when it shared
+    /// a job with crawling.
+    fn post_extract_interval_minutes() -> u32 {
+        env::var("POST_EXTRACT_INTERVAL_MINUTES")
+            .ok()
+            .and_then(|value| value.parse::<u32>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or_else(|| Self::app_post_check_interval_minutes().max(1))
+    }
+
+    fn cron_timezone() -> String {
+        env::var("CRON_TIMEZONE").unwrap_or_else(|_| "UTC".into())
+    }
+
+    fn post_cleanup_cron() -> String {
+        env::var("POST_CLEANUP_CRON").unwrap_or_else(|_| "0 3 * * *".into())
+    }
+
     fn app_post_keep_latest() -> u64 {
         env::var("POST_KEEP_LATEST")
             .unwrap_or_else(|_| String::from("1000"))
@@ -189,6 +257,86 @@ impl Config {
             .unwrap_or(25)
     }
 
+    fn browser_max_tabs() -> usize {
+        env::var("CRAWLER_BROWSER_MAX_TABS")
+            .ok()
+            .and_then(|value| value.parse::<usize>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(10)
+    }
+
+    fn webhook_tick_seconds() -> u64 {
+        env::var("WEBHOOK_TICK_SECONDS")
+            .ok()
+            .and_then(|value| value.parse::<u64>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(10)
+    }
+
+    fn webhook_timeout_seconds() -> u64 {
+        env::var("WEBHOOK_TIMEOUT_SECONDS")
+            .ok()
+            .and_then(|value| value.parse::<u64>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(10)
+    }
+
+    fn webhook_max_attempts() -> i32 {
+        env::var("WEBHOOK_MAX_ATTEMPTS")
+            .ok()
+            .and_then(|value| value.parse::<i32>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(8)
+    }
+
+    fn webhook_allow_private_networks() -> bool {
+        env::var("WEBHOOK_ALLOW_PRIVATE_NETWORKS")
+            .unwrap_or_else(|_| "false".into())
+            .parse()
+            .unwrap_or(false)
+    }
+
+    fn media_directory() -> String {
+        env::var("MEDIA_DIRECTORY").unwrap_or_else(|_| "media".into())
+    }
+
+    fn screenshot_format() -> String {
+        env::var("SCREENSHOT_FORMAT").unwrap_or_else(|_| "jpeg".into())
+    }
+
+    fn screenshot_full_page() -> bool {
+        env::var("SCREENSHOT_FULL_PAGE")
+            .unwrap_or_else(|_| "false".into())
+            .parse()
+            .unwrap_or(false)
+    }
+
+    fn media_max_image_bytes() -> u64 {
+        env::var("MEDIA_MAX_IMAGE_MB")
+            .ok()
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(10)
+            * 1024
+            * 1024
+    }
+
+    fn media_max_video_bytes() -> u64 {
+        env::var("MEDIA_MAX_VIDEO_MB")
+            .ok()
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(100)
+            * 1024
+            * 1024
+    }
+
+    fn sanitize_allowed_tags() -> String {
+        env::var("SANITIZE_ALLOWED_TAGS").unwrap_or_default()
+    }
+
+    fn sanitize_allowed_attributes() -> String {
+        env::var("SANITIZE_ALLOWED_ATTRIBUTES").unwrap_or_default()
+    }
+
     pub fn log_directory() -> String {
         env::var("LOG_DIRECTORY").unwrap_or_else(|_| "logs".into())
     }
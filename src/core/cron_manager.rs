@@ -1,18 +1,77 @@
 use crate::core::logger::targets;
+use crate::core::schedule::parse_cron;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use once_cell::sync::Lazy;
+use rand::Rng;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
-use std::time::Duration;
-use tokio::time::interval;
-use tracing::error;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
 
 // Type aliases for async job function
 type CronFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
 type CronFn = Arc<dyn Fn() -> CronFuture + Send + Sync>;
 
+static JOB_REGISTRY: Lazy<JobRegistry> = Lazy::new(JobRegistry::default);
+
+/// When a job fires: a fixed period (first run right away) or a cron expression
+/// evaluated in a given timezone.
+pub enum CronSchedule {
+    Every(Duration),
+    Cron {
+        schedule: Box<cron::Schedule>,
+        timezone: Tz,
+    },
+}
+
+impl CronSchedule {
+    /// Parse a five/six-field cron expression and an IANA timezone name such as `Asia/Tehran`.
+    pub fn cron(expression: &str, timezone: &str) -> Result<Self, String> {
+        let schedule = parse_cron(expression)?;
+        let timezone = timezone
+            .parse::<Tz>()
+            .map_err(|e| format!("Invalid timezone `{timezone}`: {e}"))?;
+
+        Ok(Self::Cron {
+            schedule: Box::new(schedule),
+            timezone,
+        })
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Every(period) => format!("every {}s", period.as_secs()),
+            Self::Cron { schedule, timezone } => format!("cron {} ({timezone})", schedule.source()),
+        }
+    }
+}
+
+/// What to do when a task is due while its previous run is still in progress.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverlapPolicy {
+    /// Drop this run.
+    #[default]
+    Skip,
+    /// Run once the previous run finishes; further due runs are coalesced into that one.
+    Queue,
+    /// Start this run alongside the previous one.
+    Allow,
+}
+
 pub struct CronDefinition {
     pub name: &'static str,
-    pub interval: Duration,
+    pub schedule: CronSchedule,
+    pub overlap: OverlapPolicy,
+    /// Upper bound of a random delay added before every run, to spread load.
+    pub jitter: Duration,
     pub tasks: Vec<CronFn>,
 }
 
@@ -20,6 +79,95 @@ pub struct CronManager {
     pub definitions: Vec<CronDefinition>,
 }
 
+/// Snapshot of a registered job, as exposed by the jobs API.
+#[derive(Clone, Debug, Serialize)]
+pub struct JobStatus {
+    pub name: &'static str,
+    pub schedule: String,
+    pub overlap: OverlapPolicy,
+    pub jitter_seconds: u64,
+    pub paused: bool,
+    pub running: bool,
+    pub last_started_at: Option<DateTime<Utc>>,
+    pub last_finished_at: Option<DateTime<Utc>>,
+    pub last_duration_ms: Option<u64>,
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+/// Every job started by a `CronManager`, keyed by name.
+#[derive(Default)]
+pub struct JobRegistry {
+    jobs: RwLock<BTreeMap<&'static str, Arc<Job>>>,
+}
+
+/// A started job: its tasks, overlap state and run bookkeeping.
+pub struct Job {
+    name: &'static str,
+    schedule: String,
+    overlap: OverlapPolicy,
+    jitter: Duration,
+    slots: Vec<TaskSlot>,
+    paused: AtomicBool,
+    running: AtomicUsize,
+    runtime: Mutex<JobRuntime>,
+}
+
+#[derive(Default)]
+struct JobRuntime {
+    last_started_at: Option<DateTime<Utc>>,
+    last_finished_at: Option<DateTime<Utc>>,
+    last_duration: Option<Duration>,
+    next_run_at: Option<DateTime<Utc>>,
+}
+
+/// A task plus the state its overlap policy needs, shared across ticks.
+struct TaskSlot {
+    task: CronFn,
+    running: Arc<Semaphore>,
+    queued: AtomicBool,
+}
+
+enum Ticker {
+    Every {
+        period: Duration,
+        started: bool,
+    },
+    Cron {
+        schedule: Box<cron::Schedule>,
+        timezone: Tz,
+    },
+}
+
+impl Ticker {
+    fn new(schedule: CronSchedule) -> Self {
+        match schedule {
+            CronSchedule::Every(period) => Self::Every {
+                period,
+                started: false,
+            },
+            CronSchedule::Cron { schedule, timezone } => Self::Cron { schedule, timezone },
+        }
+    }
+
+    /// The next fire time, or `None` once the schedule has no upcoming time.
+    fn next_run(&mut self) -> Option<DateTime<Utc>> {
+        let now = Utc::now();
+        match self {
+            Self::Every { period, started } => {
+                if !*started {
+                    *started = true;
+                    return Some(now);
+                }
+                chrono::Duration::from_std(*period).ok().map(|d| now + d)
+            }
+            Self::Cron { schedule, timezone } => schedule
+                .after(&now.with_timezone(timezone))
+                .next()
+                .map(|next| next.with_timezone(&Utc)),
+        }
+    }
+}
+
 impl CronManager {
     pub fn new(definitions: Vec<CronDefinition>) -> Self {
         Self { definitions }
@@ -27,34 +175,196 @@ impl CronManager {
 
     pub fn start(self) {
         for def in self.definitions {
+            let job = Arc::new(Job {
+                name: def.name,
+                schedule: def.schedule.describe(),
+                overlap: def.overlap,
+                jitter: def.jitter,
+                slots: def
+                    .tasks
+                    .into_iter()
+                    .map(|task| TaskSlot {
+                        task,
+                        running: Arc::new(Semaphore::new(1)),
+                        queued: AtomicBool::new(false),
+                    })
+                    .collect(),
+                paused: AtomicBool::new(false),
+                running: AtomicUsize::new(0),
+                runtime: Mutex::new(JobRuntime::default()),
+            });
+            JobRegistry::global().register(job.clone());
+
+            let mut ticker = Ticker::new(def.schedule);
+
             tokio::spawn(async move {
-                let mut ticker = interval(def.interval);
-
-                loop {
-                    ticker.tick().await;
-
-                    for task in &def.tasks {
-                        let fut = task.clone()();
-                        let name = def.name;
-
-                        tokio::spawn(async move {
-                            // Async cron job execution with error visibility
-                            if let Err(e) = async {
-                                fut.await;
-                                Ok::<(), anyhow::Error>(())
-                            }
-                            .await
-                            {
-                                error!(target: targets::SYSTEM, "[cron:{name}] failed: {e}");
-                            }
-                        });
+                let name = job.name;
+
+                while let Some(next) = ticker.next_run() {
+                    job.update(|runtime| runtime.next_run_at = Some(next));
+
+                    let delay = (next - Utc::now()).to_std().unwrap_or_default();
+                    sleep(delay).await;
+
+                    if job.is_paused() {
+                        info!(target: targets::SYSTEM, "[cron:{name}] paused, skipping scheduled run");
+                        continue;
                     }
+
+                    job.fire(job.jitter);
                 }
+
+                job.update(|runtime| runtime.next_run_at = None);
+                warn!(target: targets::SYSTEM, "[cron:{name}] schedule has no upcoming runs, stopping");
             });
         }
     }
 }
 
+impl JobRegistry {
+    pub fn global() -> &'static JobRegistry {
+        &JOB_REGISTRY
+    }
+
+    fn register(&self, job: Arc<Job>) {
+        if let Ok(mut jobs) = self.jobs.write()
+            && jobs.insert(job.name, job).is_some()
+        {
+            warn!(target: targets::SYSTEM, "Cron job registered twice, keeping the latest");
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<Job>> {
+        self.jobs.read().ok()?.get(name).cloned()
+    }
+
+    pub fn list(&self) -> Vec<JobStatus> {
+        self.jobs
+            .read()
+            .map(|jobs| jobs.values().map(|job| job.status()).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Job {
+    pub fn status(&self) -> JobStatus {
+        let runtime = self.runtime.lock().unwrap_or_else(|e| e.into_inner());
+
+        JobStatus {
+            name: self.name,
+            schedule: self.schedule.clone(),
+            overlap: self.overlap,
+            jitter_seconds: self.jitter.as_secs(),
+            paused: self.is_paused(),
+            running: self.running.load(Ordering::Acquire) > 0,
+            last_started_at: runtime.last_started_at,
+            last_finished_at: runtime.last_finished_at,
+            last_duration_ms: runtime.last_duration.map(|d| d.as_millis() as u64),
+            next_run_at: runtime.next_run_at,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Acquire)
+    }
+
+    /// Stop scheduled runs; manual runs through `run_now` still work.
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Release);
+        info!(target: targets::SYSTEM, "[cron:{}] paused", self.name);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Release);
+        info!(target: targets::SYSTEM, "[cron:{}] resumed", self.name);
+    }
+
+    /// Trigger every task right away, without jitter. Returns `false` when the job's
+    /// overlap policy is `Skip` and a previous run is still in progress.
+    pub fn run_now(self: &Arc<Self>) -> bool {
+        let busy = self
+            .slots
+            .iter()
+            .any(|slot| slot.running.available_permits() == 0);
+
+        if busy && self.overlap == OverlapPolicy::Skip {
+            return false;
+        }
+
+        info!(target: targets::SYSTEM, "[cron:{}] triggered manually", self.name);
+        self.fire(Duration::ZERO);
+        true
+    }
+
+    fn fire(self: &Arc<Self>, jitter: Duration) {
+        for index in 0..self.slots.len() {
+            let job = self.clone();
+            tokio::spawn(async move {
+                sleep(random_jitter(jitter)).await;
+                job.run_slot(index).await;
+            });
+        }
+    }
+
+    async fn run_slot(&self, index: usize) {
+        let name = self.name;
+        let slot = &self.slots[index];
+
+        let _permit = match self.overlap {
+            OverlapPolicy::Allow => None,
+            OverlapPolicy::Skip => match slot.running.clone().try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    info!(target: targets::SYSTEM, "[cron:{name}#{index}] previous run still in progress, skipping");
+                    return;
+                }
+            },
+            OverlapPolicy::Queue => match slot.running.clone().try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    if slot.queued.swap(true, Ordering::AcqRel) {
+                        info!(target: targets::SYSTEM, "[cron:{name}#{index}] a run is already queued, skipping");
+                        return;
+                    }
+
+                    let permit = slot.running.clone().acquire_owned().await.ok();
+                    slot.queued.store(false, Ordering::Release);
+                    permit
+                }
+            },
+        };
+
+        self.running.fetch_add(1, Ordering::AcqRel);
+        self.update(|runtime| runtime.last_started_at = Some(Utc::now()));
+        let started = Instant::now();
+
+        // Running the job in its own task turns a panic into a JoinError instead of
+        // tearing down the scheduler, and keeps the overlap permit until it's done.
+        if let Err(e) = tokio::spawn((slot.task)()).await {
+            error!(target: targets::SYSTEM, "[cron:{name}#{index}] failed: {e}");
+        }
+
+        self.update(|runtime| {
+            runtime.last_finished_at = Some(Utc::now());
+            runtime.last_duration = Some(started.elapsed());
+        });
+        self.running.fetch_sub(1, Ordering::AcqRel);
+    }
+
+    fn update(&self, f: impl FnOnce(&mut JobRuntime)) {
+        let mut runtime = self.runtime.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut runtime);
+    }
+}
+
+fn random_jitter(max: Duration) -> Duration {
+    if max.is_zero() {
+        return Duration::ZERO;
+    }
+
+    Duration::from_millis(rand::rng().random_range(0..=max.as_millis() as u64))
+}
+
 pub fn boxed<F, Fut>(f: F) -> CronFn
 where
     F: Fn() -> Fut + Send + Sync + 'static,
@@ -7,4 +7,5 @@ pub mod logger;
 pub mod repository;
 pub mod response;
 pub mod router;
+pub mod schedule;
 pub mod state;
@@ -0,0 +1,142 @@
+use cron::Schedule;
+use std::borrow::Cow;
+use std::str::FromStr;
+use validator::ValidationError;
+
+/// Parse a cron expression. Standard five-field expressions (minute precision) are
+/// accepted next to the six/seven-field form with seconds (and year) used by `cron`.
+/// In the five-field form the day of week follows standard cron (0 or 7 is Sunday); the
+/// longer form keeps `cron`'s own numbering (1 is Sunday).
+pub fn parse_cron(expression: &str) -> Result<Schedule, String> {
+    let expression = expression.trim();
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let normalized = match fields.as_slice() {
+        [minute, hour, day, month, weekday] => {
+            format!("0 {minute} {hour} {day} {month} {}", weekday_names(weekday))
+        }
+        _ => expression.to_string(),
+    };
+
+    Schedule::from_str(&normalized)
+        .map_err(|e| format!("Invalid cron expression `{expression}`: {e}"))
+}
+
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// A standard day-of-week field as a list of day names, which `cron` reads the same way
+/// whatever its numbering. A field it can't read is returned as is for `cron` to reject.
+fn weekday_names(field: &str) -> String {
+    if field == "*" || field == "?" {
+        return field.to_string();
+    }
+
+    let mut days = [false; 7];
+    for item in field.split(',') {
+        match weekday_item(item) {
+            Some(matched) => matched.into_iter().for_each(|day| days[day % 7] = true),
+            None => return field.to_string(),
+        }
+    }
+
+    WEEKDAYS
+        .iter()
+        .zip(days)
+        .filter(|(_, matched)| *matched)
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The days (0–7, Sunday twice) one list item matches: `*`, `n`, `n-m`, each optionally
+/// followed by `/step`, where a day is a number or a three-letter name.
+fn weekday_item(item: &str) -> Option<Vec<usize>> {
+    let (range, step) = match item.split_once('/') {
+        Some((range, step)) => (range, step.parse::<usize>().ok().filter(|s| *s > 0)?),
+        None => (item, 1),
+    };
+    let (start, end) = match range.split_once('-') {
+        _ if range == "*" => (0, 6),
+        Some((start, end)) => (weekday(start)?, weekday(end)?),
+        // `n/step` runs to the end of the week.
+        None if step > 1 => (weekday(range)?, 6),
+        None => (weekday(range)?, weekday(range)?),
+    };
+
+    (start <= end).then(|| (start..=end).step_by(step).collect())
+}
+
+fn weekday(value: &str) -> Option<usize> {
+    match value.parse::<usize>() {
+        Ok(day) => (day <= 7).then_some(day),
+        Err(_) => WEEKDAYS
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(value)),
+    }
+}
+
+/// Form validator for fields holding a cron expression. A blank value clears the schedule.
+pub fn validate_cron(expression: &str) -> Result<(), ValidationError> {
+    if expression.trim().is_empty() {
+        return Ok(());
+    }
+
+    parse_cron(expression)
+        .map(|_| ())
+        .map_err(|message| ValidationError::new("cron").with_message(Cow::Owned(message)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, TimeZone, Utc, Weekday};
+
+    /// The weekdays of the next `count` runs after Saturday 2026-10-17.
+    fn weekdays(expression: &str, count: usize) -> Vec<Weekday> {
+        let start = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        parse_cron(expression)
+            .unwrap()
+            .after(&start)
+            .take(count)
+            .map(|run| run.weekday())
+            .collect()
+    }
+
+    #[test]
+    fn weekday_ranges_follow_standard_cron() {
+        use Weekday::*;
+        assert_eq!(weekdays("0 9 * * 1-5", 5), vec![Mon, Tue, Wed, Thu, Fri]);
+        assert_eq!(
+            weekdays("0 9 * * MON-FRI", 5),
+            vec![Mon, Tue, Wed, Thu, Fri]
+        );
+        assert_eq!(weekdays("0 9 * * 5-7", 3), vec![Sun, Fri, Sat]);
+    }
+
+    #[test]
+    fn sunday_is_zero_or_seven() {
+        assert_eq!(weekdays("0 9 * * 0", 2), vec![Weekday::Sun; 2]);
+        assert_eq!(weekdays("0 9 * * 7", 2), vec![Weekday::Sun; 2]);
+        assert_eq!(weekdays("* * * * 0", 1), vec![Weekday::Sun]);
+    }
+
+    #[test]
+    fn weekday_lists_and_steps() {
+        use Weekday::*;
+        assert_eq!(weekdays("0 9 * * 1,3,sat", 3), vec![Mon, Wed, Sat]);
+        assert_eq!(weekdays("0 9 * * */2", 4), vec![Sun, Tue, Thu, Sat]);
+        assert_eq!(weekdays("0 9 * * 1-5/2", 3), vec![Mon, Wed, Fri]);
+    }
+
+    #[test]
+    fn six_field_expressions_keep_cron_numbering() {
+        assert_eq!(weekdays("0 0 9 * * 1", 1), vec![Weekday::Sun]);
+    }
+
+    #[test]
+    fn invalid_expressions_are_rejected() {
+        assert!(parse_cron("0 9 * * 8").is_err());
+        assert!(parse_cron("0 9 * * 5-1").is_err());
+        assert!(parse_cron("0 9 * *").is_err());
+        assert!(validate_cron("  ").is_ok());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::features::crawler::AnyError;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Marks a selector as an XPath 1.0 expression instead of CSS, e.g. `xpath://h1[1]`.
+pub const XPATH_PREFIX: &str = "xpath:";
+
+/// Selector operations shared by every page backend (headless browser or plain HTTP),
+/// so the crawl jobs stay agnostic of how a page was loaded. Selectors are CSS, or
+/// XPath when prefixed with [`XPATH_PREFIX`]; either way they must select elements.
+#[async_trait]
+pub trait Fetcher: Send + Sync {
+    async fn get_element_text(&self, selector: &str) -> Result<String, AnyError>;
+
+    async fn get_element_html(&self, selector: &str) -> Result<String, AnyError>;
+
+    async fn get_attr(&self, selector: &str, attr: &str) -> Result<String, AnyError>;
+
+    async fn get_attrs(&self, selector: &str, attr: &str) -> Result<Vec<String>, AnyError>;
+
+    /// Attributes of every element matching `selector`, with those of its nested media elements.
+    async fn get_elements(&self, selector: &str) -> Result<Vec<ElementAttrs>, AnyError>;
+
+    async fn remove_elements(&self, selectors: Vec<String>) -> Result<(), AnyError>;
+
+    async fn wait_for_selector(&self, selector: &str, timeout: Duration) -> Result<(), AnyError>;
+
+    /// Capture the rendered page. Only backends that actually render can do this.
+    async fn screenshot(
+        &self,
+        _format: ScreenshotFormat,
+        _full_page: bool,
+    ) -> Result<Vec<u8>, AnyError> {
+        Err("Screenshots need fetch_mode=browser".into())
+    }
+}
+
+/// One matched element: its own attributes plus those of the `<source>` and `<img>`
+/// elements inside it, which is where `<picture>` and `<video>` keep their URLs.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ElementAttrs {
+    pub tag: String,
+    pub attrs: HashMap<String, String>,
+    pub nested: Vec<NestedAttrs>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct NestedAttrs {
+    pub tag: String,
+    pub attrs: HashMap<String, String>,
+}
+
+/// Tags collected into `ElementAttrs::nested`.
+pub const NESTED_MEDIA_SELECTOR: &str = "source, img";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScreenshotFormat {
+    Jpeg,
+    Png,
+}
+
+impl ScreenshotFormat {
+    /// `png` selects PNG; anything else falls back to JPEG.
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "png" => Self::Png,
+            _ => Self::Jpeg,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+        }
+    }
+}
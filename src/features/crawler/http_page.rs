@@ -0,0 +1,341 @@
+use crate::features::crawler::fetcher::{
+    ElementAttrs, Fetcher, NESTED_MEDIA_SELECTOR, NestedAttrs, XPATH_PREFIX,
+};
+use crate::features::crawler::xpath::select_xpath;
+use crate::features::crawler::{ACCEPT_LANGUAGE, AnyError, USER_AGENT};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use reqwest::header::{ACCEPT_LANGUAGE as ACCEPT_LANGUAGE_HEADER, HeaderMap, HeaderValue};
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+// One client for the whole process so connections and TLS sessions are reused.
+static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        ACCEPT_LANGUAGE_HEADER,
+        HeaderValue::from_static(ACCEPT_LANGUAGE),
+    );
+
+    Client::builder()
+        .user_agent(USER_AGENT)
+        .default_headers(headers)
+        .connect_timeout(Duration::from_secs(10))
+        .timeout(Duration::from_secs(30))
+        .build()
+        .expect("Failed to build HTTP client")
+});
+
+/// A page fetched with a plain GET request and queried with CSS or XPath selectors,
+/// for sites that render their content server-side and don't need a browser. The HTML is
+/// parsed once and every query runs against the same tree.
+pub struct HttpPage {
+    pub url: String,
+    document: Mutex<Html>,
+}
+
+impl HttpPage {
+    pub async fn new(url: &str) -> Result<HttpPage, AnyError> {
+        let html = HTTP_CLIENT
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        Ok(Self::from_html(url, html))
+    }
+
+    pub fn from_html(url: &str, html: impl Into<String>) -> HttpPage {
+        Self {
+            url: url.to_string(),
+            document: Mutex::new(Html::parse_document(&html.into())),
+        }
+    }
+
+    pub fn html(&self) -> String {
+        self.document
+            .lock()
+            .map(|document| document.html())
+            .unwrap_or_default()
+    }
+
+    fn with_document<R>(
+        &self,
+        f: impl FnOnce(&mut Html) -> Result<R, AnyError>,
+    ) -> Result<R, AnyError> {
+        let mut document = self
+            .document
+            .lock()
+            .map_err(|_| -> AnyError { "Document lock poisoned".into() })?;
+        f(&mut document)
+    }
+
+    fn first_match<R>(
+        &self,
+        selector: &str,
+        f: impl FnOnce(ElementRef) -> R,
+    ) -> Result<R, AnyError> {
+        self.with_document(|document| {
+            select_all(document, selector)?
+                .into_iter()
+                .next()
+                .map(f)
+                .ok_or_else(|| "Element not found".into())
+        })
+    }
+}
+
+/// A file fetched by `download`, with the `Content-Type` the server sent (parameters stripped).
+pub struct Download {
+    pub bytes: Vec<u8>,
+    pub content_type: Option<String>,
+}
+
+/// Fetch a file with the shared client, giving up as soon as it turns out to be larger
+/// than `max_bytes`, whether the server announces that up front or not.
+pub async fn download(url: &str, max_bytes: u64) -> Result<Download, AnyError> {
+    let mut response = HTTP_CLIENT
+        .get(url)
+        // Large videos need longer than the page timeout of the shared client.
+        .timeout(Duration::from_secs(300))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    if response.content_length().is_some_and(|len| len > max_bytes) {
+        return Err(format!("File is larger than {max_bytes} bytes").into());
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty());
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if (bytes.len() + chunk.len()) as u64 > max_bytes {
+            return Err(format!("File is larger than {max_bytes} bytes").into());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(Download {
+        bytes,
+        content_type,
+    })
+}
+
+#[async_trait]
+impl Fetcher for HttpPage {
+    async fn get_element_text(&self, selector: &str) -> Result<String, AnyError> {
+        self.first_match(selector, |element| {
+            element.text().collect::<String>().trim().to_string()
+        })
+    }
+
+    async fn get_element_html(&self, selector: &str) -> Result<String, AnyError> {
+        self.first_match(selector, |element| element.html())
+    }
+
+    async fn get_attr(&self, selector: &str, attr: &str) -> Result<String, AnyError> {
+        self.first_match(selector, |element| {
+            element.value().attr(attr).unwrap_or_default().to_string()
+        })
+    }
+
+    async fn get_attrs(&self, selector: &str, attr: &str) -> Result<Vec<String>, AnyError> {
+        self.with_document(|document| {
+            Ok(select_all(document, selector)?
+                .into_iter()
+                .filter_map(|element| element.value().attr(attr))
+                .map(str::to_string)
+                .collect())
+        })
+    }
+
+    async fn get_elements(&self, selector: &str) -> Result<Vec<ElementAttrs>, AnyError> {
+        let nested = parse_selector(NESTED_MEDIA_SELECTOR)?;
+
+        self.with_document(|document| {
+            Ok(select_all(document, selector)?
+                .into_iter()
+                .map(|element| ElementAttrs {
+                    tag: element.value().name().to_string(),
+                    attrs: attributes(element),
+                    nested: element
+                        .select(&nested)
+                        .map(|child| NestedAttrs {
+                            tag: child.value().name().to_string(),
+                            attrs: attributes(child),
+                        })
+                        .collect(),
+                })
+                .collect())
+        })
+    }
+
+    async fn remove_elements(&self, selectors: Vec<String>) -> Result<(), AnyError> {
+        self.with_document(|document| {
+            for selector in &selectors {
+                let ids: Vec<_> = select_all(document, selector)?
+                    .into_iter()
+                    .map(|element| element.id())
+                    .collect();
+                for id in ids {
+                    if let Some(mut node) = document.tree.get_mut(id) {
+                        node.detach();
+                    }
+                }
+            }
+
+            // Detached nodes stay in the tree's arena, where `Html::select` would still find
+            // them, so the page is parsed again from what's left.
+            *document = Html::parse_document(&document.html());
+            Ok(())
+        })
+    }
+
+    async fn wait_for_selector(&self, selector: &str, _timeout: Duration) -> Result<(), AnyError> {
+        // The document is static, so the element is either there already or never will be.
+        self.first_match(selector, |_| ())
+    }
+}
+
+fn attributes(element: ElementRef) -> HashMap<String, String> {
+    element
+        .value()
+        .attrs()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Every element matching a CSS selector, or an XPath expression behind [`XPATH_PREFIX`].
+fn select_all<'a>(document: &'a Html, selector: &str) -> Result<Vec<ElementRef<'a>>, AnyError> {
+    match selector.strip_prefix(XPATH_PREFIX) {
+        Some(xpath) => select_xpath(document, xpath),
+        None => Ok(document.select(&parse_selector(selector)?).collect()),
+    }
+}
+
+fn parse_selector(selector: &str) -> Result<Selector, AnyError> {
+    Selector::parse(selector).map_err(|e| format!("Invalid selector `{selector}`: {e}").into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><head><title>Example</title></head><body>
+        <nav><a href="/">Home</a></nav>
+        <article id="story">
+            <h1 class="headline"> Title  </h1>
+            <time datetime="2026-10-17T08:00:00Z">yesterday</time>
+            <p>First <b>para</b>.</p>
+            <div class="share"><a href="/share">Share</a></div>
+            <picture><source srcset="/a.webp"><img src="/a.jpg" alt="A"></picture>
+            <img class="inline" src="/b.jpg">
+        </article>
+    </body></html>"#;
+
+    fn page() -> HttpPage {
+        HttpPage::from_html("https://example.com/post", PAGE)
+    }
+
+    #[tokio::test]
+    async fn reads_text_html_and_attributes() {
+        let page = page();
+        assert_eq!(page.get_element_text("h1.headline").await.unwrap(), "Title");
+        assert_eq!(
+            page.get_element_html("#story p").await.unwrap(),
+            "<p>First <b>para</b>.</p>"
+        );
+        assert_eq!(
+            page.get_attr("time", "datetime").await.unwrap(),
+            "2026-10-17T08:00:00Z"
+        );
+        assert_eq!(page.get_attr("h1", "missing").await.unwrap(), "");
+        assert_eq!(
+            page.get_attrs("a", "href").await.unwrap(),
+            vec!["/", "/share"]
+        );
+    }
+
+    #[tokio::test]
+    async fn xpath_selectors_use_the_same_getters() {
+        let page = page();
+        assert_eq!(
+            page.get_element_text("xpath://article/h1").await.unwrap(),
+            "Title"
+        );
+        assert_eq!(
+            page.get_attr("xpath://time", "datetime").await.unwrap(),
+            "2026-10-17T08:00:00Z"
+        );
+        assert_eq!(
+            page.get_attrs("xpath://article//a", "href").await.unwrap(),
+            vec!["/share"]
+        );
+    }
+
+    #[tokio::test]
+    async fn elements_carry_their_nested_media() {
+        let elements = page().get_elements("picture, img.inline").await.unwrap();
+        assert_eq!(elements.len(), 2);
+        assert_eq!(elements[0].tag, "picture");
+        let nested: Vec<_> = elements[0]
+            .nested
+            .iter()
+            .map(|child| child.tag.as_str())
+            .collect();
+        assert_eq!(nested, vec!["source", "img"]);
+        assert_eq!(elements[1].attrs.get("src").unwrap(), "/b.jpg");
+    }
+
+    #[tokio::test]
+    async fn missing_elements_and_bad_selectors_are_errors() {
+        let page = page();
+        assert!(page.get_element_text(".nothing").await.is_err());
+        assert!(page.get_element_text("p[").await.is_err());
+        assert!(page.get_attrs(".nothing", "href").await.unwrap().is_empty());
+        assert!(
+            page.wait_for_selector("#story", Duration::from_secs(1))
+                .await
+                .is_ok()
+        );
+        assert!(
+            page.wait_for_selector(".nothing", Duration::from_secs(1))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn removed_elements_are_gone_for_later_queries() {
+        let page = page();
+        page.remove_elements(vec!["nav".into(), "xpath://div[@class='share']".into()])
+            .await
+            .unwrap();
+
+        assert!(page.get_element_text("nav").await.is_err());
+        assert!(page.get_attrs("a", "href").await.unwrap().is_empty());
+        let story = page.get_element_html("#story").await.unwrap();
+        assert!(!story.contains("Share"));
+        assert!(story.contains("<p>First <b>para</b>.</p>"));
+        assert!(!page.html().contains("Home"));
+    }
+
+    #[tokio::test]
+    async fn a_bad_removal_selector_leaves_the_page_usable() {
+        let page = page();
+        assert!(page.remove_elements(vec!["p[".into()]).await.is_err());
+        assert_eq!(page.get_element_text("h1").await.unwrap(), "Title");
+    }
+}
@@ -1,5 +1,17 @@
+pub mod fetcher;
+pub mod http_page;
+pub mod pool;
+pub mod readability;
+pub mod xpath;
+
+use crate::features::crawler::fetcher::{
+    ElementAttrs, Fetcher, NESTED_MEDIA_SELECTOR, ScreenshotFormat, XPATH_PREFIX,
+};
+use crate::features::crawler::pool::{BrowserPool, PooledTab};
+use async_trait::async_trait;
+use headless_chrome::Tab;
+use headless_chrome::browser::tab::element::Element;
 use headless_chrome::protocol::cdp::{Emulation, Page};
-use headless_chrome::{Browser as CBrowser, Tab};
 use serde_json::{json, to_string};
 use std::error::Error as StdError;
 use std::sync::Arc;
@@ -8,8 +20,36 @@ use tokio::task::spawn_blocking;
 
 pub type AnyError = Box<dyn StdError + Send + Sync>;
 
+pub const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) \
+                              AppleWebKit/537.36 (KHTML, like Gecko) \
+                              Chrome/123.0.0.0 Safari/537.36";
+
+// Chromium refuses to capture surfaces much taller than this.
+const MAX_SCREENSHOT_HEIGHT: u32 = 16_384;
+
+/// JS helper that resolves a selector the way the Rust side does: `xpath:` expressions
+/// through `document.evaluate`, anything else through `querySelectorAll`. Elements only.
+const JS_QUERY_ALL: &str = r#"function queryAll(selector) {
+    if (selector.indexOf(XPATH_PREFIX) === 0) {
+        var snapshot = document.evaluate(selector.slice(XPATH_PREFIX.length), document, null,
+            XPathResult.ORDERED_NODE_SNAPSHOT_TYPE, null);
+        var found = [];
+        for (var i = 0; i < snapshot.snapshotLength; i++) {
+            var node = snapshot.snapshotItem(i);
+            if (node.nodeType === Node.ELEMENT_NODE) {
+                found.push(node);
+            }
+        }
+        return found;
+    }
+    return Array.from(document.querySelectorAll(selector));
+}"#;
+
+// Prefer Persian, then English
+pub const ACCEPT_LANGUAGE: &str = "fa-IR,fa;q=0.9,en-US;q=0.8,en;q=0.7";
+
 pub struct Browser {
-    _browser: Arc<CBrowser>,
+    _lease: PooledTab,
     pub tab: Arc<Tab>,
     pub width: u32,
     pub height: u32,
@@ -25,10 +65,10 @@ impl Browser {
         let viewport_width = width.unwrap_or(1920);
         let viewport_height = height.unwrap_or(1080);
 
-        run_blocking_chrome_task(move || {
-            let browser = Arc::new(CBrowser::default()?);
-            let tab = browser.new_tab()?;
+        let lease = BrowserPool::global().acquire().await?;
+        let tab = lease.tab.clone();
 
+        run_blocking_chrome_task(move || {
             let set_device_metrics = Emulation::SetDeviceMetricsOverride {
                 width: viewport_width,
                 height: viewport_height,
@@ -48,23 +88,12 @@ impl Browser {
 
             tab.call_method(set_device_metrics)?;
 
-            let user_agent = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) \
-                              AppleWebKit/537.36 (KHTML, like Gecko) \
-                              Chrome/123.0.0.0 Safari/537.36";
-
-            // Prefer Persian, then English
-            let accept_language = "fa-IR,fa;q=0.9,en-US;q=0.8,en;q=0.7";
-
             // This becomes navigator.platform
             let platform = "Win32";
 
             // If your headless_chrome version has this signature:
             // fn set_user_agent(&self, ua: &str, accept_language: Option<String>, platform: Option<String>)
-            tab.set_user_agent(
-                user_agent,
-                Some(&*accept_language.to_string()),
-                Some(&*platform.to_string()),
-            )?;
+            tab.set_user_agent(USER_AGENT, Some(ACCEPT_LANGUAGE), Some(platform))?;
 
             tab.navigate_to(&target_url)?;
 
@@ -72,7 +101,7 @@ impl Browser {
             wait_for_page_load(&tab, Duration::from_secs(30))?;
 
             Ok(Self {
-                _browser: browser,
+                _lease: lease,
                 tab,
                 height: viewport_height,
                 width: viewport_width,
@@ -81,21 +110,29 @@ impl Browser {
         .await
     }
 
-    pub async fn screenshot(&self, output_path: Option<&str>) -> Result<String, AnyError> {
-        let output_file = output_path.unwrap_or("screenshot.jpeg").to_string();
+    pub async fn screenshot(
+        &self,
+        format: ScreenshotFormat,
+        full_page: bool,
+    ) -> Result<Vec<u8>, AnyError> {
         let width = self.width;
         let height = self.height;
         let tab = self.tab.clone();
 
         run_blocking_chrome_task(move || {
-            let vp = tab
-                .wait_for_element_with_custom_timeout("body", Duration::from_secs(10))?
-                .get_box_model()?
-                .margin_viewport();
+            tab.wait_for_element_with_custom_timeout("body", Duration::from_secs(10))?;
+
+            // Growing the viewport to the document height captures everything below the fold.
+            let capture_height = if full_page {
+                let metrics = tab.call_method(Page::GetLayoutMetrics(None))?;
+                (metrics.css_content_size.height.ceil() as u32).clamp(height, MAX_SCREENSHOT_HEIGHT)
+            } else {
+                height
+            };
 
             let set_device_metrics = Emulation::SetDeviceMetricsOverride {
                 width,
-                height,
+                height: capture_height,
                 device_scale_factor: 1.0,
                 mobile: false,
                 scale: None,
@@ -112,16 +149,12 @@ impl Browser {
 
             tab.call_method(set_device_metrics)?;
 
-            let jpeg_data = tab.capture_screenshot(
-                Page::CaptureScreenshotFormatOption::Jpeg,
-                None,
-                Some(vp),
-                false,
-            )?;
-
-            std::fs::write(&output_file, jpeg_data)?;
+            let (format, quality) = match format {
+                ScreenshotFormat::Jpeg => (Page::CaptureScreenshotFormatOption::Jpeg, Some(85)),
+                ScreenshotFormat::Png => (Page::CaptureScreenshotFormatOption::Png, None),
+            };
 
-            Ok(output_file)
+            Ok(tab.capture_screenshot(format, quality, None, true)?)
         })
         .await
     }
@@ -131,7 +164,7 @@ impl Browser {
         let selector = selector.to_string();
 
         run_blocking_chrome_task(move || {
-            let element = tab.find_element(&selector)?;
+            let element = find_first(&tab, &selector)?;
             let text = element.get_inner_text()?;
             Ok(text)
         })
@@ -143,7 +176,7 @@ impl Browser {
         let selector = selector.to_string();
 
         run_blocking_chrome_task(move || {
-            let element = tab.find_element(&selector)?;
+            let element = find_first(&tab, &selector)?;
             let result =
                 element.call_js_fn(r#"function() { return this.outerHTML; }"#, vec![], false)?;
             let value = result.value.ok_or("JS did not return a value")?;
@@ -159,13 +192,19 @@ impl Browser {
         let attr = attr.to_string();
 
         run_blocking_chrome_task(move || {
-            let element = tab.find_element(&selector)?;
+            let element = find_first(&tab, &selector)?;
             let result = element.call_js_fn(
                 r#"function(name) { return this.getAttribute(name); }"#,
                 vec![json!(attr)],
                 false,
             )?;
-            Ok(result.value.unwrap_or_default().to_string())
+            // A missing attribute comes back as null, which reads as an empty value.
+            Ok(result
+                .value
+                .as_ref()
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .to_string())
         })
         .await
     }
@@ -176,7 +215,7 @@ impl Browser {
         let attr = attr.to_string();
 
         run_blocking_chrome_task(move || {
-            let elements = tab.find_elements(&selector)?;
+            let elements = find_all(&tab, &selector)?;
             let mut results = Vec::new();
 
             for element in elements {
@@ -186,9 +225,7 @@ impl Browser {
                     false,
                 )?;
 
-                if let Some(s) = value.value
-                    && s.is_string()
-                {
+                if let Some(s) = value.value.as_ref().and_then(|v| v.as_str()) {
                     results.push(s.to_string());
                 }
             }
@@ -198,6 +235,46 @@ impl Browser {
         .await
     }
 
+    pub async fn get_elements(&self, selector: &str) -> Result<Vec<ElementAttrs>, AnyError> {
+        let tab = self.tab.clone();
+        let selector = selector.to_string();
+
+        run_blocking_chrome_task(move || {
+            let script = format!(
+                r#"(function(selector, nested) {{
+                    var XPATH_PREFIX = {};
+                    {}
+                    function attrs(el) {{
+                        var out = {{}};
+                        for (var i = 0; i < el.attributes.length; i++) {{
+                            out[el.attributes[i].name] = el.attributes[i].value;
+                        }}
+                        return out;
+                    }}
+                    return JSON.stringify(queryAll(selector).map(function(el) {{
+                        return {{
+                            tag: el.tagName.toLowerCase(),
+                            attrs: attrs(el),
+                            nested: Array.from(el.querySelectorAll(nested)).map(function(child) {{
+                                return {{ tag: child.tagName.toLowerCase(), attrs: attrs(child) }};
+                            }})
+                        }};
+                    }}));
+                }})({}, {});"#,
+                to_string(XPATH_PREFIX)?,
+                JS_QUERY_ALL,
+                to_string(&selector)?,
+                to_string(NESTED_MEDIA_SELECTOR)?
+            );
+
+            let result = tab.evaluate(&script, false)?;
+            let value = result.value.ok_or("JS did not return a value")?;
+            let json = value.as_str().ok_or("JS did not return a string")?;
+            Ok(serde_json::from_str(json)?)
+        })
+        .await
+    }
+
     pub async fn remove_elements(&self, selectors: Vec<String>) -> Result<(), AnyError> {
         let tab = self.tab.clone();
 
@@ -205,12 +282,16 @@ impl Browser {
             let js_array = to_string(&selectors)?;
             let script = format!(
                 r#"(function(selectors) {{
+                    var XPATH_PREFIX = {};
+                    {}
                     selectors.forEach(function(sel) {{
-                        document.querySelectorAll(sel).forEach(function(el) {{
+                        queryAll(sel).forEach(function(el) {{
                             el.remove();
                         }});
                     }});
                 }})({});"#,
+                to_string(XPATH_PREFIX)?,
+                JS_QUERY_ALL,
                 js_array
             );
 
@@ -230,14 +311,70 @@ impl Browser {
 
         run_blocking_chrome_task(move || {
             // Wait until an element matching selector appears or timeout
-            tab.wait_for_element_with_custom_timeout(&selector, timeout)?;
+            match selector.strip_prefix(XPATH_PREFIX) {
+                Some(xpath) => tab.wait_for_xpath_with_custom_timeout(xpath, timeout)?,
+                None => tab.wait_for_element_with_custom_timeout(&selector, timeout)?,
+            };
             Ok(())
         })
         .await
     }
 }
 
-async fn run_blocking_chrome_task<F, R>(task: F) -> Result<R, AnyError>
+#[async_trait]
+impl Fetcher for Browser {
+    async fn get_element_text(&self, selector: &str) -> Result<String, AnyError> {
+        Browser::get_element_text(self, selector).await
+    }
+
+    async fn get_element_html(&self, selector: &str) -> Result<String, AnyError> {
+        Browser::get_element_html(self, selector).await
+    }
+
+    async fn get_attr(&self, selector: &str, attr: &str) -> Result<String, AnyError> {
+        Browser::get_attr(self, selector, attr).await
+    }
+
+    async fn get_attrs(&self, selector: &str, attr: &str) -> Result<Vec<String>, AnyError> {
+        Browser::get_attrs(self, selector, attr).await
+    }
+
+    async fn get_elements(&self, selector: &str) -> Result<Vec<ElementAttrs>, AnyError> {
+        Browser::get_elements(self, selector).await
+    }
+
+    async fn remove_elements(&self, selectors: Vec<String>) -> Result<(), AnyError> {
+        Browser::remove_elements(self, selectors).await
+    }
+
+    async fn wait_for_selector(&self, selector: &str, timeout: Duration) -> Result<(), AnyError> {
+        Browser::wait_for_selector(self, selector, timeout).await
+    }
+
+    async fn screenshot(
+        &self,
+        format: ScreenshotFormat,
+        full_page: bool,
+    ) -> Result<Vec<u8>, AnyError> {
+        Browser::screenshot(self, format, full_page).await
+    }
+}
+
+fn find_first<'a>(tab: &'a Tab, selector: &str) -> Result<Element<'a>, AnyError> {
+    Ok(match selector.strip_prefix(XPATH_PREFIX) {
+        Some(xpath) => tab.find_element_by_xpath(xpath)?,
+        None => tab.find_element(selector)?,
+    })
+}
+
+fn find_all<'a>(tab: &'a Tab, selector: &str) -> Result<Vec<Element<'a>>, AnyError> {
+    Ok(match selector.strip_prefix(XPATH_PREFIX) {
+        Some(xpath) => tab.find_elements_by_xpath(xpath)?,
+        None => tab.find_elements(selector)?,
+    })
+}
+
+pub(crate) async fn run_blocking_chrome_task<F, R>(task: F) -> Result<R, AnyError>
 where
     F: FnOnce() -> Result<R, AnyError> + Send + 'static,
     R: Send + 'static,
@@ -0,0 +1,168 @@
+use crate::core::logger::targets;
+use crate::core::state::APP_STATE;
+use crate::features::crawler::{AnyError, run_blocking_chrome_task};
+use headless_chrome::browser::default_executable;
+use headless_chrome::{Browser as CBrowser, LaunchOptions, Tab};
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::task::spawn_blocking;
+use tracing::{info, warn};
+
+const DEFAULT_MAX_TABS: usize = 10;
+
+// The pooled browser stays alive between cron ticks, so the websocket must not be
+// dropped after the library's default 30 idle seconds.
+const IDLE_BROWSER_TIMEOUT: Duration = Duration::from_secs(60 * 60 * 24);
+
+static BROWSER_POOL: Lazy<BrowserPool> = Lazy::new(|| {
+    let max_tabs = APP_STATE
+        .get()
+        .map(|state| state.config.browser_max_tabs)
+        .unwrap_or(DEFAULT_MAX_TABS);
+
+    BrowserPool::new(max_tabs)
+});
+
+/// One long-lived Chromium process shared by every crawl job. Jobs borrow tabs from it,
+/// bounded by `max_tabs`, and a browser that fails its health check is relaunched.
+pub struct BrowserPool {
+    browser: Mutex<Option<Arc<CBrowser>>>,
+    tabs: Arc<Semaphore>,
+    max_tabs: usize,
+}
+
+/// A tab borrowed from the pool. Dropping it closes the tab and frees its slot.
+pub struct PooledTab {
+    pub tab: Arc<Tab>,
+    browser: Arc<CBrowser>,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl BrowserPool {
+    fn new(max_tabs: usize) -> Self {
+        Self {
+            browser: Mutex::new(None),
+            tabs: Arc::new(Semaphore::new(max_tabs)),
+            max_tabs,
+        }
+    }
+
+    pub fn global() -> &'static BrowserPool {
+        &BROWSER_POOL
+    }
+
+    pub fn max_tabs(&self) -> usize {
+        self.max_tabs
+    }
+
+    pub fn tabs_in_use(&self) -> usize {
+        self.max_tabs - self.tabs.available_permits()
+    }
+
+    /// Wait for a free slot, then open a fresh tab on a healthy browser.
+    pub async fn acquire(&'static self) -> Result<PooledTab, AnyError> {
+        let permit = self.tabs.clone().acquire_owned().await?;
+
+        let (browser, tab) = run_blocking_chrome_task(move || self.open_tab()).await?;
+
+        Ok(PooledTab {
+            tab,
+            browser,
+            permit: Some(permit),
+        })
+    }
+
+    fn open_tab(&self) -> Result<(Arc<CBrowser>, Arc<Tab>), AnyError> {
+        let browser = self.healthy_browser()?;
+
+        match browser.new_tab() {
+            Ok(tab) => Ok((browser, tab)),
+            Err(e) => {
+                warn!(
+                    target: targets::SYSTEM,
+                    error = %e,
+                    "Failed to open browser tab, restarting browser"
+                );
+                let browser = self.restart()?;
+                let tab = browser.new_tab()?;
+                Ok((browser, tab))
+            }
+        }
+    }
+
+    fn healthy_browser(&self) -> Result<Arc<CBrowser>, AnyError> {
+        let mut guard = self
+            .browser
+            .lock()
+            .map_err(|_| -> AnyError { "Browser pool lock poisoned".into() })?;
+
+        if let Some(browser) = guard.as_ref() {
+            match browser.get_version() {
+                Ok(_) => return Ok(browser.clone()),
+                Err(e) => warn!(
+                    target: targets::SYSTEM,
+                    error = %e,
+                    "Browser health check failed, restarting browser"
+                ),
+            }
+        }
+
+        let browser = Arc::new(launch_browser()?);
+        *guard = Some(browser.clone());
+        Ok(browser)
+    }
+
+    fn restart(&self) -> Result<Arc<CBrowser>, AnyError> {
+        let mut guard = self
+            .browser
+            .lock()
+            .map_err(|_| -> AnyError { "Browser pool lock poisoned".into() })?;
+
+        // Tabs still borrowed from the old process keep it alive until they are dropped.
+        let browser = Arc::new(launch_browser()?);
+        *guard = Some(browser.clone());
+        Ok(browser)
+    }
+}
+
+impl Drop for PooledTab {
+    fn drop(&mut self) {
+        let tab = self.tab.clone();
+        let browser = self.browser.clone();
+        let permit = self.permit.take();
+
+        let close = move || {
+            let _ = tab.close(false);
+            drop(browser);
+            drop(permit);
+        };
+
+        // Closing a tab is a blocking CDP call; keep it off the async workers.
+        match Handle::try_current() {
+            Ok(_) => {
+                spawn_blocking(close);
+            }
+            Err(_) => close(),
+        }
+    }
+}
+
+fn launch_browser() -> Result<CBrowser, AnyError> {
+    let options = LaunchOptions::default_builder()
+        .path(Some(default_executable()?))
+        .idle_browser_timeout(IDLE_BROWSER_TIMEOUT)
+        .build()?;
+
+    let browser = CBrowser::new(options)?;
+
+    info!(
+        target: targets::SYSTEM,
+        pid = browser.get_process_id(),
+        "Shared browser launched"
+    );
+
+    Ok(browser)
+}
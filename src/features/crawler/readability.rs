@@ -0,0 +1,305 @@
+use ego_tree::NodeId;
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashMap;
+
+/// Elements that never hold article text.
+const JUNK_SELECTOR: &str = "script, style, noscript, template, iframe, object, embed, form, \
+                             button, input, select, textarea, nav, aside, footer, svg, canvas, dialog";
+
+/// Elements whose text seeds the scores of their ancestors.
+const PARAGRAPH_SELECTOR: &str = "p, pre, td, blockquote, li";
+
+/// Containers that may become or join the article.
+const CANDIDATE_TAGS: [&str; 6] = ["div", "section", "article", "main", "td", "blockquote"];
+
+/// Class/id fragments of page furniture.
+const NEGATIVE_HINTS: [&str; 28] = [
+    "comment",
+    "footer",
+    "sidebar",
+    "side-bar",
+    "widget",
+    "nav",
+    "menu",
+    "share",
+    "social",
+    "related",
+    "recommend",
+    "promo",
+    "sponsor",
+    "advert",
+    "banner",
+    "masthead",
+    "breadcrumb",
+    "pagination",
+    "pager",
+    "popup",
+    "modal",
+    "cookie",
+    "subscribe",
+    "newsletter",
+    "tags",
+    "author-box",
+    "disqus",
+    "outbrain",
+];
+
+/// Class/id fragments of the article itself.
+const POSITIVE_HINTS: [&str; 10] = [
+    "article", "content", "entry", "main", "post", "story", "text", "body", "blog", "news",
+];
+
+/// A best candidate with less text than this is not taken for an article.
+const MIN_ARTICLE_CHARS: usize = 140;
+const MIN_PARAGRAPH_CHARS: usize = 25;
+
+/// Find the main content of an article page the way reader modes do: paragraphs score
+/// their ancestors by length and comma count, class/id names push containers up or down,
+/// scores are discounted by link density, and the best container plus its related
+/// siblings is returned as HTML with boilerplate removed. `None` when nothing on the
+/// page looks like an article.
+pub fn extract_main_content(html: &str) -> Option<String> {
+    let mut document = Html::parse_document(html);
+
+    detach_all(&mut document, |document| {
+        let junk = Selector::parse(JUNK_SELECTOR).expect("valid selector");
+        let all = Selector::parse("*").expect("valid selector");
+
+        let mut ids: Vec<NodeId> = document.select(&junk).map(|e| e.id()).collect();
+        ids.extend(
+            document
+                .select(&all)
+                .filter(|e| is_unlikely_candidate(*e))
+                .map(|e| e.id()),
+        );
+        ids
+    });
+
+    let scores = score_candidates(&document);
+    let (top_id, top_score) = scores
+        .iter()
+        .filter_map(|(id, score)| {
+            let element = element(&document, *id)?;
+            Some((*id, score * (1.0 - link_density(element))))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    let top = element(&document, top_id)?;
+    if text_length(top) < MIN_ARTICLE_CHARS {
+        return None;
+    }
+
+    let parts = with_siblings(&document, top, top_score, &scores);
+    detach_all(&mut document, |document| {
+        parts
+            .iter()
+            .filter_map(|id| element(document, *id))
+            .flat_map(boilerplate_within)
+            .collect()
+    });
+
+    let html: String = parts
+        .iter()
+        .filter_map(|id| element(&document, *id))
+        .map(|part| part.html())
+        .collect();
+
+    (!html.trim().is_empty()).then_some(html)
+}
+
+fn score_candidates(document: &Html) -> HashMap<NodeId, f64> {
+    let paragraphs = Selector::parse(PARAGRAPH_SELECTOR).expect("valid selector");
+    let mut scores: HashMap<NodeId, f64> = HashMap::new();
+
+    for paragraph in document.select(&paragraphs) {
+        let text = collapsed_text(paragraph);
+        let length = text.chars().count();
+        if length < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+
+        // One point for the paragraph, one per comma (Latin or Arabic), up to three for length.
+        let commas = text.chars().filter(|c| matches!(c, ',' | '،')).count();
+        let score = 1.0 + commas as f64 + (length as f64 / 100.0).min(3.0);
+
+        let ancestors = paragraph
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .filter(|e| CANDIDATE_TAGS.contains(&e.value().name()))
+            .take(3);
+        for (level, ancestor) in ancestors.enumerate() {
+            let base = scores
+                .entry(ancestor.id())
+                .or_insert_with(|| initial_score(ancestor));
+            // Parents get the full score, grandparents half, the next level a third.
+            *base += score / (level as f64 + 1.0);
+        }
+    }
+
+    scores
+}
+
+fn initial_score(element: ElementRef) -> f64 {
+    let tag_score = match element.value().name() {
+        "article" | "main" => 10.0,
+        "div" | "section" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        _ => 0.0,
+    };
+    tag_score + class_weight(element)
+}
+
+/// The top candidate plus siblings that score close to it or are substantial paragraphs,
+/// in page order.
+fn with_siblings(
+    document: &Html,
+    top: ElementRef,
+    top_score: f64,
+    scores: &HashMap<NodeId, f64>,
+) -> Vec<NodeId> {
+    let Some(parent) = top.parent().and_then(ElementRef::wrap) else {
+        return vec![top.id()];
+    };
+
+    let threshold = (top_score * 0.2).max(10.0);
+    let top_class = top.value().attr("class").unwrap_or_default();
+
+    parent
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|sibling| {
+            if sibling.id() == top.id() {
+                return true;
+            }
+
+            let mut bonus = 0.0;
+            if !top_class.is_empty() && sibling.value().attr("class") == Some(top_class) {
+                bonus += top_score * 0.2;
+            }
+            if scores
+                .get(&sibling.id())
+                .is_some_and(|score| score + bonus >= threshold)
+            {
+                return true;
+            }
+
+            if sibling.value().name() == "p" {
+                let length = text_length(*sibling);
+                let density = link_density(*sibling);
+                return (length > 80 && density < 0.25)
+                    || (length > 0 && density == 0.0 && collapsed_text(*sibling).contains(". "));
+            }
+
+            false
+        })
+        .map(|sibling| sibling.id())
+        .filter(|id| element(document, *id).is_some())
+        .collect()
+}
+
+/// Nested blocks of the chosen content that are mostly links, furniture by name, or
+/// short fragments without any media.
+fn boilerplate_within(root: ElementRef) -> Vec<NodeId> {
+    let blocks = Selector::parse("div, section, ul, ol, table, header, h1, h2, h3, h4, h5, h6")
+        .expect("valid selector");
+
+    root.select(&blocks)
+        .filter(|block| {
+            if class_weight(*block) < 0.0 {
+                return true;
+            }
+
+            let name = block.value().name();
+            if name.starts_with('h') && name.len() == 2 {
+                // Headings that are nothing but links are usually teaser titles.
+                return link_density(*block) > 0.5;
+            }
+
+            let length = text_length(*block);
+            let density = link_density(*block);
+            let has_media = block
+                .descendants()
+                .filter_map(ElementRef::wrap)
+                .any(|e| matches!(e.value().name(), "img" | "picture" | "video"));
+
+            density > 0.5 || (name != "table" && length < MIN_PARAGRAPH_CHARS && !has_media)
+        })
+        .map(|block| block.id())
+        .collect()
+}
+
+/// Collect node ids from a snapshot of the document, then remove them from the tree.
+fn detach_all(document: &mut Html, collect: impl FnOnce(&Html) -> Vec<NodeId>) {
+    let ids = collect(document);
+    for id in ids {
+        if let Some(mut node) = document.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+}
+
+fn element(document: &Html, id: NodeId) -> Option<ElementRef<'_>> {
+    document.tree.get(id).and_then(ElementRef::wrap)
+}
+
+fn is_unlikely_candidate(element: ElementRef) -> bool {
+    if matches!(element.value().name(), "html" | "body" | "article" | "main") {
+        return false;
+    }
+
+    let names = class_and_id(element);
+    !names.is_empty()
+        && NEGATIVE_HINTS.iter().any(|hint| names.contains(hint))
+        && !POSITIVE_HINTS.iter().any(|hint| names.contains(hint))
+}
+
+fn class_weight(element: ElementRef) -> f64 {
+    let names = class_and_id(element);
+    if names.is_empty() {
+        return 0.0;
+    }
+
+    let mut weight = 0.0;
+    if NEGATIVE_HINTS.iter().any(|hint| names.contains(hint)) {
+        weight -= 25.0;
+    }
+    if POSITIVE_HINTS.iter().any(|hint| names.contains(hint)) {
+        weight += 25.0;
+    }
+    weight
+}
+
+fn class_and_id(element: ElementRef) -> String {
+    let value = element.value();
+    format!(
+        "{} {}",
+        value.attr("class").unwrap_or_default(),
+        value.id().unwrap_or_default()
+    )
+    .trim()
+    .to_lowercase()
+}
+
+/// Share of the element's text that sits inside links.
+fn link_density(element: ElementRef) -> f64 {
+    let total = text_length(element);
+    if total == 0 {
+        return 0.0;
+    }
+
+    let links = Selector::parse("a").expect("valid selector");
+    let linked: usize = element.select(&links).map(text_length).sum();
+    linked as f64 / total as f64
+}
+
+fn text_length(element: ElementRef) -> usize {
+    collapsed_text(element).chars().count()
+}
+
+fn collapsed_text(element: ElementRef) -> String {
+    element
+        .text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
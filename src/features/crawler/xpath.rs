@@ -0,0 +1,149 @@
+use crate::features::crawler::AnyError;
+use ego_tree::NodeId;
+use scraper::node::Node;
+use scraper::{ElementRef, Html};
+use sxd_document::Package;
+use sxd_document::dom::{Document, Element};
+use sxd_xpath::{Context, Factory, Value};
+
+/// Attribute that ties each mirrored XML element back to its node in the HTML tree.
+const NODE_INDEX_ATTR: &str = "_crawler_node";
+
+/// Evaluate an XPath 1.0 expression against a parsed HTML page and return the matching
+/// elements in document order. Expressions selecting attributes or text are rejected,
+/// since the browser backend can only return elements too.
+pub fn select_xpath<'a>(
+    document: &'a Html,
+    expression: &str,
+) -> Result<Vec<ElementRef<'a>>, AnyError> {
+    let xpath = Factory::new()
+        .build(expression)
+        .map_err(|e| format!("Invalid XPath `{expression}`: {e}"))?
+        .ok_or_else(|| format!("Invalid XPath `{expression}`: empty expression"))?;
+
+    // sxd-xpath works on its own DOM, so the page is mirrored into one first.
+    let package = Package::new();
+    let xml = package.as_document();
+    let mut nodes = Vec::new();
+    mirror(&xml, None, document.root_element(), &mut nodes);
+
+    let value = xpath
+        .evaluate(&Context::new(), xml.root())
+        .map_err(|e| format!("XPath `{expression}` failed: {e}"))?;
+    let Value::Nodeset(matches) = value else {
+        return Err(format!("XPath `{expression}` does not select elements").into());
+    };
+
+    matches
+        .document_order()
+        .into_iter()
+        .map(|node| {
+            node.element()
+                .and_then(|element| element.attribute_value(NODE_INDEX_ATTR))
+                .and_then(|index| index.parse::<usize>().ok())
+                .and_then(|index| nodes.get(index))
+                .and_then(|id| document.tree.get(*id))
+                .and_then(ElementRef::wrap)
+                .ok_or_else(|| format!("XPath `{expression}` does not select elements").into())
+        })
+        .collect()
+}
+
+fn mirror<'d>(
+    xml: &Document<'d>,
+    parent: Option<Element<'d>>,
+    element: ElementRef,
+    nodes: &mut Vec<NodeId>,
+) {
+    let mirrored = xml.create_element(element.value().name());
+    for (name, value) in element.value().attrs() {
+        // XML is stricter about names than HTML; attributes it can't hold are left out.
+        if is_xml_name(name) {
+            mirrored.set_attribute_value(name, value);
+        }
+    }
+    mirrored.set_attribute_value(NODE_INDEX_ATTR, &nodes.len().to_string());
+    nodes.push(element.id());
+
+    match parent {
+        Some(parent) => parent.append_child(mirrored),
+        None => xml.root().append_child(mirrored),
+    }
+
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => mirrored.append_child(xml.create_text(text)),
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    mirror(xml, Some(mirrored), child, nodes);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_xml_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body>
+        <div class="post" data-id="1"><h2>First</h2><a href="/one">one</a></div>
+        <div class="post" data-id="2" @click="open"><h2>Second</h2><a href="/two">two</a></div>
+        <p>Published <time datetime="2026-10-17">today</time></p>
+    </body></html>"#;
+
+    fn texts(expression: &str) -> Vec<String> {
+        let document = Html::parse_document(PAGE);
+        select_xpath(&document, expression)
+            .unwrap()
+            .into_iter()
+            .map(|element| element.text().collect::<String>())
+            .collect()
+    }
+
+    #[test]
+    fn selects_elements_in_document_order() {
+        assert_eq!(texts("//div[@class='post']/h2"), ["First", "Second"]);
+        assert_eq!(texts("(//a)[last()] | //h2[1]"), ["First", "Second", "two"]);
+    }
+
+    #[test]
+    fn predicates_see_attributes_and_text() {
+        assert_eq!(texts("//div[@data-id='2']/a"), ["two"]);
+        assert_eq!(texts("//p[contains(., 'Published')]/time"), ["today"]);
+    }
+
+    #[test]
+    fn attributes_xml_cannot_hold_are_skipped() {
+        assert_eq!(texts("//div[h2='Second']/a"), ["two"]);
+    }
+
+    #[test]
+    fn no_match_is_empty() {
+        assert!(texts("//article").is_empty());
+    }
+
+    #[test]
+    fn non_element_results_are_rejected() {
+        let document = Html::parse_document(PAGE);
+        assert!(select_xpath(&document, "//a/@href").is_err());
+        assert!(select_xpath(&document, "//h2/text()").is_err());
+        assert!(select_xpath(&document, "count(//a)").is_err());
+    }
+
+    #[test]
+    fn invalid_expressions_are_rejected() {
+        let document = Html::parse_document(PAGE);
+        assert!(select_xpath(&document, "//div[").is_err());
+        assert!(select_xpath(&document, "").is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use crate::core::cron_manager::JobRegistry;
+use crate::core::response::{json_error, json_success};
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::Response;
+
+pub struct JobController;
+
+impl JobController {
+    // GET /jobs
+    pub async fn list() -> Response {
+        json_success(JobRegistry::global().list())
+    }
+
+    // GET /jobs/{name}
+    pub async fn show(Path(name): Path<String>) -> Response {
+        let Some(job) = JobRegistry::global().get(&name) else {
+            return json_error(StatusCode::NOT_FOUND, "Job not found");
+        };
+
+        json_success(job.status())
+    }
+
+    // POST /jobs/{name}/run
+    pub async fn run(Path(name): Path<String>) -> Response {
+        let Some(job) = JobRegistry::global().get(&name) else {
+            return json_error(StatusCode::NOT_FOUND, "Job not found");
+        };
+
+        if !job.run_now() {
+            return json_error(StatusCode::CONFLICT, "Job is already running");
+        }
+
+        json_success(job.status())
+    }
+
+    // POST /jobs/{name}/pause
+    pub async fn pause(Path(name): Path<String>) -> Response {
+        let Some(job) = JobRegistry::global().get(&name) else {
+            return json_error(StatusCode::NOT_FOUND, "Job not found");
+        };
+
+        job.pause();
+        json_success(job.status())
+    }
+
+    // POST /jobs/{name}/resume
+    pub async fn resume(Path(name): Path<String>) -> Response {
+        let Some(job) = JobRegistry::global().get(&name) else {
+            return json_error(StatusCode::NOT_FOUND, "Job not found");
+        };
+
+        job.resume();
+        json_success(job.status())
+    }
+}
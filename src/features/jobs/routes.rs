@@ -0,0 +1,25 @@
+use crate::features::jobs::controller::JobController;
+use crate::middleware::auth::auth;
+use crate::middleware::is_admin::is_admin;
+use crate::utility::state::app_state;
+use axum::routing::{get, post};
+use axum::{Router, middleware};
+
+pub fn jobs_route() -> (&'static str, Router) {
+    let state = app_state();
+
+    let middleware_auth = middleware::from_fn_with_state(state.clone(), auth);
+    let middleware_is_admin = middleware::from_fn_with_state(state.clone(), is_admin);
+
+    (
+        "api/v1/jobs",
+        Router::new()
+            .route("/", get(JobController::list))
+            .route("/{name}", get(JobController::show))
+            .route("/{name}/run", post(JobController::run))
+            .route("/{name}/pause", post(JobController::pause))
+            .route("/{name}/resume", post(JobController::resume))
+            .route_layer(middleware_is_admin)
+            .route_layer(middleware_auth),
+    )
+}
@@ -1,4 +1,6 @@
 pub mod crawler;
 pub mod home;
+pub mod jobs;
 pub mod sites;
 pub mod users;
+pub mod webhooks;
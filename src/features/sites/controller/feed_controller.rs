@@ -0,0 +1,112 @@
+use crate::core::response::json_error;
+use crate::features::sites::controller::site_controller::SiteController;
+use crate::features::sites::dto::feed_params::FeedParams;
+use crate::features::sites::model::posts;
+use crate::features::sites::repository::post_repository::PostRepository;
+use crate::features::sites::utility::feed_writer::{Feed, FeedFormat, render};
+use crate::features::users::model::{api_key, user};
+use crate::features::users::service::srv_api_key::ServiceApiKey;
+use crate::utility::url::url;
+use axum::extract::{Path, Query};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use sea_orm::DbErr;
+
+/// Stem of the aggregated feed of an API key, e.g. `/feeds/all.rss`.
+pub const ALL_POSTS_FEED: &str = "all";
+
+pub struct FeedController;
+
+impl FeedController {
+    // GET /feeds/sites/:site_id.{rss,atom,json}?token=
+    pub async fn site(Path(file): Path<String>, Query(params): Query<FeedParams>) -> Response {
+        let Some((site_id, format)) = FeedFormat::split(&file)
+            .and_then(|(stem, format)| Some((stem.parse::<i64>().ok()?, format)))
+        else {
+            return json_error(StatusCode::NOT_FOUND, "Feed not found".to_string());
+        };
+
+        let (user, _api_key) = match Self::authorize(&params).await {
+            Ok(owner) => owner,
+            Err(resp) => return resp,
+        };
+
+        let site = match SiteController::check_access(site_id, &user).await {
+            Ok(site) => site,
+            Err(resp) => return resp,
+        };
+
+        let posts = PostRepository::feed_by_site(site.id, params.limit()).await;
+        Self::respond(
+            format,
+            site.name,
+            site.url,
+            format!("feeds/sites/{file}"),
+            posts,
+        )
+    }
+
+    // GET /feeds/all.{rss,atom,json}?token=
+    pub async fn api_key(Path(file): Path<String>, Query(params): Query<FeedParams>) -> Response {
+        let Some((ALL_POSTS_FEED, format)) = FeedFormat::split(&file) else {
+            return json_error(StatusCode::NOT_FOUND, "Feed not found".to_string());
+        };
+
+        let (user, api_key) = match Self::authorize(&params).await {
+            Ok(owner) => owner,
+            Err(resp) => return resp,
+        };
+
+        let posts = PostRepository::feed_by_api_key(api_key.id, params.limit()).await;
+        Self::respond(
+            format,
+            format!("{}: all sites", user.name),
+            url("/"),
+            format!("feeds/{file}"),
+            posts,
+        )
+    }
+
+    async fn authorize(params: &FeedParams) -> Result<(user::Model, api_key::Model), Response> {
+        let Some(token) = params.token.as_deref().filter(|token| !token.is_empty()) else {
+            return Err(json_error(
+                StatusCode::UNAUTHORIZED,
+                "Missing feed token".to_string(),
+            ));
+        };
+
+        ServiceApiKey::feed_auth(token)
+            .await
+            .ok_or_else(|| json_error(StatusCode::UNAUTHORIZED, "Invalid feed token".to_string()))
+    }
+
+    fn respond(
+        format: FeedFormat,
+        title: String,
+        home_url: String,
+        path: String,
+        posts: Result<Vec<posts::Model>, DbErr>,
+    ) -> Response {
+        let posts = match posts {
+            Ok(posts) => posts,
+            Err(e) => return json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        };
+
+        let feed = Feed {
+            title,
+            home_url,
+            self_url: url(&path),
+            posts: &posts,
+        };
+
+        (
+            [
+                (header::CONTENT_TYPE, format.content_type()),
+                // The URL carries a token, so shared caches must not keep it.
+                (header::CACHE_CONTROL, "private, max-age=300"),
+            ],
+            render(format, &feed),
+        )
+            .into_response()
+    }
+}
@@ -0,0 +1,45 @@
+use crate::core::response::json_error;
+use crate::features::sites::utility::media_store::object_path;
+use axum::extract::Path;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use std::io::ErrorKind;
+
+pub struct MediaController;
+
+impl MediaController {
+    // GET /media/:file
+    pub async fn show(Path(file): Path<String>) -> Response {
+        let Some(path) = object_path(&file) else {
+            return json_error(StatusCode::NOT_FOUND, "Media not found".to_string());
+        };
+
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => {
+                let mime = mime_guess::from_path(&path).first_or_octet_stream();
+                (
+                    [
+                        (header::CONTENT_TYPE, mime.to_string()),
+                        // The name is the content hash, so a file never changes.
+                        (
+                            header::CACHE_CONTROL,
+                            "public, max-age=31536000, immutable".to_string(),
+                        ),
+                        (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+                        // Mirrored SVGs come from third parties; never let them run scripts here.
+                        (
+                            header::CONTENT_SECURITY_POLICY,
+                            "default-src 'none'; style-src 'unsafe-inline'; sandbox".to_string(),
+                        ),
+                    ],
+                    bytes,
+                )
+                    .into_response()
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                json_error(StatusCode::NOT_FOUND, "Media not found".to_string())
+            }
+            Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+}
@@ -1,2 +1,4 @@
+pub mod feed_controller;
+pub mod media_controller;
 pub mod post_controller;
 pub mod site_controller;
@@ -1,13 +1,35 @@
+use crate::core::dto::pagination::PaginationParams;
 use crate::core::response::{json_error, json_success};
-use crate::features::sites::dto::list_params::PostListParams;
+use crate::features::sites::controller::site_controller::SiteController;
+use crate::features::sites::dto::list_params::{PostListParams, PostSort, PostStreamParams};
+use crate::features::sites::dto::post_search::PostSearchParams;
+use crate::features::sites::dto::revision_diff::{RevisionDiff, RevisionDiffParams};
+use crate::features::sites::model::posts;
+use crate::features::sites::repository::post_media_repository::PostMediaRepository;
 use crate::features::sites::repository::post_repository::PostRepository;
+use crate::features::sites::repository::post_revision_repository::PostRevisionRepository;
+use crate::features::sites::repository::post_search_repository::{
+    PostSearchRepository, SearchScope,
+};
+use crate::features::sites::service::post_stream::{Cursor, PostChange, PostStream};
+use crate::features::sites::utility::html_sanitizer::sanitize_html;
+use crate::features::sites::utility::screenshot_store::{media_path, remove_screenshot};
+use crate::features::sites::utility::search_query::match_expression;
 use crate::features::sites::validation::post_form::{PostForm, PostFormCreate};
 use crate::features::users::service::api_key_user::ApiKey;
 use crate::features::users::service::auth_user::AuthUser;
+use crate::features::users::utility::stream_token::StreamToken;
 use axum::Form;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
 use axum::extract::{Path, Query};
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::{IntoResponse, Response};
+use chrono::{NaiveTime, Utc};
+use futures::StreamExt;
+use std::convert::Infallible;
+use std::io::ErrorKind;
+use std::time::Duration;
 use validator::Validate;
 
 pub struct PostController;
@@ -15,8 +37,8 @@ pub struct PostController;
 impl PostController {
     // GET /posts
     pub async fn list(Query(p): Query<PostListParams>) -> impl IntoResponse {
-        let (page, per_page, post_id) = Self::req_params(p);
-        match PostRepository::list(page, per_page, post_id).await {
+        let (page, per_page, post_id, sort) = Self::req_params(p);
+        match PostRepository::list(page, per_page, post_id, sort).await {
             Ok(items) => json_success(items),
             Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
         }
@@ -27,8 +49,8 @@ impl PostController {
         Path(site_id): Path<i64>,
         Query(p): Query<PostListParams>,
     ) -> impl IntoResponse {
-        let (page, per_page, post_id) = Self::req_params(p);
-        match PostRepository::list_by_site(site_id, page, per_page, post_id).await {
+        let (page, per_page, post_id, sort) = Self::req_params(p);
+        match PostRepository::list_by_site(site_id, page, per_page, post_id, sort).await {
             Ok(items) => json_success(items),
             Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
         }
@@ -39,8 +61,8 @@ impl PostController {
         Query(p): Query<PostListParams>,
         AuthUser(user): AuthUser,
     ) -> impl IntoResponse {
-        let (page, per_page, post_id) = Self::req_params(p);
-        match PostRepository::list_by_user(user.id, page, per_page, post_id).await {
+        let (page, per_page, post_id, sort) = Self::req_params(p);
+        match PostRepository::list_by_user(user.id, page, per_page, post_id, sort).await {
             Ok(items) => json_success(items),
             Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
         }
@@ -51,13 +73,135 @@ impl PostController {
         Query(p): Query<PostListParams>,
         ApiKey(api_key): ApiKey,
     ) -> impl IntoResponse {
-        let (page, per_page, post_id) = Self::req_params(p);
-        match PostRepository::list_by_api_key(api_key.id, page, per_page, post_id).await {
+        let (page, per_page, post_id, sort) = Self::req_params(p);
+        match PostRepository::list_by_api_key(api_key.id, page, per_page, post_id, sort).await {
             Ok(items) => json_success(items),
             Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
         }
     }
 
+    // GET /posts/search?q=
+    pub async fn search(
+        Query(p): Query<PostSearchParams>,
+        ApiKey(api_key): ApiKey,
+        AuthUser(user): AuthUser,
+    ) -> Response {
+        let Some(expression) = match_expression(&p.q) else {
+            return json_error(StatusCode::BAD_REQUEST, "Missing search terms".to_string());
+        };
+
+        let scope = SearchScope {
+            api_key_id: (!user.is_admin).then_some(api_key.id),
+            site_id: p.site_id,
+            from: p.from.map(|date| date.and_time(NaiveTime::MIN)),
+            to: p
+                .to
+                .and_then(|date| date.succ_opt())
+                .map(|date| date.and_time(NaiveTime::MIN)),
+        };
+
+        let (page, per_page) = (p.pagination.page(), p.pagination.per_page());
+        match PostSearchRepository::search(&expression, &scope, page, per_page).await {
+            Ok(items) => json_success(items),
+            Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+
+    // POST /posts/stream/token
+    pub async fn stream_token(ApiKey(api_key): ApiKey) -> Response {
+        let (token, expires_at) = StreamToken::issue(&api_key, Utc::now());
+        json_success(serde_json::json!({
+            "token": token,
+            "expires_at": expires_at,
+        }))
+    }
+
+    // GET /posts/stream (SSE)
+    pub async fn stream(
+        headers: HeaderMap,
+        Query(p): Query<PostStreamParams>,
+        ApiKey(api_key): ApiKey,
+        AuthUser(user): AuthUser,
+    ) -> Response {
+        // Browsers resend the last event id on reconnect; it wins over the query string.
+        let after = headers
+            .get("Last-Event-ID")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<i64>().ok())
+            .or(p.post_id);
+
+        let changes = match PostStream::subscribe(&user, &api_key, after).await {
+            Ok(changes) => changes,
+            Err(e) => return json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        };
+
+        let mut cursor = Cursor::new(after);
+        let events = changes.map(move |change| {
+            let event = Event::default()
+                .id(cursor.advance(&change).to_string())
+                .event(change.kind.as_str())
+                .json_data(&*change.post)
+                .unwrap_or_default();
+            Ok::<_, Infallible>(event)
+        });
+
+        Sse::new(events)
+            .keep_alive(KeepAlive::default())
+            .into_response()
+    }
+
+    // GET /posts/stream/ws (WebSocket)
+    pub async fn stream_ws(
+        ws: WebSocketUpgrade,
+        Query(p): Query<PostStreamParams>,
+        ApiKey(api_key): ApiKey,
+        AuthUser(user): AuthUser,
+    ) -> Response {
+        let changes = match PostStream::subscribe(&user, &api_key, p.post_id).await {
+            Ok(changes) => changes,
+            Err(e) => return json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        };
+
+        ws.on_upgrade(move |socket| Self::forward(socket, changes, Cursor::new(p.post_id)))
+    }
+
+    /// Send each change as `{"id", "event", "post"}` until either side goes away.
+    async fn forward(
+        mut socket: WebSocket,
+        changes: impl futures::Stream<Item = PostChange>,
+        mut cursor: Cursor,
+    ) {
+        let mut changes = std::pin::pin!(changes);
+        let mut ping = tokio::time::interval(Duration::from_secs(30));
+        ping.tick().await;
+
+        loop {
+            let message = tokio::select! {
+                change = changes.next() => {
+                    // Lagged behind: close so the client resumes from its last id.
+                    let Some(change) = change else { break };
+                    let frame = serde_json::json!({
+                        "id": cursor.advance(&change),
+                        "event": change.kind.as_str(),
+                        "post": &*change.post,
+                    });
+                    Message::Text(frame.to_string().into())
+                }
+                _ = ping.tick() => Message::Ping(Default::default()),
+                incoming = socket.recv() => match incoming {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                    Some(Ok(_)) => continue,
+                },
+            };
+
+            if socket.send(message).await.is_err() {
+                return;
+            }
+        }
+
+        let _ = socket.send(Message::Close(None)).await;
+    }
+
     // POST /posts
     pub async fn create(Form(form): Form<PostFormCreate>) -> Response {
         if let Err(e) = form.validate() {
@@ -72,10 +216,11 @@ impl PostController {
     }
 
     // PUT /posts/:id
-    pub async fn update(Path(post_id): Path<i64>, Form(form): Form<PostForm>) -> Response {
+    pub async fn update(Path(post_id): Path<i64>, Form(mut form): Form<PostForm>) -> Response {
         if let Err(e) = form.validate() {
             return json_error(StatusCode::BAD_REQUEST, e.to_string());
         }
+        form.body = form.body.map(|body| sanitize_html(&body, None));
 
         match PostRepository::update(post_id, form).await {
             Ok(Some(post)) => json_success(post),
@@ -87,7 +232,7 @@ impl PostController {
     // GET /posts/:id
     pub async fn show(Path(post_id): Path<i64>) -> Response {
         match PostRepository::find_by_id(post_id).await {
-            Ok(Some(post)) => json_success(post),
+            Ok(Some(post)) => Self::with_media(post).await,
             Ok(None) => json_error(StatusCode::NOT_FOUND, "Post not found".to_string()),
             Err(e) => json_error(StatusCode::BAD_REQUEST, e.to_string()),
         }
@@ -96,7 +241,7 @@ impl PostController {
     // GET /posts/by-url/:url
     pub async fn show_by_url(Path(url): Path<String>) -> Response {
         match PostRepository::find_by_url(&url).await {
-            Ok(Some(post)) => json_success(post),
+            Ok(Some(post)) => Self::with_media(post).await,
             Ok(None) => json_error(StatusCode::NOT_FOUND, "Post not found".to_string()),
             Err(e) => json_error(StatusCode::BAD_REQUEST, e.to_string()),
         }
@@ -105,14 +250,84 @@ impl PostController {
     // DELETE /posts/:id
     pub async fn delete(Path(post_id): Path<i64>) -> Response {
         match PostRepository::delete(post_id).await {
-            Ok(result) => json_success(result),
+            Ok(post) => {
+                // Best effort: the cleanup job sweeps up screenshots left behind.
+                if let Some(path) = post.screenshot_path {
+                    let _ = remove_screenshot(&path).await;
+                }
+                json_success(true)
+            }
             Err(e) => json_error(StatusCode::BAD_REQUEST, e),
         }
     }
 
-    fn req_params(p: PostListParams) -> (u64, u64, Option<i64>) {
+    // GET /posts/:id/revisions
+    pub async fn revisions(
+        Path(post_id): Path<i64>,
+        Query(p): Query<PaginationParams>,
+    ) -> Response {
+        match PostRevisionRepository::list_by_post(post_id, p.page(), p.per_page()).await {
+            Ok(items) => json_success(items),
+            Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+
+    // GET /posts/:id/revisions/diff?from=&to=
+    pub async fn revision_diff(
+        Path(post_id): Path<i64>,
+        Query(p): Query<RevisionDiffParams>,
+    ) -> Response {
+        let from = PostRevisionRepository::find(post_id, p.from).await;
+        let to = PostRevisionRepository::find(post_id, p.to).await;
+
+        match (from, to) {
+            (Ok(Some(from)), Ok(Some(to))) => json_success(RevisionDiff::between(&from, &to)),
+            (Err(e), _) | (_, Err(e)) => {
+                json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+            }
+            _ => json_error(StatusCode::NOT_FOUND, "Revision not found".to_string()),
+        }
+    }
+
+    // GET /posts/:id/screenshot
+    pub async fn screenshot(AuthUser(user): AuthUser, Path(post_id): Path<i64>) -> Response {
+        let post = match PostRepository::find_by_id(post_id).await {
+            Ok(Some(post)) => post,
+            Ok(None) => return json_error(StatusCode::NOT_FOUND, "Post not found".to_string()),
+            Err(e) => return json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        };
+
+        if let Err(resp) = SiteController::check_access(post.site_id, &user).await {
+            return resp;
+        }
+
+        let Some(relative) = post.screenshot_path else {
+            return json_error(StatusCode::NOT_FOUND, "Screenshot not found".to_string());
+        };
+
+        let path = media_path(&relative);
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => {
+                let mime = mime_guess::from_path(&path).first_or_octet_stream();
+                ([(header::CONTENT_TYPE, mime.to_string())], bytes).into_response()
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                json_error(StatusCode::NOT_FOUND, "Screenshot not found".to_string())
+            }
+            Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+
+    async fn with_media(post: posts::Model) -> Response {
+        match PostMediaRepository::attach(post).await {
+            Ok(post) => json_success(post),
+            Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+
+    fn req_params(p: PostListParams) -> (u64, u64, Option<i64>, PostSort) {
         let page: u64 = p.pagination.page();
         let per_page: u64 = p.pagination.per_page();
-        (page, per_page, p.post_id)
+        (page, per_page, p.post_id, p.sort)
     }
 }
@@ -1,17 +1,34 @@
 use crate::core::dto::pagination::PaginationParams;
-use crate::core::response::{json_error, json_success};
+use crate::core::response::{ApiResponse, json_error, json_success};
+use crate::features::sites::dto::crawl_summary::{CrawlParams, CrawlSummary};
+use crate::features::sites::dto::site_preview::PreviewParams;
+use crate::features::sites::jobs::check_new_post::crawl_site;
+use crate::features::sites::jobs::get_post_content::get_post_content_for_site;
 use crate::features::sites::model::site::Model;
+use crate::features::sites::repository::crawl_run_repository::CrawlRunRepository;
 use crate::features::sites::repository::site_repository::SiteRepository;
+use crate::features::sites::service::site_preview::{
+    DEFAULT_PREVIEW_LIMIT, MAX_PREVIEW_LIMIT, SitePreviewService,
+};
+use crate::features::sites::utility::crawl_schedule::next_crawl_at;
+use crate::features::sites::utility::screenshot_store::remove_site_screenshots;
+use crate::features::sites::utility::site_lock::{SiteLock, SiteWork};
 use crate::features::sites::validation::site_form::SiteForm;
 use crate::features::users::model::user;
 use crate::features::users::service::api_key_user::ApiKey;
 use crate::features::users::service::auth_user::AuthUser;
-use axum::Form;
 use axum::extract::{Path, Query};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use axum::{Form, Json};
+use chrono::Utc;
+use std::time::Duration;
+use tokio::time::timeout;
 use validator::Validate;
 
+// On-demand crawls and previews must answer within the router's 30 s request timeout.
+const INLINE_CRAWL_BUDGET: Duration = Duration::from_secs(25);
+
 pub struct SiteController;
 
 impl SiteController {
@@ -135,18 +152,139 @@ impl SiteController {
         };
 
         match SiteRepository::delete(site_id).await {
-            Ok(result) => json_success(result),
+            Ok(result) => {
+                // Best effort: the cleanup job sweeps up screenshots left behind.
+                let _ = remove_site_screenshots(site_id).await;
+                json_success(result)
+            }
             Err(e) => json_error(StatusCode::BAD_REQUEST, e),
         }
     }
 
+    // GET /sites/:id/runs
+    pub async fn list_runs(
+        AuthUser(user): AuthUser,
+        Path(site_id): Path<i64>,
+        Query(p): Query<PaginationParams>,
+    ) -> Response {
+        if let Err(resp) = Self::check_access(site_id, &user).await {
+            return resp;
+        }
+
+        match CrawlRunRepository::list_by_site(site_id, p.page(), p.per_page()).await {
+            Ok(items) => json_success(items),
+            Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+
+    // POST /sites/:id/crawl
+    pub async fn crawl(
+        AuthUser(user): AuthUser,
+        Path(site_id): Path<i64>,
+        Query(p): Query<CrawlParams>,
+    ) -> Response {
+        let site = match Self::check_access(site_id, &user).await {
+            Ok(site) => site,
+            Err(resp) => return resp,
+        };
+
+        // One run per site at a time, shared with the scheduled jobs; this also keeps
+        // repeated requests from piling up background tasks.
+        let busy = || {
+            json_error(
+                StatusCode::CONFLICT,
+                "A crawl is already running for this site".to_string(),
+            )
+        };
+        let Some(crawl_lock) = SiteLock::try_acquire(SiteWork::Crawl, site_id) else {
+            return busy();
+        };
+        let extract_lock = if p.extract.unwrap_or(false) {
+            let Some(lock) = SiteLock::try_acquire(SiteWork::Extract, site_id) else {
+                return busy();
+            };
+            Some(lock)
+        } else {
+            None
+        };
+
+        // Claim the site's slot so the scheduler doesn't crawl it again straight after.
+        if let Err(e) =
+            SiteRepository::schedule_next(site_id, next_crawl_at(&site, Utc::now().naive_utc()))
+                .await
+        {
+            return json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+        }
+
+        // The crawl runs in its own task so it still completes (and records its run) if it
+        // outlives the request timeout. The locks go with it.
+        let task = tokio::spawn(async move {
+            let crawl = crawl_site(site).await;
+            drop(crawl_lock);
+            let extraction = match extract_lock {
+                Some(_lock) => Some(get_post_content_for_site(site_id).await),
+                None => None,
+            };
+
+            CrawlSummary {
+                site_id,
+                finished: true,
+                crawl: Some(crawl),
+                extraction,
+            }
+        });
+
+        match timeout(INLINE_CRAWL_BUDGET, task).await {
+            Ok(Ok(summary)) => json_success(summary),
+            Ok(Err(e)) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+            Err(_) => (
+                StatusCode::ACCEPTED,
+                Json(ApiResponse::success(CrawlSummary {
+                    site_id,
+                    ..Default::default()
+                })),
+            )
+                .into_response(),
+        }
+    }
+
+    // POST /sites/preview
+    pub async fn preview(
+        AuthUser(_user): AuthUser,
+        Query(p): Query<PreviewParams>,
+        Form(form): Form<SiteForm>,
+    ) -> Response {
+        if let Err(e) = form.validate() {
+            return json_error(StatusCode::BAD_REQUEST, e.to_string());
+        }
+
+        let limit = p
+            .limit
+            .unwrap_or(DEFAULT_PREVIEW_LIMIT)
+            .clamp(1, MAX_PREVIEW_LIMIT);
+
+        match timeout(
+            INLINE_CRAWL_BUDGET,
+            SitePreviewService::preview(&form, limit),
+        )
+        .await
+        {
+            Ok(Ok(preview)) => json_success(preview),
+            Ok(Err(e)) => json_error(StatusCode::UNPROCESSABLE_ENTITY, e),
+            Err(_) => json_error(
+                StatusCode::GATEWAY_TIMEOUT,
+                "Preview timed out; try a smaller limit",
+            ),
+        }
+    }
+
     fn check_update(form: &SiteForm) -> Result<(), (StatusCode, String)> {
         form.validate()
             .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
         Ok(())
     }
 
-    async fn check_access(site_id: i64, user: &user::Model) -> Result<Model, Response> {
+    pub(crate) async fn check_access(site_id: i64, user: &user::Model) -> Result<Model, Response> {
         let site = match SiteRepository::find_by_id(site_id).await {
             Ok(Some(site)) => site,
             Ok(None) => {
@@ -1,34 +1,58 @@
-use crate::core::cron_manager::{CronDefinition, CronManager, boxed};
+use crate::core::cron_manager::{CronDefinition, CronManager, CronSchedule, OverlapPolicy, boxed};
+use crate::core::logger::targets;
 use crate::core::state::AppState;
 use crate::features::sites::jobs::check_new_post::check_new_post;
 use crate::features::sites::jobs::cleanup_posts::cleanup_old_posts;
 use crate::features::sites::jobs::get_post_content::get_post_content;
+use crate::features::sites::jobs::mirror_media::mirror_media;
 use std::time::Duration;
+use tracing::warn;
 
 pub struct SiteCron;
 impl SiteCron {
     pub async fn run(app_state: AppState) -> CronManager {
-        let m = app_state.config.post_check_interval_minutes;
-        let jon_time = Duration::from_secs((m * 60) as u64);
-        let job = CronDefinition {
+        let config = &app_state.config;
+        // Each site carries its own schedule; the tick only decides how often we look for due sites.
+        let tick = Duration::from_secs(config.scheduler_tick_seconds);
+        let crawl = CronDefinition {
             name: "fetch_new_posts",
-            interval: jon_time,
+            schedule: CronSchedule::Every(tick),
+            overlap: OverlapPolicy::Skip,
+            jitter: Duration::ZERO,
+            tasks: vec![boxed(|| async { check_new_post().await })],
+        };
+
+        let interval = Duration::from_secs(u64::from(config.post_extract_interval_minutes) * 60);
+        let extract = CronDefinition {
+            name: "extract_posts",
+            schedule: CronSchedule::Every(interval),
+            // A slow batch must finish before the same pending posts are picked up again.
+            overlap: OverlapPolicy::Skip,
+            jitter: Duration::ZERO,
             tasks: vec![
                 boxed(|| async { get_post_content().await }),
-                boxed(|| async { check_new_post().await }),
+                boxed(|| async { mirror_media().await }),
             ],
         };
-        CronManager::new(vec![job])
+        CronManager::new(vec![crawl, extract])
     }
 }
 
 pub struct PostCleanupCron;
 impl PostCleanupCron {
-    pub async fn run(_app_state: AppState) -> CronManager {
-        let interval = Duration::from_secs(60 * 60 * 24);
+    pub async fn run(app_state: AppState) -> CronManager {
+        let config = &app_state.config;
+        let schedule = CronSchedule::cron(&config.post_cleanup_cron, &config.cron_timezone)
+            .unwrap_or_else(|e| {
+                warn!(target: targets::SYSTEM, error = %e, "Invalid POST_CLEANUP_CRON, running cleanup daily");
+                CronSchedule::Every(Duration::from_secs(60 * 60 * 24))
+            });
+
         let job = CronDefinition {
             name: "cleanup_old_posts",
-            interval,
+            schedule,
+            overlap: OverlapPolicy::Skip,
+            jitter: Duration::from_secs(60),
             tasks: vec![boxed(|| async { cleanup_old_posts().await })],
         };
         CronManager::new(vec![job])
@@ -0,0 +1,27 @@
+use serde::Serialize;
+
+/// Counters collected while crawling one site, persisted as a `crawl_runs` row.
+///
+/// Link discovery (`check_new_post`) fills `links_found`, `pages_crawled`, `posts_created`
+/// and `duplicates`; content extraction (`get_post_content`) fills `posts_processed` and
+/// `posts_updated`. Both count `failures`.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct CrawlStats {
+    pub links_found: i32,
+    pub pages_crawled: i32,
+    pub posts_created: i32,
+    /// Posts an extraction run looked at, pending and re-checked alike.
+    pub posts_processed: i32,
+    /// Posts whose content was stored or changed; an unchanged re-check is not counted.
+    pub posts_updated: i32,
+    pub duplicates: i32,
+    pub failures: i32,
+    pub error: Option<String>,
+}
+
+impl CrawlStats {
+    pub fn fail(&mut self, error: impl Into<String>) {
+        self.failures += 1;
+        self.error = Some(error.into());
+    }
+}
@@ -0,0 +1,18 @@
+use crate::features::sites::dto::crawl_stats::CrawlStats;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct CrawlParams {
+    /// Also extract content for the site's pending posts once the listing crawl is done.
+    pub extract: Option<bool>,
+}
+
+/// Result of an on-demand crawl. `finished` is false when the crawl outlived the request;
+/// it keeps running and its outcome shows up under the site's crawl runs.
+#[derive(Debug, Default, Serialize)]
+pub struct CrawlSummary {
+    pub site_id: i64,
+    pub finished: bool,
+    pub crawl: Option<CrawlStats>,
+    pub extraction: Option<CrawlStats>,
+}
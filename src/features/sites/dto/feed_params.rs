@@ -0,0 +1,20 @@
+use serde::Deserialize;
+
+/// Items in a feed unless `limit` asks otherwise.
+const DEFAULT_FEED_ITEMS: u64 = 50;
+const MAX_FEED_ITEMS: u64 = 200;
+
+#[derive(Deserialize)]
+pub struct FeedParams {
+    /// The API key's feed token; feed readers can't send `X-API-Key`.
+    pub token: Option<String>,
+    pub limit: Option<u64>,
+}
+
+impl FeedParams {
+    pub fn limit(&self) -> u64 {
+        self.limit
+            .unwrap_or(DEFAULT_FEED_ITEMS)
+            .clamp(1, MAX_FEED_ITEMS)
+    }
+}
@@ -6,4 +6,23 @@ pub struct PostListParams {
     #[serde(flatten)]
     pub pagination: PaginationParams,
     pub post_id: Option<i64>,
+    #[serde(default)]
+    pub sort: PostSort,
+}
+
+/// Order of post listings, newest first either way.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostSort {
+    /// Discovery order (`id`).
+    #[default]
+    Id,
+    /// Publication date; posts without one come last.
+    PublishedAt,
+}
+
+#[derive(Deserialize)]
+pub struct PostStreamParams {
+    /// Resume after this post id; the `Last-Event-ID` header takes precedence.
+    pub post_id: Option<i64>,
 }
@@ -1 +1,8 @@
+pub mod crawl_stats;
+pub mod crawl_summary;
+pub mod feed_params;
 pub mod list_params;
+pub mod post_search;
+pub mod post_with_media;
+pub mod revision_diff;
+pub mod site_preview;
@@ -0,0 +1,28 @@
+use crate::core::dto::pagination::PaginationParams;
+use crate::features::sites::model::posts;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct PostSearchParams {
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+    pub q: String,
+    pub site_id: Option<i64>,
+    /// Publication date range, inclusive; posts without one use their discovery date.
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// A matching post, best match first, with the matched terms wrapped in `<mark>`.
+#[derive(Debug, Serialize)]
+pub struct PostSearchHit {
+    #[serde(flatten)]
+    pub post: posts::Model,
+    /// The HTML-escaped title with every match marked.
+    pub title_highlight: Option<String>,
+    /// An HTML-escaped excerpt of `body_text` around the matches.
+    pub snippet: Option<String>,
+    /// BM25 relevance; lower is better.
+    pub rank: f64,
+}
@@ -0,0 +1,10 @@
+use crate::features::sites::model::{post_media, posts};
+use serde::Serialize;
+
+/// A post as returned by the API, with its images and videos in page order.
+#[derive(Debug, Serialize)]
+pub struct PostWithMedia {
+    #[serde(flatten)]
+    pub post: posts::Model,
+    pub media: Vec<post_media::Model>,
+}
@@ -0,0 +1,61 @@
+use crate::features::sites::model::post_revision::Model;
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+
+#[derive(Deserialize)]
+pub struct RevisionDiffParams {
+    pub from: i32,
+    pub to: i32,
+}
+
+/// Field-by-field unified diff between two revisions of a post. Unchanged fields are omitted.
+#[derive(Debug, Serialize)]
+pub struct RevisionDiff {
+    pub post_id: i64,
+    pub from: i32,
+    pub to: i32,
+    pub changes: Vec<FieldDiff>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FieldDiff {
+    pub field: &'static str,
+    pub diff: String,
+}
+
+impl RevisionDiff {
+    pub fn between(from: &Model, to: &Model) -> Self {
+        let fields = [
+            ("title", &from.title, &to.title),
+            ("body", &from.body, &to.body),
+            ("image", &from.image, &to.image),
+            ("video", &from.video, &to.video),
+        ];
+
+        let from_header = format!("revision {}", from.revision);
+        let to_header = format!("revision {}", to.revision);
+
+        let changes = fields
+            .into_iter()
+            .filter(|(_, old, new)| old != new)
+            .map(|(field, old, new)| FieldDiff {
+                field,
+                diff: TextDiff::from_lines(
+                    old.as_deref().unwrap_or_default(),
+                    new.as_deref().unwrap_or_default(),
+                )
+                .unified_diff()
+                .missing_newline_hint(false)
+                .header(&from_header, &to_header)
+                .to_string(),
+            })
+            .collect();
+
+        Self {
+            post_id: to.post_id,
+            from: from.revision,
+            to: to.revision,
+            changes,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::features::sites::utility::post_extractor::ExtractedPost;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct PreviewParams {
+    /// How many article links to open and extract (default 3, at most 10).
+    pub limit: Option<usize>,
+}
+
+/// What an unsaved site configuration would produce, without touching the database.
+#[derive(Debug, Serialize)]
+pub struct SitePreview {
+    pub links: Vec<String>,
+    /// Where the listing continues, when `max_pages` allows more than one page.
+    pub next_page: Option<String>,
+    pub posts: Vec<PostPreview>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostPreview {
+    pub url: String,
+    #[serde(flatten)]
+    pub extracted: ExtractedPost,
+    pub error: Option<String>,
+}
@@ -1,16 +1,34 @@
 use crate::core::logger::targets;
-use crate::features::crawler::Browser;
-use crate::features::sites::model::site::Model;
+use crate::features::sites::dto::crawl_stats::CrawlStats;
+use crate::features::sites::model::site::{Model, SourceType};
+use crate::features::sites::repository::crawl_run_repository::CrawlRunRepository;
 use crate::features::sites::repository::post_repository::PostRepository;
 use crate::features::sites::repository::site_repository::SiteRepository;
+use crate::features::sites::utility::crawl_schedule::next_crawl_at;
+use crate::features::sites::utility::link_sources::discover_links;
+use crate::features::sites::utility::listing_pages::Pagination;
 use crate::features::sites::utility::normalize_link::normalize_link;
-use crate::features::sites::utility::site_error_tracker::{register_site_error, reset_site_error};
+use crate::features::sites::utility::page_loader::load_page;
+use crate::features::sites::utility::post_extractor::split_selectors;
+use crate::features::sites::utility::selector_chain::SelectorChain;
+use crate::features::sites::utility::site_error_tracker::{block, reset_site_error};
+use crate::features::sites::utility::site_lock::{SiteLock, SiteWork};
 use crate::features::sites::validation::post_form::PostFormCreate;
+use chrono::Utc;
+use std::collections::HashSet;
 use tokio::time::{Duration, sleep, timeout};
 use tracing::{error, warn};
 
+const JOB_NAME: &str = "check_new_post";
+
+/// Time allowed per listing page; a crawl may take this times the site's `max_pages`.
+const SITE_TIMEOUT: Duration = Duration::from_secs(60);
+/// Pause between listing pages of the same site.
+const PAGE_DELAY: Duration = Duration::from_secs(2);
+
 pub async fn check_new_post() {
-    let sites = match SiteRepository::all().await {
+    let now = Utc::now().naive_utc();
+    let sites = match SiteRepository::due(now).await {
         Ok(list) => list,
         Err(e) => {
             error!(target: targets::CRAWLER_SITE, error = %e, "Failed to load sites");
@@ -18,6 +36,18 @@ pub async fn check_new_post() {
         }
     };
 
+    // Claim every due site up front so an overlapping tick doesn't pick them up again.
+    for site in &sites {
+        if let Err(e) = SiteRepository::schedule_next(site.id, next_crawl_at(site, now)).await {
+            error!(
+                target: targets::CRAWLER_SITE,
+                site_id = site.id,
+                error = %e,
+                "Failed to schedule next crawl"
+            );
+        }
+    }
+
     let mut is_first_site = true;
 
     for site in sites {
@@ -28,83 +58,208 @@ pub async fn check_new_post() {
 
         is_first_site = false;
 
-        let timeout_result =
-            tokio::time::timeout(Duration::from_secs(60), process_site(site)).await;
-
-        match timeout_result {
-            Ok(Ok(())) => {}
-            Ok(Err(e)) => error!(target: targets::CRAWLER_SITE, error = %e, "process_site failed"),
-            Err(_) => warn!(target: targets::CRAWLER_SITE, "process_site timeout"),
-        }
+        // An on-demand crawl of the site is already doing this run's work.
+        let Some(_lock) = SiteLock::try_acquire(SiteWork::Crawl, site.id) else {
+            continue;
+        };
+        crawl_site(site).await;
     }
 }
 
-async fn process_site(site: Model) -> anyhow::Result<()> {
-    let path = match &site.path_link {
-        Some(p) if !p.is_empty() => p,
-        _ => return Ok(()),
+/// Crawl one site's listing pages, sitemap or feed, record the run and return its counters.
+pub async fn crawl_site(site: Model) -> CrawlStats {
+    let site_id = site.id;
+    // A listing site without a link selector has nothing to crawl yet; don't log empty runs.
+    if site.source_type == SourceType::Listing
+        && SelectorChain::new(site.path_link.as_deref().unwrap_or("")).is_empty()
+    {
+        return CrawlStats::default();
+    }
+
+    let run = match CrawlRunRepository::start(JOB_NAME, site_id).await {
+        Ok(run) => Some(run),
+        Err(e) => {
+            error!(target: targets::CRAWLER_SITE, site_id, error = %e, "Failed to record crawl run");
+            None
+        }
     };
 
-    // Timeout for Browser::new
-    let browser = match timeout(
-        Duration::from_secs(30),
-        Browser::new(&site.url_list, None, None),
-    )
-    .await
+    let mut stats = CrawlStats::default();
+    let pages = site.max_pages.unwrap_or(1).max(1) as u32;
+    let timeout_result = timeout(SITE_TIMEOUT * pages, process_site(site, &mut stats)).await;
+
+    match timeout_result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            error!(target: targets::CRAWLER_SITE, error = %e, "process_site failed");
+            stats.fail(e.to_string());
+        }
+        Err(_) => {
+            warn!(target: targets::CRAWLER_SITE, "process_site timeout");
+            stats.fail("process_site timeout");
+        }
+    }
+
+    if let Some(run) = run
+        && let Err(e) = CrawlRunRepository::finish(run.id, &stats).await
     {
+        error!(target: targets::CRAWLER_SITE, site_id, error = %e, "Failed to record crawl run");
+    }
+
+    stats
+}
+
+async fn process_site(site: Model, stats: &mut CrawlStats) -> anyhow::Result<()> {
+    match site.source_type {
+        SourceType::Listing => process_listing(&site, stats).await,
+        SourceType::Sitemap | SourceType::Feed => {
+            process_source(&site, stats).await;
+            Ok(())
+        }
+    }
+}
+
+/// Read post URLs from the site's sitemap or feed.
+async fn process_source(site: &Model, stats: &mut CrawlStats) {
+    match discover_links(&site.source_type, &site.url_list, site.max_age_hours).await {
+        Ok(source) => {
+            reset_site_error(site.id).await;
+            stats.pages_crawled += source.documents;
+            save_links(site, source.links, stats).await;
+        }
+        Err(e) => {
+            error!(
+                target: targets::CRAWLER_SITE,
+                site_id = site.id,
+                error = %e,
+                "Link source failed to load for site"
+            );
+
+            let reason = format!("link source failed: {e}");
+            stats.fail(&reason);
+            block(site, &reason).await;
+        }
+    }
+}
+
+async fn process_listing(site: &Model, stats: &mut CrawlStats) -> anyhow::Result<()> {
+    let links_chain = SelectorChain::new(site.path_link.as_deref().unwrap_or(""));
+    if links_chain.is_empty() {
+        return Ok(());
+    }
+
+    let pagination = Pagination::from_site(site);
+    let mut url = site.url_list.clone();
+    let mut visited = HashSet::from([url.clone()]);
+
+    for number in 1..=pagination.max_pages {
+        let Some(listing) =
+            read_listing(site, &url, number, &links_chain, &pagination, stats).await?
+        else {
+            break;
+        };
+        stats.pages_crawled += 1;
+
+        // Pages past this one are older still, so once a page adds nothing the rest is known.
+        if save_links(site, listing.links, stats).await == 0 {
+            break;
+        }
+
+        let Some(next) = listing.next_url.filter(|next| visited.insert(next.clone())) else {
+            break;
+        };
+        url = next;
+        sleep(PAGE_DELAY).await;
+    }
+
+    Ok(())
+}
+
+/// The links of one listing page and where the listing continues.
+struct Listing {
+    links: Vec<String>,
+    next_url: Option<String>,
+}
+
+/// Load listing page `number` and read its links. Problems with the first page block the
+/// site as before; a later page that fails only ends the pagination. `None` means stop.
+async fn read_listing(
+    site: &Model,
+    url: &str,
+    number: u32,
+    links_chain: &SelectorChain,
+    pagination: &Pagination,
+    stats: &mut CrawlStats,
+) -> anyhow::Result<Option<Listing>> {
+    let first = number == 1;
+
+    // Timeout for loading the listing page
+    let page = match timeout(Duration::from_secs(30), load_page(&site.fetch_mode, url)).await {
         Ok(Ok(b)) => b,
         Ok(Err(e)) => {
             error!(
                 target: targets::CRAWLER_SITE,
                 site_id = site.id,
+                page = number,
                 error = %e,
-                "Browser failed to start for site"
+                "Page failed to load for site"
             );
 
-            block(&site).await;
+            if first {
+                let reason = format!("page load failed: {e}");
+                stats.fail(&reason);
+                block(site, &reason).await;
+            }
 
-            return Ok(());
+            return Ok(None);
         }
         Err(_) => {
             warn!(
                 target: targets::CRAWLER_SITE,
                 site_id = site.id,
-                "Browser startup timeout for site"
+                page = number,
+                "Page load timeout for site"
             );
-            block(&site).await;
-            return Ok(());
+            if first {
+                stats.fail("page load timed out");
+                block(site, "page load timed out").await;
+            }
+            return Ok(None);
         }
     };
 
-    if let Err(e) = browser
-        .wait_for_selector(path, Duration::from_secs(20))
+    if let Err(e) = links_chain
+        .wait_for_any(page.as_ref(), Duration::from_secs(20))
         .await
     {
         error!(
             target: targets::CRAWLER_SITE,
             site_id = site.id,
-            selector = %path,
+            page = number,
+            selector = site.path_link.as_deref().unwrap_or_default(),
             error = %e,
             "wait_for_selector failed for site"
         );
 
-        block(&site).await;
+        if first {
+            let reason = format!("link selector not found: {e}");
+            stats.fail(&reason);
+            block(site, &reason).await;
+        }
 
-        return Ok(());
+        return Ok(None);
     }
 
-    reset_site_error(site.id).await;
+    if first {
+        reset_site_error(site.id).await;
+    }
 
     if let Some(remove_str) = &site.path_remove {
-        let selectors: Vec<String> = remove_str
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
+        let selectors = split_selectors(remove_str);
 
         if !selectors.is_empty() {
             let remove_result =
-                timeout(Duration::from_secs(10), browser.remove_elements(selectors)).await;
+                timeout(Duration::from_secs(10), page.remove_elements(selectors)).await;
 
             match remove_result {
                 Ok(Ok(())) => {}
@@ -122,36 +277,68 @@ async fn process_site(site: Model) -> anyhow::Result<()> {
                         site_id = site.id,
                         "remove_elements timeout"
                     );
-                    block(&site).await;
+                    stats.fail("remove_elements timed out");
+                    block(site, "remove_elements timed out").await;
                 }
             }
         }
     }
 
-    let links = match timeout(Duration::from_secs(20), browser.get_attrs(path, "href")).await {
+    let links = match timeout(
+        Duration::from_secs(20),
+        links_chain.attrs(page.as_ref(), "href"),
+    )
+    .await
+    {
         Ok(Ok(links)) => links,
-        Ok(Err(e)) => {
+        Ok(Err(e)) if first => {
             return Err(anyhow::anyhow!(
                 "get_attrs failed for site {}: {}",
                 site.id,
                 e
             ));
         }
+        Ok(Err(e)) => {
+            warn!(
+                target: targets::CRAWLER_SITE,
+                site_id = site.id,
+                page = number,
+                error = %e,
+                "get_attrs failed for listing page"
+            );
+            return Ok(None);
+        }
         Err(_) => {
             warn!(
                 target: targets::CRAWLER_SITE,
                 site_id = site.id,
+                page = number,
                 "get_attrs timeout"
             );
-            block(&site).await;
-            return Ok(());
+            if first {
+                stats.fail("get_attrs timed out");
+                block(site, "get_attrs timed out").await;
+            }
+            return Ok(None);
         }
     };
 
+    let next_url = pagination
+        .next_url(page.as_ref(), &site.url_list, url, number)
+        .await;
+
+    Ok(Some(Listing { links, next_url }))
+}
+
+/// Create a pending post for every new link and return how many were created.
+async fn save_links(site: &Model, links: Vec<String>, stats: &mut CrawlStats) -> i32 {
+    stats.links_found += links.len() as i32;
+    let created_before = stats.posts_created;
+
     for raw_link in links {
         let link = normalize_link(&site.url, &raw_link);
 
-        if let Err(e) = PostRepository::create(PostFormCreate {
+        match PostRepository::create(PostFormCreate {
             url: Some(link),
             site_id: site.id,
             user_id: site.user_id,
@@ -159,37 +346,23 @@ async fn process_site(site: Model) -> anyhow::Result<()> {
         })
         .await
         {
-            let msg = e.to_string();
-            if !msg.contains("UNIQUE constraint failed") {
-                error!(
-                    target: targets::CRAWLER_SITE,
-                    site_id = site.id,
-                    error = %msg,
-                    "Failed to create post"
-                );
+            Ok(_) => stats.posts_created += 1,
+            Err(e) => {
+                let msg = e.to_string();
+                if msg.contains("UNIQUE constraint failed") {
+                    stats.duplicates += 1;
+                } else {
+                    error!(
+                        target: targets::CRAWLER_SITE,
+                        site_id = site.id,
+                        error = %msg,
+                        "Failed to create post"
+                    );
+                    stats.fail(msg);
+                }
             }
         }
     }
 
-    Ok(())
-}
-
-async fn block(site: &Model) {
-    let count = register_site_error(site.id).await;
-    if count >= 5 {
-        warn!(
-            target: targets::CRAWLER_SITE,
-            site_id = site.id,
-            error_count = count,
-            "Site reached error threshold, disabling"
-        );
-        if let Err(e) = SiteRepository::disable(site.id).await {
-            error!(
-                target: targets::CRAWLER_SITE,
-                site_id = site.id,
-                error = %e,
-                "Failed to disable site"
-            );
-        }
-    }
+    stats.posts_created - created_before
 }
@@ -1,9 +1,24 @@
 use crate::core::logger::targets;
+use crate::features::sites::repository::post_media_repository::PostMediaRepository;
 use crate::features::sites::repository::post_repository::PostRepository;
+use crate::features::sites::utility::media_store::remove_orphans;
+use crate::features::sites::utility::screenshot_store::remove_orphan_screenshots;
 use crate::utility::state::app_state;
+use std::time::Duration;
 use tracing::{error, info, warn};
 
+/// Stored files this recent are never treated as orphans, so a mirror run in progress
+/// can still record the file it just wrote.
+const ORPHAN_GRACE: Duration = Duration::from_secs(60 * 60);
+
 pub async fn cleanup_old_posts() {
+    delete_old_posts().await;
+    // Deleted posts take their media rows with them; their mirrored files and screenshots go here.
+    delete_orphan_media().await;
+    delete_orphan_screenshots().await;
+}
+
+async fn delete_old_posts() {
     let state = app_state();
     let keep_latest = state.config.post_keep_latest;
 
@@ -31,3 +46,53 @@ pub async fn cleanup_old_posts() {
         }
     }
 }
+
+async fn delete_orphan_media() {
+    let referenced = match PostMediaRepository::mirrored_checksums().await {
+        Ok(checksums) => checksums,
+        Err(err) => {
+            error!(target: targets::SYSTEM, error = %err, "[cron:cleanup_old_posts] failed to load mirrored media");
+            return;
+        }
+    };
+
+    match remove_orphans(&referenced, ORPHAN_GRACE).await {
+        Ok(removed) => {
+            if removed > 0 {
+                info!(
+                    target: targets::SYSTEM,
+                    removed,
+                    "[cron:cleanup_old_posts] removed orphaned media files"
+                );
+            }
+        }
+        Err(err) => {
+            error!(target: targets::SYSTEM, error = %err, "[cron:cleanup_old_posts] failed to remove orphaned media")
+        }
+    }
+}
+
+async fn delete_orphan_screenshots() {
+    let referenced = match PostRepository::screenshot_paths().await {
+        Ok(paths) => paths,
+        Err(err) => {
+            error!(target: targets::SYSTEM, error = %err, "[cron:cleanup_old_posts] failed to load screenshot paths");
+            return;
+        }
+    };
+
+    match remove_orphan_screenshots(&referenced, ORPHAN_GRACE).await {
+        Ok(removed) => {
+            if removed > 0 {
+                info!(
+                    target: targets::SYSTEM,
+                    removed,
+                    "[cron:cleanup_old_posts] removed orphaned screenshots"
+                );
+            }
+        }
+        Err(err) => {
+            error!(target: targets::SYSTEM, error = %err, "[cron:cleanup_old_posts] failed to remove orphaned screenshots")
+        }
+    }
+}
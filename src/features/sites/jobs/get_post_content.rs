@@ -1,22 +1,36 @@
 use crate::core::config::Config;
 use crate::core::logger::targets;
 use crate::core::state::APP_STATE;
-use crate::features::crawler::Browser;
+use crate::features::crawler::fetcher::{Fetcher, ScreenshotFormat};
+use crate::features::sites::dto::crawl_stats::CrawlStats;
 use crate::features::sites::model::posts::Model;
 use crate::features::sites::model::{posts, site};
+use crate::features::sites::repository::crawl_run_repository::CrawlRunRepository;
+use crate::features::sites::repository::post_media_repository::PostMediaRepository;
 use crate::features::sites::repository::post_repository::PostRepository;
 use crate::features::sites::repository::site_repository::SiteRepository;
-use crate::features::sites::utility::normalize_link::normalize_link;
-use crate::features::sites::utility::site_error_tracker::register_site_error;
+use crate::features::sites::utility::content_hash::content_hash;
+use crate::features::sites::utility::crawl_schedule::recheck_bounds;
+use crate::features::sites::utility::page_loader::load_page;
+use crate::features::sites::utility::page_metadata::read_metadata;
+use crate::features::sites::utility::post_extractor::{
+    ExtractedPost, PostSelectors, extract_post, remove_elements,
+};
+use crate::features::sites::utility::screenshot_store::save_screenshot;
+use crate::features::sites::utility::site_error_tracker::block;
+use crate::features::sites::utility::site_lock::{SiteLock, SiteWork};
 use crate::features::sites::validation::post_form::PostForm;
+use chrono::Utc;
 use futures::FutureExt;
-use tokio::task::JoinSet;
+use std::collections::HashMap;
+use tokio::task::{self, JoinSet};
 use tokio::time::{Duration, timeout};
 use tracing::{error, warn};
 
 const DEFAULT_POST_PROCESS_TIMEOUT: Duration = Duration::from_secs(45);
 const DEFAULT_BROWSER_START_TIMEOUT: Duration = Duration::from_secs(30);
 const DEFAULT_MAX_CONCURRENT_JOBS: usize = 20;
+const JOB_NAME: &str = "get_post_content";
 
 #[derive(Clone)]
 struct PostProcessingConfig {
@@ -52,11 +66,89 @@ struct PostContentJob {
 }
 
 enum JobResult {
-    Completed(()),
+    /// `Ok(true)` when the post content was stored or changed, `Ok(false)` for an unchanged re-check.
+    Completed(Result<bool, &'static str>),
     TimedOut(i64),
     Panicked(i64),
 }
 
+struct SiteRun {
+    run_id: Option<i64>,
+    stats: CrawlStats,
+}
+
+/// One `crawl_runs` row per site touched by a batch, finished once the batch drains.
+struct SiteRuns {
+    runs: HashMap<i64, SiteRun>,
+}
+
+impl SiteRuns {
+    async fn start(jobs: &[PostContentJob]) -> Self {
+        let mut runs: HashMap<i64, SiteRun> = HashMap::new();
+
+        for job in jobs {
+            if let Some(run) = runs.get_mut(&job.site.id) {
+                run.stats.posts_processed += 1;
+                continue;
+            }
+
+            let run_id = match CrawlRunRepository::start(JOB_NAME, job.site.id).await {
+                Ok(run) => Some(run.id),
+                Err(e) => {
+                    error!(
+                        target: targets::CRAWLER_POST,
+                        site_id = job.site.id,
+                        error = %e,
+                        "Failed to record crawl run"
+                    );
+                    None
+                }
+            };
+
+            let stats = CrawlStats {
+                posts_processed: 1,
+                ..Default::default()
+            };
+            runs.insert(job.site.id, SiteRun { run_id, stats });
+        }
+
+        Self { runs }
+    }
+
+    fn record(&mut self, site_id: i64, outcome: Result<bool, &str>) {
+        let Some(run) = self.runs.get_mut(&site_id) else {
+            return;
+        };
+
+        match outcome {
+            Ok(true) => run.stats.posts_updated += 1,
+            Ok(false) => {}
+            Err(reason) => run.stats.fail(reason),
+        }
+    }
+
+    async fn finish(self) -> HashMap<i64, CrawlStats> {
+        let mut stats = HashMap::with_capacity(self.runs.len());
+
+        for (site_id, run) in self.runs {
+            if let Some(run_id) = run.run_id
+                && let Err(e) = CrawlRunRepository::finish(run_id, &run.stats).await
+            {
+                error!(
+                    target: targets::CRAWLER_POST,
+                    site_id,
+                    error = %e,
+                    "Failed to record crawl run"
+                );
+            }
+
+            stats.insert(site_id, run.stats);
+        }
+
+        stats
+    }
+}
+
 struct PostContentOrchestrator {
     config: PostProcessingConfig,
 }
@@ -66,50 +158,78 @@ impl PostContentOrchestrator {
         Self { config }
     }
 
-    async fn run(&self, jobs: Vec<PostContentJob>) {
+    async fn run(&self, jobs: Vec<PostContentJob>) -> HashMap<i64, CrawlStats> {
         if jobs.is_empty() {
-            return;
+            return HashMap::new();
         }
 
-        let mut join_set: JoinSet<JobResult> = JoinSet::new();
+        let mut site_runs = SiteRuns::start(&jobs).await;
+        let mut join_set: JoinSet<(i64, JobResult)> = JoinSet::new();
+        // A task that fails to join loses its result, so keep track of what it was working on.
+        let mut in_flight: HashMap<task::Id, (i64, i64)> = HashMap::new();
         let mut queue = jobs.into_iter();
 
         for _ in 0..self.config.concurrency_limit {
             if let Some(job) = queue.next() {
-                self.spawn_job(&mut join_set, job);
+                self.spawn_job(&mut join_set, &mut in_flight, job);
             }
         }
 
-        while let Some(result) = join_set.join_next().await {
+        while let Some(result) = join_set.join_next_with_id().await {
             if let Some(next_job) = queue.next() {
-                self.spawn_job(&mut join_set, next_job);
+                self.spawn_job(&mut join_set, &mut in_flight, next_job);
             }
 
+            let result = match result {
+                Ok((id, result)) => {
+                    in_flight.remove(&id);
+                    Ok(result)
+                }
+                Err(join_error) => Err((in_flight.remove(&join_error.id()), join_error)),
+            };
+
             match result {
-                Ok(JobResult::Completed(_)) => {}
-                Ok(JobResult::TimedOut(post_id)) => {
+                Ok((site_id, JobResult::Completed(outcome))) => {
+                    site_runs.record(site_id, outcome);
+                }
+                Ok((site_id, JobResult::TimedOut(post_id))) => {
                     mark_post_failed(post_id, "processing timed out").await;
+                    site_runs.record(site_id, Err("processing timed out"));
                 }
-                Ok(JobResult::Panicked(post_id)) => {
+                Ok((site_id, JobResult::Panicked(post_id))) => {
                     mark_post_failed(post_id, "task panicked").await;
+                    site_runs.record(site_id, Err("task panicked"));
                 }
-                Err(join_error) => {
+                Err((job, join_error)) => {
                     error!(
                         target: targets::CRAWLER_POST,
                         error = %join_error,
                         "Join error while processing posts"
-                    )
+                    );
+                    if let Some((site_id, post_id)) = job {
+                        mark_post_failed(post_id, "task failed").await;
+                        site_runs.record(site_id, Err("task failed"));
+                    }
                 }
             }
         }
+
+        site_runs.finish().await
     }
 
-    fn spawn_job(&self, join_set: &mut JoinSet<JobResult>, job: PostContentJob) {
+    fn spawn_job(
+        &self,
+        join_set: &mut JoinSet<(i64, JobResult)>,
+        in_flight: &mut HashMap<task::Id, (i64, i64)>,
+        job: PostContentJob,
+    ) {
         let timeout_duration = self.config.post_timeout;
         let browser_timeout = self.config.browser_start_timeout;
+        let job_ids = (job.site.id, job.post.id);
 
-        join_set.spawn(async move {
+        let handle = join_set.spawn(async move {
             let post_id = job.post.id;
+            let site_id = job.site.id;
             let task = async move {
                 match timeout(
                     timeout_duration,
@@ -117,16 +237,19 @@ impl PostContentOrchestrator {
                 )
                 .await
                 {
-                    Ok(()) => JobResult::Completed(()),
+                    Ok(outcome) => JobResult::Completed(outcome),
                     Err(_) => JobResult::TimedOut(post_id),
                 }
             };
 
-            match std::panic::AssertUnwindSafe(task).catch_unwind().await {
+            let result = match std::panic::AssertUnwindSafe(task).catch_unwind().await {
                 Ok(result) => result,
                 Err(_) => JobResult::Panicked(post_id),
-            }
+            };
+
+            (site_id, result)
         });
+        in_flight.insert(handle.id(), job_ids);
     }
 }
 
@@ -141,14 +264,87 @@ pub async fn get_post_content() {
         }
     };
 
-    let jobs: Vec<PostContentJob> = posts
+    let mut jobs: Vec<PostContentJob> = posts
         .into_iter()
         .map(|(post, site)| PostContentJob { post, site })
         .collect();
+    jobs.extend(recheck_jobs().await);
+
+    // Sites with an on-demand extraction in progress wait for the next run; the rest stay
+    // locked until this batch is done.
+    let mut locks: HashMap<i64, Option<SiteLock>> = HashMap::new();
+    jobs.retain(|job| {
+        locks
+            .entry(job.site.id)
+            .or_insert_with(|| SiteLock::try_acquire(SiteWork::Extract, job.site.id))
+            .is_some()
+    });
 
     PostContentOrchestrator::new(config).run(jobs).await;
 }
 
+/// Completed posts still inside their site's re-check window and due for another look.
+async fn recheck_jobs() -> Vec<PostContentJob> {
+    let sites = match SiteRepository::with_recheck().await {
+        Ok(sites) => sites,
+        Err(e) => {
+            error!(target: targets::CRAWLER_POST, error = %e, "Failed to load re-check sites");
+            return Vec::new();
+        }
+    };
+
+    let now = Utc::now().naive_utc();
+    let mut jobs = Vec::new();
+
+    for site in sites {
+        let Some((created_after, checked_before)) = recheck_bounds(&site, now) else {
+            continue;
+        };
+
+        match PostRepository::recheck_list(site.id, created_after, checked_before).await {
+            Ok(posts) => jobs.extend(posts.into_iter().map(|post| PostContentJob {
+                post,
+                site: site.clone(),
+            })),
+            Err(e) => error!(
+                target: targets::CRAWLER_POST,
+                site_id = site.id,
+                error = %e,
+                "Failed to load posts to re-check"
+            ),
+        }
+    }
+
+    jobs
+}
+
+/// Extract content for one site's pending posts right away and return the run's counters.
+/// The caller holds the site's [`SiteWork::Extract`] lock.
+pub async fn get_post_content_for_site(site_id: i64) -> CrawlStats {
+    let config = load_post_processing_config();
+
+    let posts = match PostRepository::pending_list_by_site(site_id).await {
+        Ok(list) => list,
+        Err(e) => {
+            error!(target: targets::CRAWLER_POST, site_id, error = %e, "Failed to load pending posts");
+            let mut stats = CrawlStats::default();
+            stats.fail(e.to_string());
+            return stats;
+        }
+    };
+
+    let jobs: Vec<PostContentJob> = posts
+        .into_iter()
+        .map(|(post, site)| PostContentJob { post, site })
+        .collect();
+
+    PostContentOrchestrator::new(config)
+        .run(jobs)
+        .await
+        .remove(&site_id)
+        .unwrap_or_default()
+}
+
 fn load_post_processing_config() -> PostProcessingConfig {
     // Reading from APP_STATE ensures we reuse the already-loaded configuration.
     APP_STATE
@@ -157,151 +353,209 @@ fn load_post_processing_config() -> PostProcessingConfig {
         .unwrap_or_else(PostProcessingConfig::fallback)
 }
 
-async fn process_post(post: Model, site: site::Model, browser_timeout: Duration) {
+async fn process_post(
+    post: Model,
+    site: site::Model,
+    browser_timeout: Duration,
+) -> Result<bool, &'static str> {
     let url = post.url.as_deref().unwrap_or("");
-    let path_title = site.path_title.as_deref().unwrap_or("");
-    let path_image = site.path_image.as_deref().unwrap_or("");
-    let path_video = site.path_video.as_deref().unwrap_or("");
-    let path_content = site.path_content.as_deref().unwrap_or("");
+    let recheck = post.status == posts::PostStatus::COMPLETED;
+    let selectors = PostSelectors::from_site(&site);
 
-    let (title, image, video, content) = {
-        let browser = match timeout(browser_timeout, Browser::new(url, None, None)).await {
+    let (extracted, screenshot) = {
+        let page = match timeout(browser_timeout, load_page(&site.fetch_mode, url)).await {
             Ok(Ok(b)) => b,
             Ok(Err(e)) => {
                 error!(
                     target: targets::CRAWLER_POST,
                     post_id = post.id,
                     error = %e,
-                    "Browser failed to start for post"
+                    "Page failed to load for post"
                 );
-                mark_post_failed(post.id, "browser initialization failed").await;
-                return;
+                mark_post_failed(post.id, "page load failed").await;
+                return Err("page load failed");
             }
             Err(_) => {
                 warn!(
                     target: targets::CRAWLER_POST,
                     post_id = post.id,
                     timeout_ms = browser_timeout.as_millis(),
-                    "Browser startup timeout for post"
+                    "Page load timeout for post"
                 );
-                mark_post_failed(post.id, "browser initialization timed out").await;
-                return;
+                mark_post_failed(post.id, "page load timed out").await;
+                return Err("page load timed out");
             }
         };
 
-        if let Some(remove_str) = &site.path_remove {
-            let selectors: Vec<String> = remove_str
-                .split(',')
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect();
+        let metadata = read_metadata(page.as_ref(), &site.url).await;
+        // Taken before `path_remove` touches the DOM, so it shows the page as served.
+        let screenshot = capture_screenshot(page.as_ref(), &post, &site).await;
 
-            if !selectors.is_empty()
-                && let Err(e) = browser.remove_elements(selectors).await
-            {
-                warn!(
-                    target: targets::CRAWLER_SITE,
-                    site_id = site.id,
-                    error = %e,
-                    "Failed to remove elements"
-                );
-            }
+        if let Err(e) = remove_elements(page.as_ref(), selectors.remove).await {
+            warn!(
+                target: targets::CRAWLER_SITE,
+                site_id = site.id,
+                error = %e,
+                "Failed to remove elements"
+            );
         }
 
-        let title = browser
-            .get_element_text(path_title)
-            .await
-            .unwrap_or_default();
-        let image_attr =
-            fetch_attribute_with_fallback(&browser, path_image, "src", "content").await;
-        let image = normalize_link(&site.url, &image_attr);
-        let video = normalize_link(
-            &site.url,
-            &browser
-                .get_attr(path_video, "src")
-                .await
-                .unwrap_or_default(),
-        );
-        let content = browser
-            .get_element_html(path_content)
-            .await
-            .unwrap_or_default();
+        let extracted = extract_post(page.as_ref(), &site.url, &selectors, metadata).await;
+        let screenshot = screenshot.filter(|_| screenshot_needed(&post, &extracted));
 
-        (title, image, video, content)
+        (extracted, screenshot)
     };
 
-    if title.is_empty() && image.is_empty() && video.is_empty() && content.is_empty() {
-        mark_post_failed(post.id, "no content extracted").await;
-        block(&site).await;
-        return;
+    if let Some(reason) = extracted.failure() {
+        mark_post_failed(post.id, reason).await;
+        // A re-check keeps the content it already has; only first extractions count against the site.
+        if !recheck && extracted.counts_against_site() {
+            block(&site, reason).await;
+        }
+        return Err(reason);
+    }
+    if !extracted.missed_selectors.is_empty() {
+        warn!(
+            target: targets::CRAWLER_POST,
+            post_id = post.id,
+            selectors = ?extracted.missed_selectors,
+            body_source = ?extracted.body_source,
+            "Selectors matched nothing, fields taken from fallbacks"
+        );
     }
 
-    if let Err(e) = PostRepository::update(
+    let extracted_media = extracted.media;
+    let updated = match PostRepository::update(
         post.id,
         PostForm {
-            title: Some(title),
-            body: Some(content),
-            image: Some(image),
-            video: Some(video),
+            title: Some(extracted.title),
+            body: Some(extracted.body),
+            body_source: extracted.body_source,
+            image: Some(extracted.image),
+            video: Some(extracted.video),
             status: posts::PostStatus::COMPLETED,
+            author: Some(extracted.author).filter(|author| !author.is_empty()),
+            published_at: extracted.published_at,
+            metadata: (!extracted.metadata.is_empty())
+                .then(|| serde_json::to_value(&extracted.metadata).ok())
+                .flatten(),
         },
     )
     .await
     {
-        mark_post_failed(post.id, "database update failed").await;
+        Ok(updated) => updated,
+        Err(e) => {
+            mark_post_failed(post.id, "database update failed").await;
+            error!(
+                target: targets::CRAWLER_POST,
+                post_id = post.id,
+                error = %e,
+                "Failed to update post"
+            );
+            return Err("database update failed");
+        }
+    };
+
+    if let Err(e) = PostMediaRepository::replace(post.id, &extracted_media).await {
         error!(
             target: targets::CRAWLER_POST,
             post_id = post.id,
             error = %e,
-            "Failed to update post"
+            "Failed to store post media"
         );
     }
-}
 
-async fn fetch_attribute_with_fallback(
-    browser: &Browser,
-    selector: &str,
-    primary_attr: &str,
-    fallback_attr: &str,
-) -> String {
-    // Attempt to read the preferred attribute and gracefully fall back to an alternate one for cases
-    // such as meta tags that expose the URL through "content" instead of "src".
-    if selector.trim().is_empty() {
-        return String::new();
+    // Already mirrored media survive a re-extraction; the post's own image/video follow them.
+    if site.mirror_media == Some(true)
+        && let Err(e) = PostMediaRepository::relink_post(post.id).await
+    {
+        error!(
+            target: targets::CRAWLER_POST,
+            post_id = post.id,
+            error = %e,
+            "Failed to point post at mirrored media"
+        );
     }
 
-    let primary_value = sanitize_attribute_value(
-        browser
-            .get_attr(selector, primary_attr)
-            .await
-            .unwrap_or_default(),
-    );
-
-    if let Some(value) = primary_value {
-        return value;
+    if let Some((format, bytes)) = screenshot {
+        store_screenshot(&post, format, &bytes).await;
     }
 
-    sanitize_attribute_value(
-        browser
-            .get_attr(selector, fallback_attr)
-            .await
-            .unwrap_or_default(),
-    )
-    .unwrap_or_default()
+    Ok(updated.is_some_and(|updated| updated.revision != post.revision))
 }
 
-fn sanitize_attribute_value(raw_value: String) -> Option<String> {
-    // Some browsers return literal "null" or "undefined" strings for missing attributes,
-    // so we normalize them to None to allow proper fallback checks.
-    let trimmed_value = raw_value.trim();
-
-    if trimmed_value.is_empty() {
+/// Capture the page when the site asks for screenshots. Whether it is kept is only known
+/// after extraction; see [`screenshot_needed`]. Failures only cost the screenshot, never
+/// the extraction.
+async fn capture_screenshot(
+    page: &dyn Fetcher,
+    post: &Model,
+    site: &site::Model,
+) -> Option<(ScreenshotFormat, Vec<u8>)> {
+    if site.screenshot != Some(true) {
         return None;
     }
 
-    match trimmed_value {
-        "null" | "undefined" => None,
-        _ => Some(trimmed_value.to_string()),
+    let (format, full_page) = APP_STATE
+        .get()
+        .map(|state| {
+            (
+                ScreenshotFormat::parse(&state.config.screenshot_format),
+                state.config.screenshot_full_page,
+            )
+        })
+        .unwrap_or((ScreenshotFormat::Jpeg, false));
+
+    match page.screenshot(format, full_page).await {
+        Ok(bytes) => Some((format, bytes)),
+        Err(e) => {
+            warn!(
+                target: targets::CRAWLER_POST,
+                post_id = post.id,
+                error = %e,
+                "Failed to capture screenshot"
+            );
+            None
+        }
+    }
+}
+
+/// A screenshot is stored for extracted content when the post has none yet or its content changed.
+fn screenshot_needed(post: &Model, extracted: &ExtractedPost) -> bool {
+    if extracted.failure().is_some() {
+        return false;
+    }
+
+    let hash = content_hash(
+        &extracted.title,
+        &extracted.body,
+        &extracted.image,
+        &extracted.video,
+    );
+    post.screenshot_path.is_none() || post.content_hash.as_deref() != Some(hash.as_str())
+}
+
+async fn store_screenshot(post: &Model, format: ScreenshotFormat, bytes: &[u8]) {
+    let path = match save_screenshot(post.site_id, post.id, format, bytes).await {
+        Ok(path) => path,
+        Err(e) => {
+            warn!(
+                target: targets::CRAWLER_POST,
+                post_id = post.id,
+                error = %e,
+                "Failed to write screenshot"
+            );
+            return;
+        }
+    };
+
+    if let Err(e) = PostRepository::set_screenshot(post.id, path).await {
+        error!(
+            target: targets::CRAWLER_POST,
+            post_id = post.id,
+            error = %e,
+            "Failed to record screenshot"
+        );
     }
 }
 
@@ -322,23 +576,3 @@ async fn mark_post_failed(post_id: i64, reason: &str) {
         );
     }
 }
-
-async fn block(site: &site::Model) {
-    let count = register_site_error(site.id).await;
-    if count >= 5 {
-        warn!(
-            target: targets::CRAWLER_SITE,
-            site_id = site.id,
-            error_count = count,
-            "Site reached error threshold, disabling"
-        );
-        if let Err(e) = SiteRepository::disable(site.id).await {
-            error!(
-                target: targets::CRAWLER_SITE,
-                site_id = site.id,
-                error = %e,
-                "Failed to disable site"
-            );
-        }
-    }
-}
@@ -0,0 +1,151 @@
+use crate::core::logger::targets;
+use crate::features::crawler::http_page::download;
+use crate::features::sites::model::post_media::{MediaKind, Model};
+use crate::features::sites::repository::post_media_repository::PostMediaRepository;
+use crate::features::sites::utility::media_store::store_media;
+use crate::utility::state::app_state;
+use crate::utility::url::url;
+use chrono::{Duration, Utc};
+use futures::StreamExt;
+use std::collections::HashSet;
+use tracing::{error, info, warn};
+
+/// Media downloaded per scheduler tick; the rest waits for the next one.
+const MIRROR_BATCH_SIZE: u64 = 100;
+const MIRROR_CONCURRENCY: usize = 4;
+/// Failed downloads before a media item keeps its source URL for good.
+const MAX_MIRROR_ATTEMPTS: i32 = 5;
+/// Wait after the first failure, doubled after each further one.
+const MIRROR_BACKOFF_MINUTES: i64 = 15;
+
+/// Download the pending media of sites with `mirror_media` on into the local store and
+/// point their posts at the `/media` copies.
+pub async fn mirror_media() {
+    let pending =
+        match PostMediaRepository::pending_mirror(MIRROR_BATCH_SIZE, MAX_MIRROR_ATTEMPTS).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                error!(target: targets::CRAWLER_POST, error = %e, "Failed to load media to mirror");
+                return;
+            }
+        };
+
+    if pending.is_empty() {
+        return;
+    }
+
+    let mirrored_posts: HashSet<i64> = futures::stream::iter(pending)
+        .map(|row| async move { mirror_one(&row).await.then_some(row.post_id) })
+        .buffer_unordered(MIRROR_CONCURRENCY)
+        .filter_map(|post_id| async move { post_id })
+        .collect()
+        .await;
+
+    for post_id in &mirrored_posts {
+        if let Err(e) = PostMediaRepository::relink_post(*post_id).await {
+            error!(
+                target: targets::CRAWLER_POST,
+                post_id,
+                error = %e,
+                "Failed to point post at mirrored media"
+            );
+        }
+    }
+
+    info!(
+        target: targets::CRAWLER_POST,
+        posts = mirrored_posts.len(),
+        "[cron:mirror_media] mirrored post media"
+    );
+}
+
+/// Returns `true` when the row now points at a local copy.
+async fn mirror_one(row: &Model) -> bool {
+    let config = &app_state().config;
+    let max_bytes = match row.kind {
+        MediaKind::Image => config.media_max_image_bytes,
+        MediaKind::Video => config.media_max_video_bytes,
+    };
+
+    let file = match download(&row.url, max_bytes).await {
+        Ok(file) => file,
+        Err(e) => {
+            record_failure(row, e.to_string()).await;
+            return false;
+        }
+    };
+
+    // Servers often send `application/octet-stream`; the URL's extension is the next best hint.
+    let Some(mime) = file
+        .content_type
+        .filter(|mime| is_media_mime(mime))
+        .or_else(|| {
+            mime_guess::from_path(row.url.split(['?', '#']).next().unwrap_or_default())
+                .first()
+                .map(|mime| mime.essence_str().to_string())
+                .filter(|mime| is_media_mime(mime))
+        })
+    else {
+        record_failure(row, "Response is not an image or video".to_string()).await;
+        return false;
+    };
+
+    let stored = match store_media(&file.bytes, &mime).await {
+        Ok(stored) => stored,
+        Err(e) => {
+            error!(
+                target: targets::CRAWLER_POST,
+                media_id = row.id,
+                error = %e,
+                "Failed to write mirrored media"
+            );
+            record_failure(row, format!("Failed to store the file: {e}")).await;
+            return false;
+        }
+    };
+
+    let local_url = url(&format!("media/{}", stored.file_name));
+    match PostMediaRepository::set_mirrored(row, local_url, stored).await {
+        Ok(_) => true,
+        Err(e) => {
+            error!(
+                target: targets::CRAWLER_POST,
+                media_id = row.id,
+                error = %e,
+                "Failed to record mirrored media"
+            );
+            false
+        }
+    }
+}
+
+/// Note the failure and schedule the next attempt: 15 minutes, then 30, 60 and 120, after
+/// which the source URL is kept.
+async fn record_failure(row: &Model, reason: String) {
+    let attempts = row.mirror_attempts + 1;
+    let retry_at = (attempts < MAX_MIRROR_ATTEMPTS).then(|| {
+        Utc::now().naive_utc()
+            + Duration::minutes(MIRROR_BACKOFF_MINUTES << (attempts - 1).clamp(0, 16))
+    });
+    warn!(
+        target: targets::CRAWLER_POST,
+        media_id = row.id,
+        url = %row.url,
+        reason = %reason,
+        attempts,
+        retry_at = ?retry_at,
+        "Failed to mirror media"
+    );
+    if let Err(e) = PostMediaRepository::set_mirror_error(row, reason, retry_at).await {
+        error!(
+            target: targets::CRAWLER_POST,
+            media_id = row.id,
+            error = %e,
+            "Failed to record media mirror error"
+        );
+    }
+}
+
+fn is_media_mime(mime: &str) -> bool {
+    mime.starts_with("image/") || mime.starts_with("video/")
+}
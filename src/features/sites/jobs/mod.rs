@@ -1,3 +1,4 @@
 pub mod check_new_post;
 pub mod cleanup_posts;
 pub mod get_post_content;
+pub mod mirror_media;
@@ -5,5 +5,6 @@ pub mod jobs;
 pub mod model;
 pub mod repository;
 pub mod routes;
+pub mod service;
 pub mod utility;
 pub mod validation;
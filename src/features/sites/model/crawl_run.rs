@@ -0,0 +1,43 @@
+use crate::features::sites::model::site;
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "crawl_runs")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub job: String,
+    pub site_id: i64,
+    pub links_found: i32,
+    pub pages_crawled: i32,
+    pub posts_created: i32,
+    pub posts_updated: i32,
+    pub posts_processed: i32,
+    pub duplicates: i32,
+    pub failures: i32,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub error: Option<String>,
+    pub started_at: DateTime,
+    pub finished_at: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation, Serialize)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "site::Entity",
+        from = "Column::SiteId",
+        to = "site::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Site,
+}
+
+impl Related<site::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Site.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,5 +1,8 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0.0-rc.10
 
+pub mod crawl_run;
+pub mod post_media;
+pub mod post_revision;
 pub mod posts;
 pub mod prelude;
 pub mod site;
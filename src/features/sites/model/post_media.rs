@@ -0,0 +1,66 @@
+use crate::features::sites::model::posts;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "post_media")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    #[serde(skip)]
+    pub post_id: i64,
+    pub kind: MediaKind,
+    pub url: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub alt: Option<String>,
+    pub position: i32,
+    /// The source URL when `url` points at our mirrored copy.
+    pub original_url: Option<String>,
+    /// BLAKE3 hash of the mirrored file.
+    pub checksum: Option<String>,
+    pub size: Option<i64>,
+    pub mime: Option<String>,
+    /// Why the last download failed.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub mirror_error: Option<String>,
+    /// Failed downloads so far; the row is given up on after a few.
+    pub mirror_attempts: i32,
+    /// When a failed download is tried again.
+    pub mirror_retry_at: Option<DateTime>,
+    #[serde(skip)]
+    pub created_at: DateTime,
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, DeriveActiveEnum,
+)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    #[sea_orm(string_value = "image")]
+    Image,
+
+    #[sea_orm(string_value = "video")]
+    Video,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation, Serialize)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "posts::Entity",
+        from = "Column::PostId",
+        to = "posts::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Posts,
+}
+
+impl Related<posts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Posts.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,39 @@
+use crate::features::sites::model::posts;
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "post_revisions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub post_id: i64,
+    pub revision: i32,
+    pub title: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub body: Option<String>,
+    pub image: Option<String>,
+    pub video: Option<String>,
+    pub content_hash: String,
+    pub extracted_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation, Serialize)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "posts::Entity",
+        from = "Column::PostId",
+        to = "posts::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Posts,
+}
+
+impl Related<posts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Posts.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -11,11 +11,27 @@ pub struct Model {
     pub title: Option<String>,
     #[sea_orm(column_type = "Text", nullable)]
     pub body: Option<String>,
+    /// `body` as plain text and as Markdown, derived whenever the body is stored.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub body_text: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub body_markdown: Option<String>,
+    /// What produced `body` on the last extraction; `None` for bodies set through the API.
+    pub body_source: Option<BodySource>,
     pub image: Option<String>,
     pub video: Option<String>,
+    pub author: Option<String>,
+    pub published_at: Option<DateTime>,
     pub url: Option<String>,
     pub retry: i8,
     pub status: PostStatus,
+    pub content_hash: Option<String>,
+    pub revision: i32,
+    pub updated_at: Option<DateTime>,
+    pub checked_at: Option<DateTime>,
+    pub screenshot_path: Option<String>,
+    #[sea_orm(column_type = "Json", nullable)]
+    pub metadata: Option<Json>,
     pub site_id: i64,
     pub user_id: i64,
     pub api_key_id: i64,
@@ -38,6 +54,24 @@ pub enum PostStatus {
     CANCELLED,
 }
 
+/// Where an extracted body came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "lowercase")]
+pub enum BodySource {
+    /// The site's `path_content` selector.
+    #[sea_orm(string_value = "selector")]
+    Selector,
+
+    /// The automatic main-content extractor.
+    #[sea_orm(string_value = "extractor")]
+    Extractor,
+
+    /// The page's JSON-LD `articleBody`.
+    #[sea_orm(string_value = "metadata")]
+    Metadata,
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation, Serialize)]
 pub enum Relation {
     #[sea_orm(
@@ -1,4 +1,7 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0.0-rc.10
 
+pub use super::crawl_run::Entity as CrawlRun;
+pub use super::post_media::Entity as PostMedia;
+pub use super::post_revision::Entity as PostRevision;
 pub use super::posts::Entity as Posts;
 pub use super::site::Entity as Site;
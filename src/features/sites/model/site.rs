@@ -2,7 +2,7 @@
 
 use crate::features::users::model::{api_key, user};
 use sea_orm::entity::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "site")]
@@ -11,20 +11,79 @@ pub struct Model {
     pub id: i64,
     pub name: String,
     pub url: String,
+    /// The listing page, or the sitemap/feed URL when `source_type` says so.
     pub url_list: String,
+    pub source_type: SourceType,
+    /// Sitemap and feed entries dated longer ago than this are skipped (default 48).
+    pub max_age_hours: Option<i32>,
     pub path_link: Option<String>,
     pub path_title: Option<String>,
     pub path_content: Option<String>,
     pub path_image: Option<String>,
     pub path_video: Option<String>,
     pub path_remove: Option<String>,
+    /// Prefer the automatic main-content extractor over `path_content`.
+    pub auto_content: Option<bool>,
+    pub path_date: Option<String>,
+    pub path_author: Option<String>,
+    /// Selector chain for the listing's "next page" link.
+    pub path_next_page: Option<String>,
+    /// Listing URL for page `n` (from 2 on), e.g. `?page={n}`, resolved against `url_list`.
+    pub page_url_template: Option<String>,
+    /// How many listing pages a crawl may visit; 1 (the default) reads `url_list` only.
+    pub max_pages: Option<i32>,
     pub screenshot: Option<bool>,
+    pub mirror_media: Option<bool>,
+    pub mirror_videos: Option<bool>,
+    pub fetch_mode: FetchMode,
+    pub crawl_interval_minutes: Option<i32>,
+    pub crawl_cron: Option<String>,
+    pub next_crawl_at: Option<DateTime>,
+    pub recheck_window_hours: Option<i32>,
+    pub recheck_interval_minutes: Option<i32>,
     pub status: Option<bool>,
+    pub error_count: i32,
+    pub error_threshold: Option<i32>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub disabled_reason: Option<String>,
+    pub disabled_at: Option<DateTime>,
     pub user_id: i64,
     pub api_key_id: i64,
     pub created_at: DateTime,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "lowercase")]
+pub enum FetchMode {
+    #[sea_orm(string_value = "browser")]
+    Browser,
+
+    #[sea_orm(string_value = "http")]
+    Http,
+}
+
+/// Where a crawl finds post URLs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "lowercase")]
+pub enum SourceType {
+    /// `path_link` on the `url_list` page (and its following pages).
+    #[sea_orm(string_value = "listing")]
+    Listing,
+
+    /// A sitemap or sitemap index.
+    #[sea_orm(string_value = "sitemap")]
+    Sitemap,
+
+    /// An RSS 2.0, RSS 1.0 or Atom feed.
+    #[sea_orm(string_value = "feed")]
+    Feed,
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation, Serialize)]
 pub enum Relation {
     #[sea_orm(
@@ -0,0 +1,56 @@
+use crate::core::dto::pagination::Items;
+use crate::core::repository::paginate::paginate;
+use crate::features::sites::dto::crawl_stats::CrawlStats;
+use crate::features::sites::model::crawl_run;
+use crate::features::sites::model::crawl_run::{Column, Model};
+use crate::features::sites::model::prelude::CrawlRun;
+use crate::utility::state::app_state;
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set};
+
+pub struct CrawlRunRepository;
+
+impl CrawlRunRepository {
+    pub async fn start(job: &str, site_id: i64) -> Result<Model, DbErr> {
+        let state = app_state();
+        let am = crawl_run::ActiveModel {
+            job: Set(job.to_string()),
+            site_id: Set(site_id),
+            started_at: Set(Utc::now().naive_utc()),
+            ..Default::default()
+        };
+
+        am.insert(&state._db).await
+    }
+
+    pub async fn finish(run_id: i64, stats: &CrawlStats) -> Result<Model, DbErr> {
+        let state = app_state();
+        let am = crawl_run::ActiveModel {
+            id: Set(run_id),
+            links_found: Set(stats.links_found),
+            pages_crawled: Set(stats.pages_crawled),
+            posts_created: Set(stats.posts_created),
+            posts_updated: Set(stats.posts_updated),
+            posts_processed: Set(stats.posts_processed),
+            duplicates: Set(stats.duplicates),
+            failures: Set(stats.failures),
+            error: Set(stats.error.clone()),
+            finished_at: Set(Some(Utc::now().naive_utc())),
+            ..Default::default()
+        };
+
+        am.update(&state._db).await
+    }
+
+    pub async fn list_by_site(
+        site_id: i64,
+        page: u64,
+        per_page: u64,
+    ) -> Result<Items<Model>, DbErr> {
+        let state = app_state();
+        let q = CrawlRun::find()
+            .filter(Column::SiteId.eq(site_id))
+            .order_by_desc(Column::Id);
+        paginate::<crawl_run::Entity>(q, &state._db, page, per_page).await
+    }
+}
@@ -1,2 +1,6 @@
+pub mod crawl_run_repository;
+pub mod post_media_repository;
 pub mod post_repository;
+pub mod post_revision_repository;
+pub mod post_search_repository;
 pub mod site_repository;
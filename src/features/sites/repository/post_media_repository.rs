@@ -0,0 +1,298 @@
+use crate::core::dto::pagination::Items;
+use crate::features::sites::dto::post_with_media::PostWithMedia;
+use crate::features::sites::model::post_media::{Column, MediaKind, Model};
+use crate::features::sites::model::prelude::{PostMedia, Posts};
+use crate::features::sites::model::{post_media, posts, site};
+use crate::features::sites::utility::media_extractor::ExtractedMedia;
+use crate::features::sites::utility::media_store::StoredMedia;
+use crate::features::sites::utility::readable_text::readable_body;
+use crate::utility::state::app_state;
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DbErr, EntityTrait, JoinType, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, Set, TransactionTrait,
+};
+use std::collections::{HashMap, HashSet};
+
+pub struct PostMediaRepository;
+
+impl PostMediaRepository {
+    /// Make the post's media match `media`. Returns `false` when nothing changed, so
+    /// unchanged media keep their ids. Items are compared by their source URL, and items
+    /// that were already mirrored keep their local copy.
+    pub async fn replace(post_id: i64, media: &[ExtractedMedia]) -> Result<bool, DbErr> {
+        let state = app_state();
+        let existing = Self::list_by_post(post_id).await?;
+
+        let unchanged = existing.len() == media.len()
+            && existing.iter().zip(media).all(|(row, item)| {
+                row.kind == item.kind
+                    && Self::source_url(row) == item.url
+                    && row.width == item.width
+                    && row.height == item.height
+                    && row.alt == item.alt
+            });
+        if unchanged {
+            return Ok(false);
+        }
+
+        let mirrored: HashMap<(MediaKind, &str), &Model> = existing
+            .iter()
+            .filter(|row| row.checksum.is_some())
+            .map(|row| ((row.kind, Self::source_url(row)), row))
+            .collect();
+
+        let txn = state._db.begin().await?;
+        PostMedia::delete_many()
+            .filter(Column::PostId.eq(post_id))
+            .exec(&txn)
+            .await?;
+
+        if !media.is_empty() {
+            let rows = media.iter().enumerate().map(|(position, item)| {
+                let mut am = post_media::ActiveModel {
+                    post_id: Set(post_id),
+                    kind: Set(item.kind),
+                    url: Set(item.url.clone()),
+                    width: Set(item.width),
+                    height: Set(item.height),
+                    alt: Set(item.alt.clone()),
+                    position: Set(position as i32),
+                    ..Default::default()
+                };
+                if let Some(row) = mirrored.get(&(item.kind, item.url.as_str())) {
+                    am.url = Set(row.url.clone());
+                    am.original_url = Set(row.original_url.clone());
+                    am.checksum = Set(row.checksum.clone());
+                    am.size = Set(row.size);
+                    am.mime = Set(row.mime.clone());
+                }
+                am
+            });
+            PostMedia::insert_many(rows).exec(&txn).await?;
+        }
+
+        txn.commit().await?;
+        Ok(true)
+    }
+
+    /// Media of sites with `mirror_media` on that have no local copy, have failed fewer than
+    /// `max_attempts` times and are not waiting out a backoff. Videos only count when the
+    /// site also has `mirror_videos` on.
+    pub async fn pending_mirror(limit: u64, max_attempts: i32) -> Result<Vec<Model>, DbErr> {
+        let state = app_state();
+        let now = Utc::now().naive_utc();
+        PostMedia::find()
+            .join(JoinType::InnerJoin, post_media::Relation::Posts.def())
+            .join(JoinType::InnerJoin, posts::Relation::Site.def())
+            .filter(site::Column::MirrorMedia.eq(true))
+            .filter(Column::Checksum.is_null())
+            .filter(Column::MirrorAttempts.lt(max_attempts))
+            .filter(
+                Condition::any()
+                    .add(Column::MirrorRetryAt.is_null())
+                    .add(Column::MirrorRetryAt.lte(now)),
+            )
+            .filter(
+                Condition::any()
+                    .add(Column::Kind.eq(MediaKind::Image))
+                    .add(site::Column::MirrorVideos.eq(true)),
+            )
+            .order_by_asc(Column::Id)
+            .limit(limit)
+            .all(&state._db)
+            .await
+    }
+
+    /// Point a media row at its local copy, keeping the source URL in `original_url`.
+    pub async fn set_mirrored(
+        row: &Model,
+        url: String,
+        stored: StoredMedia,
+    ) -> Result<Model, DbErr> {
+        let state = app_state();
+        let am = post_media::ActiveModel {
+            id: Set(row.id),
+            url: Set(url),
+            original_url: Set(Some(row.url.clone())),
+            checksum: Set(Some(stored.checksum)),
+            size: Set(Some(stored.size)),
+            mime: Set(Some(stored.mime)),
+            mirror_error: Set(None),
+            mirror_retry_at: Set(None),
+            ..Default::default()
+        };
+        am.update(&state._db).await
+    }
+
+    /// Record why a download failed; the row keeps its source URL and is tried again at
+    /// `retry_at`, or never when that is `None`.
+    pub async fn set_mirror_error(
+        row: &Model,
+        reason: String,
+        retry_at: Option<NaiveDateTime>,
+    ) -> Result<(), DbErr> {
+        let state = app_state();
+        let am = post_media::ActiveModel {
+            id: Set(row.id),
+            mirror_error: Set(Some(reason)),
+            mirror_attempts: Set(row.mirror_attempts + 1),
+            mirror_retry_at: Set(retry_at),
+            ..Default::default()
+        };
+        am.update(&state._db).await.map(|_| ())
+    }
+
+    /// Point the post at the mirrored copies of its media: its own `image`/`video`, and the
+    /// source URLs inside the stored body, whose Markdown is derived again. Feeds read the
+    /// body, so they follow.
+    pub async fn relink_post(post_id: i64) -> Result<(), DbErr> {
+        let state = app_state();
+        let Some(post) = Posts::find_by_id(post_id).one(&state._db).await? else {
+            return Ok(());
+        };
+
+        let media = Self::list_by_post(post_id).await?;
+        let local_url = |kind: MediaKind, current: &Option<String>| {
+            let current = current.as_deref()?;
+            media
+                .iter()
+                .find(|row| row.kind == kind && row.original_url.as_deref() == Some(current))
+                .map(|row| row.url.clone())
+        };
+
+        let image = local_url(MediaKind::Image, &post.image);
+        let video = local_url(MediaKind::Video, &post.video);
+        let mirrored: Vec<(&str, &str)> = media
+            .iter()
+            .filter_map(|row| Some((row.original_url.as_deref()?, row.url.as_str())))
+            .collect();
+        let body = post
+            .body
+            .as_deref()
+            .and_then(|body| rewrite_urls(body, &mirrored));
+        if image.is_none() && video.is_none() && body.is_none() {
+            return Ok(());
+        }
+
+        let mut am = posts::ActiveModel {
+            id: Set(post_id),
+            ..Default::default()
+        };
+        if image.is_some() {
+            am.image = Set(image);
+        }
+        if video.is_some() {
+            am.video = Set(video);
+        }
+        if let Some(body) = body {
+            am.body_markdown = Set(Some(readable_body(&body).markdown));
+            am.body = Set(Some(body));
+        }
+        am.update(&state._db).await.map(|_| ())
+    }
+
+    /// Checksums still referenced by some post, to tell which stored files are orphans.
+    pub async fn mirrored_checksums() -> Result<HashSet<String>, DbErr> {
+        let state = app_state();
+        let checksums: Vec<Option<String>> = PostMedia::find()
+            .select_only()
+            .column(Column::Checksum)
+            .filter(Column::Checksum.is_not_null())
+            .distinct()
+            .into_tuple()
+            .all(&state._db)
+            .await?;
+
+        Ok(checksums.into_iter().flatten().collect())
+    }
+
+    pub async fn list_by_post(post_id: i64) -> Result<Vec<Model>, DbErr> {
+        let state = app_state();
+        PostMedia::find()
+            .filter(Column::PostId.eq(post_id))
+            .order_by_asc(Column::Position)
+            .all(&state._db)
+            .await
+    }
+
+    pub async fn attach(post: posts::Model) -> Result<PostWithMedia, DbErr> {
+        let media = Self::list_by_post(post.id).await?;
+        Ok(PostWithMedia { post, media })
+    }
+
+    /// Nest each post's media into a page of posts with a single query.
+    pub async fn attach_all(items: Items<posts::Model>) -> Result<Items<PostWithMedia>, DbErr> {
+        let state = app_state();
+        let ids: Vec<i64> = items.items.iter().map(|post| post.id).collect();
+
+        let mut by_post: HashMap<i64, Vec<Model>> = HashMap::new();
+        if !ids.is_empty() {
+            let rows = PostMedia::find()
+                .filter(Column::PostId.is_in(ids))
+                .order_by_asc(Column::PostId)
+                .order_by_asc(Column::Position)
+                .all(&state._db)
+                .await?;
+            for row in rows {
+                by_post.entry(row.post_id).or_default().push(row);
+            }
+        }
+
+        Ok(Items {
+            items: items
+                .items
+                .into_iter()
+                .map(|post| PostWithMedia {
+                    media: by_post.remove(&post.id).unwrap_or_default(),
+                    post,
+                })
+                .collect(),
+            page: items.page,
+            per_page: items.per_page,
+            total: items.total,
+            total_pages: items.total_pages,
+        })
+    }
+
+    fn source_url(row: &Model) -> &str {
+        row.original_url.as_deref().unwrap_or(&row.url)
+    }
+}
+
+/// `html` with each `(from, to)` URL replaced, or `None` if none occurs. A URL only counts
+/// where it ends, so `a.jpg` doesn't rewrite the start of `a.jpg?w=2`; the `&amp;` form
+/// of query strings in attributes is matched too.
+fn rewrite_urls(html: &str, urls: &[(&str, &str)]) -> Option<String> {
+    let mut rewritten = html.to_string();
+    let mut changed = false;
+
+    for (from, to) in urls {
+        for (from, to) in [
+            (from.to_string(), to.to_string()),
+            (from.replace('&', "&amp;"), to.replace('&', "&amp;")),
+        ] {
+            if from.is_empty() || !rewritten.contains(&from) {
+                continue;
+            }
+
+            let mut out = String::with_capacity(rewritten.len());
+            let mut rest = rewritten.as_str();
+            while let Some(index) = rest.find(&from) {
+                let after = &rest[index + from.len()..];
+                let ends = after
+                    .chars()
+                    .next()
+                    .is_none_or(|c| c.is_whitespace() || matches!(c, '"' | '\'' | ')' | ',' | '<'));
+                out.push_str(&rest[..index]);
+                out.push_str(if ends { &to } else { &from });
+                changed |= ends;
+                rest = after;
+            }
+            out.push_str(rest);
+            rewritten = out;
+        }
+    }
+
+    changed.then_some(rewritten)
+}
@@ -1,16 +1,32 @@
 use crate::core::dto::pagination::Items;
+use crate::core::logger::targets;
 use crate::core::repository::paginate::paginate;
 use crate::core::state::AppState;
+use crate::features::sites::dto::list_params::PostSort;
+use crate::features::sites::dto::post_with_media::PostWithMedia;
 use crate::features::sites::model::posts::{Column, Model, PostStatus};
 use crate::features::sites::model::prelude::Posts;
 use crate::features::sites::model::{posts, site};
+use crate::features::sites::repository::post_media_repository::PostMediaRepository;
+use crate::features::sites::repository::post_revision_repository::PostRevisionRepository;
+use crate::features::sites::repository::post_search_repository::PostSearchRepository;
+use crate::features::sites::service::post_stream::{PostChangeKind, PostStream};
+use crate::features::sites::utility::content_hash::content_hash;
+use crate::features::sites::utility::readable_text::readable_body;
 use crate::features::sites::validation::post_form::{PostForm, PostFormCreate};
+use crate::features::webhooks::repository::webhook_delivery_repository::WebhookDeliveryRepository;
+use crate::features::webhooks::utility::post_event::PostEvent;
 use crate::utility::state::app_state;
+use chrono::{NaiveDateTime, Utc};
 use sea_orm::ColumnTrait;
+use sea_orm::sea_query::SimpleExpr;
 use sea_orm::{
-    ActiveModelTrait, DbErr, DeleteResult, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
-    Select, Set,
+    ActiveModelTrait, Condition, DbErr, DeleteResult, EntityTrait, NotSet, QueryFilter, QueryOrder,
+    QuerySelect, QueryTrait, Select, Set, TransactionTrait,
 };
+use serde_json::Value;
+use std::collections::HashSet;
+use tracing::warn;
 
 pub struct PostRepository;
 
@@ -19,8 +35,9 @@ impl PostRepository {
         page: u64,
         per_page: u64,
         post_id: Option<i64>,
-    ) -> Result<Items<Model>, DbErr> {
-        let query = Self::build_list_query(post_id);
+        sort: PostSort,
+    ) -> Result<Items<PostWithMedia>, DbErr> {
+        let query = Self::build_list_query(post_id, sort);
         Self::paginate_query(query, page, per_page).await
     }
 
@@ -29,8 +46,9 @@ impl PostRepository {
         page: u64,
         per_page: u64,
         post_id: Option<i64>,
-    ) -> Result<Items<Model>, DbErr> {
-        let query = Self::build_list_query(post_id).filter(Column::SiteId.eq(site_id));
+        sort: PostSort,
+    ) -> Result<Items<PostWithMedia>, DbErr> {
+        let query = Self::build_list_query(post_id, sort).filter(Column::SiteId.eq(site_id));
         Self::paginate_query(query, page, per_page).await
     }
 
@@ -39,8 +57,9 @@ impl PostRepository {
         page: u64,
         per_page: u64,
         post_id: Option<i64>,
-    ) -> Result<Items<Model>, DbErr> {
-        let query = Self::build_list_query(post_id).filter(Column::UserId.eq(user_id));
+        sort: PostSort,
+    ) -> Result<Items<PostWithMedia>, DbErr> {
+        let query = Self::build_list_query(post_id, sort).filter(Column::UserId.eq(user_id));
         Self::paginate_query(query, page, per_page).await
     }
 
@@ -49,15 +68,30 @@ impl PostRepository {
         page: u64,
         per_page: u64,
         post_id: Option<i64>,
-    ) -> Result<Items<Model>, DbErr> {
-        let query = Self::build_list_query(post_id).filter(Column::ApiKeyId.eq(api_key_id));
+        sort: PostSort,
+    ) -> Result<Items<PostWithMedia>, DbErr> {
+        let query = Self::build_list_query(post_id, sort).filter(Column::ApiKeyId.eq(api_key_id));
         Self::paginate_query(query, page, per_page).await
     }
 
     pub async fn pending_list() -> Result<Vec<(Model, site::Model)>, DbErr> {
+        Self::pending_rows(None).await
+    }
+
+    pub async fn pending_list_by_site(site_id: i64) -> Result<Vec<(Model, site::Model)>, DbErr> {
+        Self::pending_rows(Some(site_id)).await
+    }
+
+    async fn pending_rows(site_id: Option<i64>) -> Result<Vec<(Model, site::Model)>, DbErr> {
         let state = app_state();
-        let rows = Posts::find()
-            .filter(Column::Status.is_in([PostStatus::PENDING, PostStatus::FAILED]))
+        let mut query =
+            Posts::find().filter(Column::Status.is_in([PostStatus::PENDING, PostStatus::FAILED]));
+
+        if let Some(site_id) = site_id {
+            query = query.filter(Column::SiteId.eq(site_id));
+        }
+
+        let rows = query
             .order_by_desc(Column::Id)
             .find_also_related(site::Entity)
             .all(&state._db)
@@ -72,6 +106,34 @@ impl PostRepository {
         Ok(rows)
     }
 
+    /// The latest completed posts of a site, newest crawl first, for its outbound feed.
+    pub async fn feed_by_site(site_id: i64, limit: u64) -> Result<Vec<Model>, DbErr> {
+        Self::feed_rows(Column::SiteId.eq(site_id), limit).await
+    }
+
+    /// The latest completed posts crawled through an API key, for its aggregated feed.
+    pub async fn feed_by_api_key(api_key_id: i64, limit: u64) -> Result<Vec<Model>, DbErr> {
+        Self::feed_rows(Column::ApiKeyId.eq(api_key_id), limit).await
+    }
+
+    /// Posts created after `post_id`, oldest first, optionally only those of one API key.
+    pub async fn created_after(
+        api_key_id: Option<i64>,
+        post_id: i64,
+        limit: u64,
+    ) -> Result<Vec<Model>, DbErr> {
+        let state = app_state();
+        Posts::find()
+            .filter(Column::Id.gt(post_id))
+            .apply_if(api_key_id, |q, api_key_id| {
+                q.filter(Column::ApiKeyId.eq(api_key_id))
+            })
+            .order_by_asc(Column::Id)
+            .limit(limit)
+            .all(&state._db)
+            .await
+    }
+
     pub async fn find_by_id(post_id: i64) -> Result<Option<Model>, DbErr> {
         let state = app_state();
         posts::Entity::find_by_id(post_id).one(&state._db).await
@@ -96,11 +158,16 @@ impl PostRepository {
         };
 
         match am.insert(&state._db).await {
-            Ok(model) => Ok(Some(model)),
+            Ok(model) => {
+                PostStream::publish(PostChangeKind::Created, &model);
+                Ok(Some(model))
+            }
             Err(e) => Err(e),
         }
     }
 
+    /// Store extracted content. The revision counter and `updated_at` only move when the
+    /// content hash changes; an identical re-fetch just records `checked_at`.
     pub async fn update(post_id: i64, data: PostForm) -> Result<Option<Model>, DbErr> {
         let state = app_state();
 
@@ -108,13 +175,103 @@ impl PostRepository {
             return Ok(None);
         };
 
-        let retry = Self::next_retry(existing.retry);
-        let am = Self::build_content_active_model(post_id, retry, data);
+        let now = Utc::now().naive_utc();
+        let hash = content_hash(
+            data.title.as_deref().unwrap_or_default(),
+            data.body.as_deref().unwrap_or_default(),
+            data.image.as_deref().unwrap_or_default(),
+            data.video.as_deref().unwrap_or_default(),
+        );
+
+        if existing.content_hash.as_deref() == Some(hash.as_str()) && existing.status == data.status
+        {
+            // Details read alongside the content (author, date, metadata) may still have changed.
+            let mut am = Self::build_details_active_model(
+                post_id,
+                data.author,
+                data.published_at,
+                data.metadata,
+            );
+            if data.body_source.is_some() {
+                am.body_source = Set(data.body_source);
+            }
+            am.checked_at = Set(Some(now));
+            return am.update(&state._db).await.map(Some);
+        }
+
+        // Re-checks of a completed post are not retries.
+        let retry = match existing.status {
+            PostStatus::COMPLETED => existing.retry,
+            _ => Self::next_retry(existing.retry),
+        };
+        let mut am = Self::build_content_active_model(post_id, retry, data);
+        am.content_hash = Set(Some(hash));
+        am.revision = Set(existing.revision + 1);
+        am.updated_at = Set(Some(now));
+        am.checked_at = Set(Some(now));
+
+        let txn = state._db.begin().await?;
+        // Posts extracted before revision history existed get their last version kept too.
+        PostRevisionRepository::record(&txn, &existing).await?;
+        let updated = am.update(&txn).await?;
+        PostRevisionRepository::record(&txn, &updated).await?;
+        if let Some(event) = PostEvent::between(&existing.status, &updated.status) {
+            WebhookDeliveryRepository::enqueue(&txn, event, &updated).await?;
+        }
+        txn.commit().await?;
+
+        PostStream::publish(PostChangeKind::Updated, &updated);
+        Ok(Some(updated))
+    }
+
+    pub async fn mark_checked(post_id: i64) -> Result<Option<Model>, DbErr> {
+        let state = app_state();
+
+        let am = posts::ActiveModel {
+            id: Set(post_id),
+            checked_at: Set(Some(Utc::now().naive_utc())),
+            ..Default::default()
+        };
 
         let updated = am.update(&state._db).await?;
         Ok(Some(updated))
     }
 
+    /// Point the post at its stored screenshot, relative to the media directory.
+    pub async fn set_screenshot(post_id: i64, path: String) -> Result<Option<Model>, DbErr> {
+        let state = app_state();
+
+        let am = posts::ActiveModel {
+            id: Set(post_id),
+            screenshot_path: Set(Some(path)),
+            ..Default::default()
+        };
+
+        let updated = am.update(&state._db).await?;
+        Ok(Some(updated))
+    }
+
+    /// Completed posts of a site created after `created_after` and not checked since `checked_before`.
+    pub async fn recheck_list(
+        site_id: i64,
+        created_after: NaiveDateTime,
+        checked_before: NaiveDateTime,
+    ) -> Result<Vec<Model>, DbErr> {
+        let state = app_state();
+        Posts::find()
+            .filter(Column::SiteId.eq(site_id))
+            .filter(Column::Status.eq(PostStatus::COMPLETED))
+            .filter(Column::CreatedAt.gte(created_after))
+            .filter(
+                Condition::any()
+                    .add(Column::CheckedAt.is_null())
+                    .add(Column::CheckedAt.lte(checked_before)),
+            )
+            .order_by_desc(Column::Id)
+            .all(&state._db)
+            .await
+    }
+
     pub async fn update_failed(post_id: i64) -> Result<Option<Model>, DbErr> {
         let state = app_state();
 
@@ -122,18 +279,31 @@ impl PostRepository {
             return Ok(None);
         };
 
+        // A failed re-check keeps the last good content.
+        if existing.status == PostStatus::COMPLETED {
+            return Self::mark_checked(post_id).await;
+        }
+
         let retry = Self::next_retry(existing.retry);
         let status = Self::resolve_failure_status(retry, state.config.max_retry_post as i8);
         let am = Self::build_failure_active_model(post_id, retry, status);
 
-        let updated = am.update(&state._db).await?;
+        let txn = state._db.begin().await?;
+        let updated = am.update(&txn).await?;
+        if let Some(event) = PostEvent::between(&existing.status, &updated.status) {
+            WebhookDeliveryRepository::enqueue(&txn, event, &updated).await?;
+        }
+        txn.commit().await?;
+
+        PostStream::publish(PostChangeKind::Failed, &updated);
         Ok(Some(updated))
     }
 
-    pub async fn delete(post_id: i64) -> Result<bool, String> {
+    /// Delete a post and return it, so the caller can remove its files.
+    pub async fn delete(post_id: i64) -> Result<Model, String> {
         let state = app_state();
 
-        let Some(_existing) = posts::Entity::find_by_id(post_id)
+        let Some(existing) = posts::Entity::find_by_id(post_id)
             .one(&state._db)
             .await
             .map_err(|e| e.to_string())?
@@ -144,10 +314,24 @@ impl PostRepository {
         posts::Entity::delete_by_id(post_id)
             .exec(&state._db)
             .await
-            .map(|_| true)
+            .map(|_| existing)
             .map_err(|e| e.to_string())
     }
 
+    /// Screenshot paths that posts still point at.
+    pub async fn screenshot_paths() -> Result<HashSet<String>, DbErr> {
+        let state = app_state();
+        let paths: Vec<Option<String>> = Posts::find()
+            .select_only()
+            .column(Column::ScreenshotPath)
+            .filter(Column::ScreenshotPath.is_not_null())
+            .into_tuple()
+            .all(&state._db)
+            .await?;
+
+        Ok(paths.into_iter().flatten().collect())
+    }
+
     pub async fn cleanup_old_posts(keep_latest: u64) -> Result<u64, DbErr> {
         if keep_latest == 0 {
             return Ok(0);
@@ -171,6 +355,14 @@ impl PostRepository {
             .exec(&state._db)
             .await?;
 
+        // The triggers removed the rows from the search index; compact what they left.
+        // The posts are gone either way, so a failed (e.g. busy) merge only waits for the next run.
+        if result.rows_affected > 0
+            && let Err(e) = PostSearchRepository::optimize().await
+        {
+            warn!(target: targets::SYSTEM, error = %e, "Search index optimize failed");
+        }
+
         Ok(result.rows_affected)
     }
 
@@ -183,14 +375,38 @@ impl PostRepository {
     }
 
     fn build_content_active_model(post_id: i64, retry: i8, data: PostForm) -> posts::ActiveModel {
+        let mut am = Self::build_details_active_model(
+            post_id,
+            data.author,
+            data.published_at,
+            data.metadata,
+        );
+        let readable = data.body.as_deref().map(readable_body);
+        am.title = Set(data.title);
+        am.body = Set(data.body);
+        am.body_text = Set(readable.as_ref().map(|r| r.text.clone()));
+        am.body_markdown = Set(readable.map(|r| r.markdown));
+        am.body_source = Set(data.body_source);
+        am.image = Set(data.image);
+        am.video = Set(data.video);
+        am.status = Set(data.status);
+        am.retry = Set(retry);
+        am
+    }
+
+    /// Details that are only overwritten when a value was found; a page that stops
+    /// showing its author or date keeps the last known one.
+    fn build_details_active_model(
+        post_id: i64,
+        author: Option<String>,
+        published_at: Option<NaiveDateTime>,
+        metadata: Option<Value>,
+    ) -> posts::ActiveModel {
         posts::ActiveModel {
             id: Set(post_id),
-            title: Set(data.title),
-            body: Set(data.body),
-            image: Set(data.image),
-            video: Set(data.video),
-            status: Set(data.status),
-            retry: Set(retry),
+            author: author.map_or(NotSet, |author| Set(Some(author))),
+            published_at: published_at.map_or(NotSet, |published_at| Set(Some(published_at))),
+            metadata: metadata.map_or(NotSet, |metadata| Set(Some(metadata))),
             ..Default::default()
         }
     }
@@ -216,8 +432,13 @@ impl PostRepository {
         }
     }
 
-    fn build_list_query(post_id: Option<i64>) -> Select<posts::Entity> {
-        let mut query = Posts::find().order_by_desc(Column::Id);
+    fn build_list_query(post_id: Option<i64>, sort: PostSort) -> Select<posts::Entity> {
+        let mut query = match sort {
+            PostSort::Id => Posts::find(),
+            // SQLite sorts NULL lowest, so undated posts trail the dated ones.
+            PostSort::PublishedAt => Posts::find().order_by_desc(Column::PublishedAt),
+        }
+        .order_by_desc(Column::Id);
 
         if let Some(min_id) = post_id {
             query = query.filter(Column::Id.gt(min_id));
@@ -226,12 +447,24 @@ impl PostRepository {
         query
     }
 
+    async fn feed_rows(filter: SimpleExpr, limit: u64) -> Result<Vec<Model>, DbErr> {
+        let state = app_state();
+        Posts::find()
+            .filter(filter)
+            .filter(Column::Status.eq(PostStatus::COMPLETED))
+            .order_by_desc(Column::Id)
+            .limit(limit)
+            .all(&state._db)
+            .await
+    }
+
     async fn paginate_query(
         query: Select<posts::Entity>,
         page: u64,
         per_page: u64,
-    ) -> Result<Items<Model>, DbErr> {
+    ) -> Result<Items<PostWithMedia>, DbErr> {
         let state = app_state();
-        paginate::<posts::Entity>(query, &state._db, page, per_page).await
+        let items = paginate::<posts::Entity>(query, &state._db, page, per_page).await?;
+        PostMediaRepository::attach_all(items).await
     }
 }
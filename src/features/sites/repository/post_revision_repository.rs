@@ -0,0 +1,66 @@
+use crate::core::dto::pagination::Items;
+use crate::core::repository::paginate::paginate;
+use crate::features::sites::model::post_revision::{Column, Model};
+use crate::features::sites::model::prelude::PostRevision;
+use crate::features::sites::model::{post_revision, posts};
+use crate::utility::state::app_state;
+use chrono::Utc;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set};
+
+pub struct PostRevisionRepository;
+
+impl PostRevisionRepository {
+    /// Snapshot a post's current content as its numbered revision. Recording the same
+    /// revision twice is a no-op.
+    pub async fn record<C: ConnectionTrait>(db: &C, post: &posts::Model) -> Result<(), DbErr> {
+        let Some(hash) = post.content_hash.clone() else {
+            return Ok(());
+        };
+
+        let am = post_revision::ActiveModel {
+            post_id: Set(post.id),
+            revision: Set(post.revision),
+            title: Set(post.title.clone()),
+            body: Set(post.body.clone()),
+            image: Set(post.image.clone()),
+            video: Set(post.video.clone()),
+            content_hash: Set(hash),
+            extracted_at: Set(post.updated_at.unwrap_or_else(|| Utc::now().naive_utc())),
+            ..Default::default()
+        };
+
+        PostRevision::insert(am)
+            .on_conflict(
+                OnConflict::columns([Column::PostId, Column::Revision])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn list_by_post(
+        post_id: i64,
+        page: u64,
+        per_page: u64,
+    ) -> Result<Items<Model>, DbErr> {
+        let state = app_state();
+        let q = PostRevision::find()
+            .filter(Column::PostId.eq(post_id))
+            .order_by_desc(Column::Revision);
+        paginate::<post_revision::Entity>(q, &state._db, page, per_page).await
+    }
+
+    pub async fn find(post_id: i64, revision: i32) -> Result<Option<Model>, DbErr> {
+        let state = app_state();
+        PostRevision::find()
+            .filter(Column::PostId.eq(post_id))
+            .filter(Column::Revision.eq(revision))
+            .one(&state._db)
+            .await
+    }
+}
@@ -0,0 +1,140 @@
+use crate::core::dto::pagination::Items;
+use crate::features::sites::dto::post_search::PostSearchHit;
+use crate::features::sites::model::posts::{Column, Model};
+use crate::features::sites::model::prelude::Posts;
+use crate::features::sites::utility::search_query::{MATCH_END, MATCH_START, mark_matches};
+use crate::utility::state::app_state;
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::{Alias, Asterisk, Expr, ExprTrait, Func, Order, Query, SelectStatement};
+use sea_orm::{ConnectionTrait, DbErr, FromQueryResult};
+
+/// The FTS5 index over post titles and `body_text`, kept up to date by triggers.
+const FTS_TABLE: &str = "posts_fts";
+/// Tokens in a body snippet.
+const SNIPPET_TOKENS: u32 = 24;
+
+/// Which posts a search may return.
+pub struct SearchScope {
+    /// `None` for admins, who search every post.
+    pub api_key_id: Option<i64>,
+    pub site_id: Option<i64>,
+    /// Publication time (else discovery time) range; `from` inclusive, `to` exclusive.
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+}
+
+pub struct PostSearchRepository;
+
+impl PostSearchRepository {
+    /// Posts matching an FTS5 `MATCH` expression, most relevant first.
+    pub async fn search(
+        expression: &str,
+        scope: &SearchScope,
+        page: u64,
+        per_page: u64,
+    ) -> Result<Items<PostSearchHit>, DbErr> {
+        let db = &app_state()._db;
+        let per = per_page.clamp(1, 100);
+        let offset = page.saturating_sub(1) * per;
+
+        let count = Self::matching(expression, scope)
+            .expr_as(Expr::cust("COUNT(*)"), Alias::new("total"))
+            .to_owned();
+        let total = match db.query_one(&count).await? {
+            Some(row) => row.try_get::<i64>("", "total")? as u64,
+            None => 0,
+        };
+
+        let fts = Alias::new(FTS_TABLE);
+        let select = Self::matching(expression, scope)
+            .column((Posts, Asterisk))
+            .expr_as(
+                Expr::cust(format!(
+                    "highlight({FTS_TABLE}, 0, '{MATCH_START}', '{MATCH_END}')"
+                )),
+                Alias::new("title_highlight"),
+            )
+            .expr_as(
+                Expr::cust(format!(
+                    "snippet({FTS_TABLE}, 1, '{MATCH_START}', '{MATCH_END}', '…', {SNIPPET_TOKENS})"
+                )),
+                Alias::new("snippet"),
+            )
+            .expr_as(
+                Expr::col((fts.clone(), Alias::new("rank"))),
+                Alias::new("rank"),
+            )
+            .order_by((fts, Alias::new("rank")), Order::Asc)
+            .order_by((Posts, Column::Id), Order::Desc)
+            .limit(per)
+            .offset(offset)
+            .to_owned();
+
+        let hits = db
+            .query_all(&select)
+            .await?
+            .into_iter()
+            .map(|row| {
+                let title: Option<String> = row.try_get("", "title_highlight")?;
+                let snippet: Option<String> = row.try_get("", "snippet")?;
+                Ok(PostSearchHit {
+                    post: Model::from_query_result(&row, "")?,
+                    title_highlight: title.as_deref().map(mark_matches),
+                    snippet: snippet
+                        .filter(|snippet| !snippet.is_empty())
+                        .as_deref()
+                        .map(mark_matches),
+                    rank: row.try_get("", "rank")?,
+                })
+            })
+            .collect::<Result<Vec<_>, DbErr>>()?;
+
+        Ok(Items::new(hits, page, per, total))
+    }
+
+    /// Merge the index segments left behind by bulk deletes.
+    pub async fn optimize() -> Result<(), DbErr> {
+        let db = &app_state()._db;
+        db.execute_unprepared(&format!(
+            "INSERT INTO {FTS_TABLE} ({FTS_TABLE}) VALUES ('optimize')"
+        ))
+        .await?;
+        Ok(())
+    }
+
+    /// The matching rows, joined to their posts and filtered to the scope.
+    fn matching(expression: &str, scope: &SearchScope) -> SelectStatement {
+        let fts = Alias::new(FTS_TABLE);
+        let posted_at = Func::coalesce([
+            Expr::col((Posts, Column::PublishedAt)),
+            Expr::col((Posts, Column::CreatedAt)),
+        ]);
+
+        let mut query = Query::select();
+        query
+            .from(fts.clone())
+            .inner_join(
+                Posts,
+                Expr::col((Posts, Column::Id)).equals((fts.clone(), Alias::new("rowid"))),
+            )
+            .and_where(Expr::cust_with_values(
+                format!("{FTS_TABLE} MATCH ?"),
+                [expression],
+            ));
+
+        if let Some(api_key_id) = scope.api_key_id {
+            query.and_where(Expr::col((Posts, Column::ApiKeyId)).eq(api_key_id));
+        }
+        if let Some(site_id) = scope.site_id {
+            query.and_where(Expr::col((Posts, Column::SiteId)).eq(site_id));
+        }
+        if let Some(from) = scope.from {
+            query.and_where(Expr::expr(posted_at.clone()).gte(from));
+        }
+        if let Some(to) = scope.to {
+            query.and_where(Expr::expr(posted_at).lt(to));
+        }
+
+        query
+    }
+}
@@ -2,12 +2,15 @@ use crate::core::dto::pagination::Items;
 use crate::core::repository::paginate::paginate;
 use crate::features::sites::model::prelude::Site;
 use crate::features::sites::model::site;
-use crate::features::sites::model::site::{Column, Model};
+use crate::features::sites::model::site::{Column, FetchMode, Model, SourceType};
 use crate::features::sites::validation::site_form::SiteForm;
 use crate::features::users::model::{api_key, user};
 use crate::utility::state::app_state;
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QueryTrait, Set,
+    ActiveModelTrait, ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QueryTrait, Set,
 };
 use std::ops::Not;
 use std::sync::Arc;
@@ -76,15 +79,44 @@ impl SiteRepository {
             .await
     }
 
-    pub async fn all() -> Result<Vec<Model>, DbErr> {
+    /// Enabled sites whose `next_crawl_at` has passed (or was never set), most overdue first.
+    pub async fn due(now: NaiveDateTime) -> Result<Vec<Model>, DbErr> {
         let state = app_state();
         Site::find()
             .filter(Column::Status.eq(true))
+            .filter(
+                Condition::any()
+                    .add(Column::NextCrawlAt.is_null())
+                    .add(Column::NextCrawlAt.lte(now)),
+            )
+            .order_by_asc(Column::NextCrawlAt)
             .order_by_desc(Column::Id)
             .all(&state._db)
             .await
     }
 
+    /// Enabled sites that re-extract their completed posts for a while after publishing.
+    pub async fn with_recheck() -> Result<Vec<Model>, DbErr> {
+        let state = app_state();
+        Site::find()
+            .filter(Column::Status.eq(true))
+            .filter(Column::RecheckWindowHours.is_not_null())
+            .all(&state._db)
+            .await
+    }
+
+    pub async fn schedule_next(site_id: i64, next_crawl_at: NaiveDateTime) -> Result<(), DbErr> {
+        let state = app_state();
+
+        site::Entity::update_many()
+            .col_expr(Column::NextCrawlAt, Expr::value(next_crawl_at))
+            .filter(Column::Id.eq(site_id))
+            .exec(&state._db)
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn create(data: SiteForm) -> Result<Option<Model>, DbErr> {
         let state = app_state();
 
@@ -97,14 +129,30 @@ impl SiteRepository {
             name: Set(data.name),
             url: Set(data.url),
             url_list: Set(data.url_list),
+            source_type: Set(data.source_type.unwrap_or(SourceType::Listing)),
+            max_age_hours: Set(data.max_age_hours),
             path_link: Set(data.path_link),
             path_title: Set(data.path_title),
             path_content: Set(data.path_content),
             path_image: Set(data.path_image),
             path_video: Set(data.path_video),
             path_remove: Set(data.path_remove),
+            auto_content: Set(data.auto_content),
+            path_date: Set(data.path_date),
+            path_author: Set(data.path_author),
+            path_next_page: Set(data.path_next_page),
+            page_url_template: Set(data.page_url_template),
+            max_pages: Set(data.max_pages),
             screenshot: Set(data.screenshot),
+            mirror_media: Set(data.mirror_media),
+            mirror_videos: Set(data.mirror_videos),
+            fetch_mode: Set(data.fetch_mode.unwrap_or(FetchMode::Browser)),
+            crawl_interval_minutes: Set(data.crawl_interval_minutes),
+            crawl_cron: Set(data.crawl_cron.filter(|expr| !expr.trim().is_empty())),
+            recheck_window_hours: Set(data.recheck_window_hours),
+            recheck_interval_minutes: Set(data.recheck_interval_minutes),
             status: Set(data.status),
+            error_threshold: Set(data.error_threshold),
             user_id: Set(user_id),
             api_key_id: Set(api_key_id),
             ..Default::default()
@@ -119,31 +167,56 @@ impl SiteRepository {
     pub async fn update(site_id: i64, data: SiteForm) -> Result<Option<Model>, DbErr> {
         let state = app_state();
 
-        let Some(_existing) = site::Entity::find_by_id(site_id).one(&state._db).await? else {
+        let Some(existing) = site::Entity::find_by_id(site_id).one(&state._db).await? else {
             return Ok(None);
         };
 
-        let am = site::ActiveModel {
+        let mut am = site::ActiveModel {
             id: Set(site_id),
             name: Set(data.name),
             url: Set(data.url),
             url_list: Set(data.url_list),
+            source_type: Set(data.source_type.unwrap_or(SourceType::Listing)),
+            max_age_hours: Set(data.max_age_hours),
             path_link: Set(data.path_link),
             path_title: Set(data.path_title),
             path_content: Set(data.path_content),
             path_image: Set(data.path_image),
             path_video: Set(data.path_video),
             path_remove: Set(data.path_remove),
+            auto_content: Set(data.auto_content),
+            path_date: Set(data.path_date),
+            path_author: Set(data.path_author),
+            path_next_page: Set(data.path_next_page),
+            page_url_template: Set(data.page_url_template),
+            max_pages: Set(data.max_pages),
             screenshot: Set(data.screenshot),
+            mirror_media: Set(data.mirror_media),
+            mirror_videos: Set(data.mirror_videos),
+            fetch_mode: Set(data.fetch_mode.unwrap_or(FetchMode::Browser)),
+            crawl_interval_minutes: Set(data.crawl_interval_minutes),
+            crawl_cron: Set(data.crawl_cron.filter(|expr| !expr.trim().is_empty())),
+            recheck_window_hours: Set(data.recheck_window_hours),
+            recheck_interval_minutes: Set(data.recheck_interval_minutes),
             status: Set(data.status),
+            error_threshold: Set(data.error_threshold),
+            // A changed schedule takes effect on the next scheduler tick.
+            next_crawl_at: Set(None),
             ..Default::default()
         };
 
+        // Re-enabling a disabled site gives it a clean slate.
+        if data.status == Some(true) && existing.status != Some(true) {
+            am.error_count = Set(0);
+            am.disabled_reason = Set(None);
+            am.disabled_at = Set(None);
+        }
+
         let updated = am.update(&state._db).await?;
         Ok(Some(updated))
     }
 
-    pub async fn disable(site_id: i64) -> Result<Option<Model>, DbErr> {
+    pub async fn disable(site_id: i64, reason: &str) -> Result<Option<Model>, DbErr> {
         let state = app_state();
 
         let Some(_existing) = site::Entity::find_by_id(site_id).one(&state._db).await? else {
@@ -153,6 +226,8 @@ impl SiteRepository {
         let am = site::ActiveModel {
             id: Set(site_id),
             status: Set(Option::from(false)),
+            disabled_reason: Set(Some(reason.to_string())),
+            disabled_at: Set(Some(Utc::now().naive_utc())),
             ..Default::default()
         };
 
@@ -160,6 +235,42 @@ impl SiteRepository {
         Ok(Some(updated))
     }
 
+    pub async fn register_error(site_id: i64, error: &str) -> Result<i32, DbErr> {
+        // Scoped import: ExprTrait::not would shadow std::ops::Not used in `list`.
+        use sea_orm::sea_query::ExprTrait;
+
+        let state = app_state();
+
+        site::Entity::update_many()
+            .col_expr(Column::ErrorCount, Expr::col(Column::ErrorCount).add(1))
+            .col_expr(Column::LastError, Expr::value(error))
+            .col_expr(Column::LastErrorAt, Expr::value(Utc::now().naive_utc()))
+            .filter(Column::Id.eq(site_id))
+            .exec(&state._db)
+            .await?;
+
+        let count = site::Entity::find_by_id(site_id)
+            .one(&state._db)
+            .await?
+            .map(|site| site.error_count)
+            .unwrap_or_default();
+
+        Ok(count)
+    }
+
+    pub async fn reset_errors(site_id: i64) -> Result<(), DbErr> {
+        let state = app_state();
+
+        site::Entity::update_many()
+            .col_expr(Column::ErrorCount, Expr::value(0))
+            .filter(Column::Id.eq(site_id))
+            .filter(Column::ErrorCount.ne(0))
+            .exec(&state._db)
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn delete(site_id: i64) -> Result<bool, String> {
         let state = app_state();
 
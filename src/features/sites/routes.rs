@@ -1,9 +1,11 @@
+use crate::features::sites::controller::feed_controller::FeedController;
+use crate::features::sites::controller::media_controller::MediaController;
 use crate::features::sites::controller::post_controller::PostController;
 use crate::features::sites::controller::site_controller::SiteController;
-use crate::middleware::auth::auth;
+use crate::middleware::auth::{auth, stream_auth};
 use crate::middleware::is_admin::is_admin;
 use crate::utility::state::app_state;
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::{Router, middleware};
 
 pub fn site_route() -> (&'static str, Router) {
@@ -19,12 +21,15 @@ pub fn site_route() -> (&'static str, Router) {
             .route("/by-token", get(SiteController::list_by_token))
             .route("/by-token/all", get(SiteController::list_all_by_token))
             .route("/", get(SiteController::list).post(SiteController::create))
+            .route("/preview", post(SiteController::preview))
             .route(
                 "/{site_id}",
                 get(SiteController::show)
                     .put(SiteController::update)
                     .delete(SiteController::delete),
             )
+            .route("/{site_id}/runs", get(SiteController::list_runs))
+            .route("/{site_id}/crawl", post(SiteController::crawl))
             .route_layer(mw_auth),
     )
 }
@@ -34,6 +39,12 @@ pub fn post_route() -> (&'static str, Router) {
 
     let mw_auth = middleware::from_fn_with_state(state.clone(), auth);
     let mw_admin = middleware::from_fn_with_state(state.clone(), is_admin);
+    let mw_stream = middleware::from_fn_with_state(state.clone(), stream_auth);
+
+    let stream_router = Router::new()
+        .route("/stream", get(PostController::stream))
+        .route("/stream/ws", get(PostController::stream_ws))
+        .route_layer(mw_stream);
 
     let admin_router = Router::new()
         .route("/", get(PostController::list).post(PostController::create))
@@ -44,6 +55,11 @@ pub fn post_route() -> (&'static str, Router) {
                 .delete(PostController::delete),
         )
         .route("/by-url/{url}", get(PostController::show_by_url))
+        .route("/{post_id}/revisions", get(PostController::revisions))
+        .route(
+            "/{post_id}/revisions/diff",
+            get(PostController::revision_diff),
+        )
         .route_layer(mw_admin);
 
     (
@@ -52,7 +68,30 @@ pub fn post_route() -> (&'static str, Router) {
             .route("/by-user", get(PostController::list_by_user))
             .route("/by-token", get(PostController::list_by_token))
             .route("/by-site/{site_id}", get(PostController::list_by_site))
+            .route("/search", get(PostController::search))
+            .route("/stream/token", post(PostController::stream_token))
+            .route("/{post_id}/screenshot", get(PostController::screenshot))
             .merge(admin_router)
-            .route_layer(mw_auth),
+            .route_layer(mw_auth)
+            .merge(stream_router),
+    )
+}
+
+/// Mirrored post media, public so the rewritten URLs work wherever posts are shown.
+pub fn media_route() -> (&'static str, Router) {
+    (
+        "media",
+        Router::new().route("/{file}", get(MediaController::show)),
+    )
+}
+
+/// RSS, Atom and JSON feeds of completed posts, authenticated by a `?token=` feed token
+/// since feed readers can't send `X-API-Key`.
+pub fn feed_route() -> (&'static str, Router) {
+    (
+        "feeds",
+        Router::new()
+            .route("/sites/{file}", get(FeedController::site))
+            .route("/{file}", get(FeedController::api_key)),
     )
 }
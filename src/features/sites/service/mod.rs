@@ -0,0 +1,2 @@
+pub mod post_stream;
+pub mod site_preview;
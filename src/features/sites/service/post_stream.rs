@@ -0,0 +1,165 @@
+use crate::core::logger::targets;
+use crate::features::sites::model::posts;
+use crate::features::sites::repository::post_repository::PostRepository;
+use crate::features::users::model::{api_key, user};
+use futures::Stream;
+use once_cell::sync::Lazy;
+use sea_orm::DbErr;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+/// Changes buffered per subscriber. One that falls further behind is disconnected and
+/// catches up from the database when it reconnects with its last event id.
+const CHANNEL_CAPACITY: usize = 1024;
+/// Posts read per query while replaying to a reconnecting subscriber; the replay pages on
+/// until it has caught up, then switches to live changes.
+const REPLAY_PAGE_SIZE: u64 = 500;
+
+static CHANNEL: Lazy<broadcast::Sender<PostChange>> =
+    Lazy::new(|| broadcast::channel(CHANNEL_CAPACITY).0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PostChangeKind {
+    /// A link was saved as a new pending post.
+    Created,
+    /// Content was stored.
+    Updated,
+    /// Extraction failed; the post is retried or cancelled.
+    Failed,
+    /// Replayed on resume: the current state of a post created after the last event id.
+    Sync,
+}
+
+impl PostChangeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "post.created",
+            Self::Updated => "post.updated",
+            Self::Failed => "post.failed",
+            Self::Sync => "post.sync",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PostChange {
+    pub kind: PostChangeKind,
+    pub post: Arc<posts::Model>,
+}
+
+/// In-process fan-out of post changes to the SSE and WebSocket streams.
+pub struct PostStream;
+
+impl PostStream {
+    /// Tell current subscribers about a stored change. Nobody listening is fine.
+    pub fn publish(kind: PostChangeKind, post: &posts::Model) {
+        let _ = CHANNEL.send(PostChange {
+            kind,
+            post: Arc::new(post.clone()),
+        });
+    }
+
+    /// The changes `api_key` may see: admins see every post, other keys the posts crawled
+    /// through them. With `after_post_id`, every post created since then is replayed first,
+    /// a page at a time. The stream ends if the subscriber lags behind.
+    pub async fn subscribe(
+        user: &user::Model,
+        api_key: &api_key::Model,
+        after_post_id: Option<i64>,
+    ) -> Result<impl Stream<Item = PostChange> + use<>, DbErr> {
+        // Subscribe before reading the backlog so nothing falls between the two.
+        let receiver = CHANNEL.subscribe();
+        let scope = (!user.is_admin).then_some(api_key.id);
+
+        let mut subscription = Subscription {
+            receiver,
+            scope,
+            backlog: VecDeque::new(),
+            next_page_after: None,
+            replayed_up_to: after_post_id.unwrap_or(0),
+        };
+        // The first page is read here so a broken database fails the request, not the stream.
+        if let Some(post_id) = after_post_id {
+            subscription.load_page(post_id).await?;
+        }
+
+        Ok(futures::stream::unfold(
+            subscription,
+            |mut subscription| async move {
+                let change = subscription.next().await?;
+                Some((change, subscription))
+            },
+        ))
+    }
+}
+
+struct Subscription {
+    receiver: broadcast::Receiver<PostChange>,
+    scope: Option<i64>,
+    /// Replayed posts not sent yet.
+    backlog: VecDeque<posts::Model>,
+    /// Where the next replay page starts, while the replay hasn't caught up.
+    next_page_after: Option<i64>,
+    /// The newest post replayed; live creations up to it were already sent.
+    replayed_up_to: i64,
+}
+
+impl Subscription {
+    async fn load_page(&mut self, after_post_id: i64) -> Result<(), DbErr> {
+        let page =
+            PostRepository::created_after(self.scope, after_post_id, REPLAY_PAGE_SIZE).await?;
+        let last = page.last().map(|post| post.id);
+        self.next_page_after = last.filter(|_| page.len() as u64 == REPLAY_PAGE_SIZE);
+        self.replayed_up_to = self.replayed_up_to.max(last.unwrap_or(0));
+        self.backlog.extend(page);
+        Ok(())
+    }
+
+    /// The next change, or `None` to end the stream and let the client resume.
+    async fn next(&mut self) -> Option<PostChange> {
+        loop {
+            if let Some(post) = self.backlog.pop_front() {
+                return Some(PostChange {
+                    kind: PostChangeKind::Sync,
+                    post: Arc::new(post),
+                });
+            }
+
+            if let Some(post_id) = self.next_page_after.take() {
+                if let Err(e) = self.load_page(post_id).await {
+                    warn!(target: targets::REQUEST, error = %e, "Post stream replay failed");
+                    return None;
+                }
+                continue;
+            }
+
+            // Lagged or closed: end the stream and let the client resume.
+            let change = self.receiver.recv().await.ok()?;
+            let visible = self
+                .scope
+                .is_none_or(|api_key_id| change.post.api_key_id == api_key_id);
+            let replayed =
+                change.kind == PostChangeKind::Created && change.post.id <= self.replayed_up_to;
+            if visible && !replayed {
+                return Some(change);
+            }
+        }
+    }
+}
+
+/// Event ids are post ids: the highest one sent so far, so a client resuming with its last
+/// event id gets every post created after it without replaying older ones.
+pub struct Cursor(i64);
+
+impl Cursor {
+    pub fn new(after_post_id: Option<i64>) -> Self {
+        Self(after_post_id.unwrap_or(0))
+    }
+
+    pub fn advance(&mut self, change: &PostChange) -> i64 {
+        self.0 = self.0.max(change.post.id);
+        self.0
+    }
+}
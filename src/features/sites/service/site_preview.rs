@@ -0,0 +1,169 @@
+use crate::core::state::APP_STATE;
+use crate::features::crawler::fetcher::Fetcher;
+use crate::features::sites::dto::site_preview::{PostPreview, SitePreview};
+use crate::features::sites::model::site::{FetchMode, SourceType};
+use crate::features::sites::utility::link_sources::discover_links;
+use crate::features::sites::utility::listing_pages::Pagination;
+use crate::features::sites::utility::normalize_link::normalize_link;
+use crate::features::sites::utility::page_loader::load_page;
+use crate::features::sites::utility::page_metadata::read_metadata;
+use crate::features::sites::utility::post_extractor::{
+    ExtractedPost, PostSelectors, extract_post, remove_elements,
+};
+use crate::features::sites::utility::selector_chain::SelectorChain;
+use crate::features::sites::validation::site_form::SiteForm;
+use futures::future::join_all;
+use std::collections::HashSet;
+use tokio::time::{Duration, timeout};
+
+pub const DEFAULT_PREVIEW_LIMIT: usize = 3;
+pub const MAX_PREVIEW_LIMIT: usize = 10;
+
+/// A preview must answer within the on-demand budget of 25 s: the listing page, the wait for
+/// its links and the article pages (loaded side by side) each get a slice of it, so one slow
+/// page can't use it all up.
+const PAGE_TIMEOUT: Duration = Duration::from_secs(8);
+const SELECTOR_WAIT: Duration = Duration::from_secs(5);
+/// A sitemap or feed may take several documents to read.
+const SOURCE_TIMEOUT: Duration = Duration::from_secs(12);
+
+pub struct SitePreviewService;
+impl SitePreviewService {
+    /// Run a site's selectors against its listing page (or sitemap/feed) and the first `limit`
+    /// article links. Errors on the listing page fail the preview; errors on an article are
+    /// reported per post.
+    pub async fn preview(form: &SiteForm, limit: usize) -> Result<SitePreview, String> {
+        let fetch_mode = form.fetch_mode.clone().unwrap_or(FetchMode::Browser);
+        let source_type = form.source_type.clone().unwrap_or(SourceType::Listing);
+
+        let (raw_links, next_page) = match source_type {
+            SourceType::Listing => preview_listing(form, &fetch_mode).await?,
+            SourceType::Sitemap | SourceType::Feed => {
+                let discover = discover_links(&source_type, &form.url_list, form.max_age_hours);
+                let source = timeout(SOURCE_TIMEOUT, discover)
+                    .await
+                    .map_err(|_| "link source timed out".to_string())?
+                    .map_err(|e| format!("link source failed: {e}"))?;
+                (source.links, None)
+            }
+        };
+
+        let mut seen = HashSet::new();
+        let links: Vec<String> = raw_links
+            .iter()
+            .map(|link| normalize_link(&form.url, link))
+            .filter(|link| !link.is_empty() && seen.insert(link.clone()))
+            .collect();
+
+        let selectors = PostSelectors {
+            title: form.path_title.as_deref().unwrap_or(""),
+            content: form.path_content.as_deref().unwrap_or(""),
+            image: form.path_image.as_deref().unwrap_or(""),
+            video: form.path_video.as_deref().unwrap_or(""),
+            date: form.path_date.as_deref().unwrap_or(""),
+            author: form.path_author.as_deref().unwrap_or(""),
+            remove: form.path_remove.as_deref().unwrap_or(""),
+            auto_content: form.auto_content == Some(true),
+        };
+
+        let posts = join_all(
+            links
+                .iter()
+                .take(limit)
+                .map(|url| preview_post(&fetch_mode, &form.url, url, &selectors)),
+        )
+        .await;
+
+        Ok(SitePreview {
+            links,
+            next_page,
+            posts,
+        })
+    }
+}
+
+/// The links of the listing page and the next page the crawl would follow.
+async fn preview_listing(
+    form: &SiteForm,
+    fetch_mode: &FetchMode,
+) -> Result<(Vec<String>, Option<String>), String> {
+    let path_link = SelectorChain::new(form.path_link.as_deref().unwrap_or(""));
+    if path_link.is_empty() {
+        return Err("path_link is required for a preview".to_string());
+    }
+
+    let page = load(fetch_mode, &form.url_list).await?;
+
+    path_link
+        .wait_for_any(page.as_ref(), SELECTOR_WAIT)
+        .await
+        .map_err(|e| format!("link selector not found: {e}"))?;
+
+    if let Some(remove) = form.path_remove.as_deref() {
+        remove_elements(page.as_ref(), remove)
+            .await
+            .map_err(|e| format!("path_remove failed: {e}"))?;
+    }
+
+    let links = path_link
+        .attrs(page.as_ref(), "href")
+        .await
+        .map_err(|e| format!("failed to read links: {e}"))?;
+    let next_page = Pagination::new(
+        form.path_next_page.as_deref(),
+        form.page_url_template.as_deref(),
+        form.max_pages,
+    )
+    .next_url(page.as_ref(), &form.url_list, &form.url_list, 1)
+    .await;
+
+    Ok((links, next_page))
+}
+
+async fn preview_post(
+    fetch_mode: &FetchMode,
+    base_url: &str,
+    url: &str,
+    selectors: &PostSelectors<'_>,
+) -> PostPreview {
+    let result = async {
+        let page = load(fetch_mode, url).await?;
+        let metadata = read_metadata(page.as_ref(), base_url).await;
+
+        if let Err(e) = remove_elements(page.as_ref(), selectors.remove).await {
+            return Err(format!("path_remove failed: {e}"));
+        }
+
+        Ok(extract_post(page.as_ref(), base_url, selectors, metadata).await)
+    }
+    .await;
+
+    match result {
+        Ok(extracted) => {
+            let error = extracted.failure().map(str::to_string);
+            PostPreview {
+                url: url.to_string(),
+                extracted,
+                error,
+            }
+        }
+        Err(error) => PostPreview {
+            url: url.to_string(),
+            extracted: ExtractedPost::default(),
+            error: Some(error),
+        },
+    }
+}
+
+async fn load(fetch_mode: &FetchMode, url: &str) -> Result<Box<dyn Fetcher>, String> {
+    let page_timeout = APP_STATE
+        .get()
+        .map(|state| Duration::from_secs(state.config.browser_start_timeout_seconds))
+        .map_or(PAGE_TIMEOUT, |configured| configured.min(PAGE_TIMEOUT));
+
+    match timeout(page_timeout, load_page(fetch_mode, url)).await {
+        Ok(Ok(page)) => Ok(page),
+        Ok(Err(e)) => Err(format!("page load failed for {url}: {e}")),
+        Err(_) => Err(format!("page load timed out for {url}")),
+    }
+}
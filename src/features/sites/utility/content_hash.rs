@@ -0,0 +1,12 @@
+/// BLAKE3 fingerprint of a post's extracted fields, used to tell a real edit from a re-fetch.
+pub fn content_hash(title: &str, body: &str, image: &str, video: &str) -> String {
+    let mut hasher = blake3::Hasher::new();
+
+    // Length-prefix each field so moving text between fields changes the hash.
+    for field in [title, body, image, video] {
+        hasher.update(&(field.len() as u64).to_le_bytes());
+        hasher.update(field.as_bytes());
+    }
+
+    hasher.finalize().to_hex().to_string()
+}
@@ -0,0 +1,62 @@
+use crate::core::logger::targets;
+use crate::core::schedule::parse_cron;
+use crate::core::state::APP_STATE;
+use crate::features::sites::model::site::Model;
+use chrono::{Duration, NaiveDateTime};
+use tracing::warn;
+
+const DEFAULT_INTERVAL_MINUTES: u32 = 15;
+const DEFAULT_RECHECK_INTERVAL_MINUTES: i32 = 60;
+
+/// When a site should be crawled next, counted from `now`.
+///
+/// A cron expression (evaluated in UTC) wins over `crawl_interval_minutes`; sites with
+/// neither fall back to `POST_CHECK_INTERVAL_MINUTES`.
+pub fn next_crawl_at(site: &Model, now: NaiveDateTime) -> NaiveDateTime {
+    if let Some(expression) = site.crawl_cron.as_deref().filter(|e| !e.trim().is_empty()) {
+        match parse_cron(expression) {
+            Ok(schedule) => {
+                if let Some(next) = schedule.after(&now.and_utc()).next() {
+                    return next.naive_utc();
+                }
+            }
+            Err(e) => warn!(
+                target: targets::CRAWLER_SITE,
+                site_id = site.id,
+                error = %e,
+                "Ignoring invalid crawl_cron"
+            ),
+        }
+    }
+
+    let minutes = site
+        .crawl_interval_minutes
+        .filter(|minutes| *minutes > 0)
+        .map(|minutes| minutes as i64)
+        .unwrap_or_else(|| default_interval_minutes() as i64);
+
+    now + Duration::minutes(minutes)
+}
+
+fn default_interval_minutes() -> u32 {
+    APP_STATE
+        .get()
+        .map(|state| state.config.post_check_interval_minutes)
+        .filter(|minutes| *minutes > 0)
+        .unwrap_or(DEFAULT_INTERVAL_MINUTES)
+}
+
+/// For sites with a re-check window, the bounds a completed post must fall in to be
+/// re-extracted now: created after the first value and last checked before the second.
+pub fn recheck_bounds(site: &Model, now: NaiveDateTime) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let window_hours = site.recheck_window_hours.filter(|hours| *hours > 0)?;
+    let interval_minutes = site
+        .recheck_interval_minutes
+        .filter(|minutes| *minutes > 0)
+        .unwrap_or(DEFAULT_RECHECK_INTERVAL_MINUTES);
+
+    Some((
+        now - Duration::hours(window_hours as i64),
+        now - Duration::minutes(interval_minutes as i64),
+    ))
+}
@@ -0,0 +1,345 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Asia::Tehran;
+
+const GREGORIAN_FORMATS: [&str; 8] = [
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+    "%B %d, %Y %H:%M",
+    "%d %B %Y %H:%M",
+    "%b %d, %Y %H:%M",
+];
+
+const GREGORIAN_DATE_FORMATS: [&str; 6] = [
+    "%Y-%m-%d",
+    "%B %d, %Y",
+    "%b %d, %Y",
+    "%d %B %Y",
+    "%d %b %Y",
+    "%a, %d %b %Y",
+];
+
+const JALALI_MONTHS: [&str; 12] = [
+    "فروردین",
+    "اردیبهشت",
+    "خرداد",
+    "تیر",
+    "مرداد",
+    "شهریور",
+    "مهر",
+    "آبان",
+    "آذر",
+    "دی",
+    "بهمن",
+    "اسفند",
+];
+
+/// Parse a publication date as sites print it, returning UTC.
+///
+/// Understands ISO 8601 / RFC 3339, RFC 2822, common English forms ("October 17, 2026"),
+/// relative dates ("3 hours ago", "yesterday", "۳ ساعت پیش") and Jalali dates, either numeric
+/// ("1403/07/25 14:30") or with month names ("۲۵ مهر ۱۴۰۳"). Times without an offset are
+/// taken as UTC, except Jalali ones, which are taken as Tehran time.
+pub fn parse_date(raw: &str, now: DateTime<Utc>) -> Option<NaiveDateTime> {
+    let text = normalize_digits(raw.trim());
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+        return Some(date.naive_utc());
+    }
+    if let Ok(date) = DateTime::parse_from_rfc2822(text) {
+        return Some(date.naive_utc());
+    }
+
+    for format in GREGORIAN_FORMATS {
+        if let Ok(date) = DateTime::parse_from_str(text, format) {
+            return Some(date.naive_utc());
+        }
+        if let Ok(date) = NaiveDateTime::parse_from_str(text, format) {
+            return Some(date);
+        }
+    }
+    for format in GREGORIAN_DATE_FORMATS {
+        if let Ok(date) = NaiveDate::parse_from_str(text, format) {
+            return Some(date.and_time(NaiveTime::MIN));
+        }
+    }
+
+    parse_relative(text, now).or_else(|| parse_numeric_or_jalali(text))
+}
+
+/// Map Persian (۰-۹) and Arabic-Indic (٠-٩) digits to ASCII and Arabic punctuation to Latin.
+fn normalize_digits(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '۰'..='۹' => char::from(b'0' + (c as u32 - '۰' as u32) as u8),
+            '٠'..='٩' => char::from(b'0' + (c as u32 - '٠' as u32) as u8),
+            '،' => ',',
+            '\u{200c}' => ' ',
+            _ => c,
+        })
+        .collect()
+}
+
+fn parse_relative(text: &str, now: DateTime<Utc>) -> Option<NaiveDateTime> {
+    let lower = text.to_lowercase();
+    let now_naive = now.naive_utc();
+
+    match lower.as_str() {
+        "now" | "just now" | "today" | "امروز" | "همین الان" | "لحظاتی پیش" | "دقایقی پیش" =>
+        {
+            return Some(now_naive);
+        }
+        "yesterday" | "دیروز" => return Some(now_naive - Duration::days(1)),
+        _ => {}
+    }
+
+    let mut tokens: Vec<&str> = lower.split_whitespace().collect();
+    match tokens.last() {
+        Some(&"ago") | Some(&"پیش") | Some(&"قبل") => {
+            tokens.pop();
+        }
+        _ => return None,
+    }
+
+    let [amount, unit] = tokens.as_slice() else {
+        return None;
+    };
+
+    let amount: i64 = match *amount {
+        "a" | "an" | "one" | "یک" => 1,
+        n => n.parse().ok()?,
+    };
+
+    let unit_seconds = match unit.trim_end_matches('s') {
+        "sec" | "second" | "ثانیه" => 1,
+        "min" | "minute" | "دقیقه" => 60,
+        "hr" | "hour" | "ساعت" => 3_600,
+        "day" | "روز" => 86_400,
+        "week" | "هفته" => 7 * 86_400,
+        "month" | "ماه" => 30 * 86_400,
+        "year" | "سال" => 365 * 86_400,
+        _ => return None,
+    };
+
+    Some(now_naive - Duration::seconds(amount.checked_mul(unit_seconds)?))
+}
+
+/// Numeric dates with `/` or `-` (a year below 1700 is read as Jalali) and Jalali dates
+/// with month names, each with an optional `HH:MM[:SS]` anywhere in the text.
+fn parse_numeric_or_jalali(text: &str) -> Option<NaiveDateTime> {
+    let tokens: Vec<&str> = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let time = tokens
+        .iter()
+        .find_map(|token| parse_time(token))
+        .unwrap_or(NaiveTime::MIN);
+
+    if let Some((year, month, day)) = tokens.iter().find_map(|token| numeric_date(token)) {
+        return if year < 1700 {
+            jalali_in_tehran(year, month, day, time)
+        } else {
+            NaiveDate::from_ymd_opt(year, month, day).map(|date| date.and_time(time))
+        };
+    }
+
+    let month_index = tokens
+        .iter()
+        .position(|token| JALALI_MONTHS.contains(token))?;
+    let month = JALALI_MONTHS
+        .iter()
+        .position(|m| *m == tokens[month_index])? as u32
+        + 1;
+
+    let numbers_around = |range: &[&str]| -> Vec<i32> {
+        range.iter().filter_map(|t| t.parse::<i32>().ok()).collect()
+    };
+    let day = *numbers_around(&tokens[..month_index]).last()?;
+    let year = *numbers_around(&tokens[month_index + 1..]).first()?;
+
+    jalali_in_tehran(year, month, u32::try_from(day).ok()?, time)
+}
+
+fn numeric_date(token: &str) -> Option<(i32, u32, u32)> {
+    let parts: Vec<&str> = token.split(['/', '-', '.']).collect();
+    let [year, month, day] = parts.as_slice() else {
+        return None;
+    };
+    if year.len() != 4 {
+        return None;
+    }
+
+    Some((year.parse().ok()?, month.parse().ok()?, day.parse().ok()?))
+}
+
+fn parse_time(token: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(token, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(token, "%H:%M"))
+        .ok()
+}
+
+fn jalali_in_tehran(year: i32, month: u32, day: u32, time: NaiveTime) -> Option<NaiveDateTime> {
+    let local = jalali_to_gregorian(year, month, day)?.and_time(time);
+    Tehran
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|date| date.naive_utc())
+}
+
+/// Convert a Jalali (Solar Hijri) date to Gregorian, using the 33-year arithmetic leap cycle.
+fn jalali_to_gregorian(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    let max_day = match month {
+        1..=6 => 31,
+        7..=12 => 30,
+        _ => return None,
+    };
+    if !(1..=3000).contains(&year) || day == 0 || day > max_day {
+        return None;
+    }
+
+    let jy = year as i64 + 1595;
+    let (jm, jd) = (month as i64, day as i64);
+    let mut days = -355_668
+        + 365 * jy
+        + (jy / 33) * 8
+        + ((jy % 33) + 3) / 4
+        + jd
+        + if jm < 7 {
+            (jm - 1) * 31
+        } else {
+            (jm - 7) * 30 + 186
+        };
+
+    let mut gy = 400 * (days / 146_097);
+    days %= 146_097;
+    if days > 36_524 {
+        days -= 1;
+        gy += 100 * (days / 36_524);
+        days %= 36_524;
+        if days >= 365 {
+            days += 1;
+        }
+    }
+    gy += 4 * (days / 1_461);
+    days %= 1_461;
+    if days > 365 {
+        gy += (days - 1) / 365;
+        days = (days - 1) % 365;
+    }
+
+    NaiveDate::from_yo_opt(gy as i32, days as u32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap()
+    }
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    fn parse(raw: &str) -> Option<NaiveDateTime> {
+        parse_date(raw, now())
+    }
+
+    #[test]
+    fn iso_and_rfc_dates_are_converted_to_utc() {
+        assert_eq!(
+            parse("2026-10-01T08:00:00+03:30"),
+            Some(at(2026, 10, 1, 4, 30))
+        );
+        assert_eq!(parse("2026-10-01T08:00:00Z"), Some(at(2026, 10, 1, 8, 0)));
+        assert_eq!(
+            parse("Thu, 01 Oct 2026 08:00:00 +0200"),
+            Some(at(2026, 10, 1, 6, 0))
+        );
+        assert_eq!(parse("2026-10-01 08:15"), Some(at(2026, 10, 1, 8, 15)));
+        assert_eq!(parse("2026-10-01"), Some(at(2026, 10, 1, 0, 0)));
+    }
+
+    #[test]
+    fn english_month_names() {
+        assert_eq!(parse("October 17, 2026"), Some(at(2026, 10, 17, 0, 0)));
+        assert_eq!(
+            parse("17 October 2026 09:30"),
+            Some(at(2026, 10, 17, 9, 30))
+        );
+        assert_eq!(parse("Oct 17, 2026"), Some(at(2026, 10, 17, 0, 0)));
+    }
+
+    #[test]
+    fn relative_dates_count_back_from_now() {
+        assert_eq!(parse("3 hours ago"), Some(at(2026, 10, 17, 9, 0)));
+        assert_eq!(parse("an hour ago"), Some(at(2026, 10, 17, 11, 0)));
+        assert_eq!(parse("2 mins ago"), Some(at(2026, 10, 17, 11, 58)));
+        assert_eq!(parse("yesterday"), Some(at(2026, 10, 16, 12, 0)));
+        assert_eq!(parse("Just now"), Some(at(2026, 10, 17, 12, 0)));
+        assert_eq!(parse("۳ ساعت پیش"), Some(at(2026, 10, 17, 9, 0)));
+        assert_eq!(parse("۲ روز قبل"), Some(at(2026, 10, 15, 12, 0)));
+        assert_eq!(parse("دیروز"), Some(at(2026, 10, 16, 12, 0)));
+    }
+
+    #[test]
+    fn numeric_jalali_dates_are_tehran_time() {
+        // 25 Mehr 1403 is 16 October 2024; Tehran is UTC+03:30.
+        assert_eq!(parse("1403/07/25 14:30"), Some(at(2024, 10, 16, 11, 0)));
+        assert_eq!(parse("۱۴۰۳/۰۷/۲۵"), Some(at(2024, 10, 15, 20, 30)));
+        assert_eq!(parse("14:30 - 1403-07-25"), Some(at(2024, 10, 16, 11, 0)));
+    }
+
+    #[test]
+    fn jalali_dates_with_month_names() {
+        assert_eq!(parse("۲۵ مهر ۱۴۰۳"), Some(at(2024, 10, 15, 20, 30)));
+        assert_eq!(
+            parse("سه‌شنبه ۲۵ مهر ۱۴۰۳، ساعت ۱۴:۳۰"),
+            Some(at(2024, 10, 16, 11, 0))
+        );
+    }
+
+    #[test]
+    fn jalali_calendar_edges() {
+        assert_eq!(
+            jalali_to_gregorian(1403, 1, 1),
+            NaiveDate::from_ymd_opt(2024, 3, 20)
+        );
+        // 1403 is a leap year, 1404 is not.
+        assert_eq!(
+            jalali_to_gregorian(1403, 12, 30),
+            NaiveDate::from_ymd_opt(2025, 3, 20)
+        );
+        assert_eq!(
+            jalali_to_gregorian(1404, 1, 1),
+            NaiveDate::from_ymd_opt(2025, 3, 21)
+        );
+        assert_eq!(jalali_to_gregorian(1403, 7, 31), None);
+        assert_eq!(jalali_to_gregorian(1403, 13, 1), None);
+    }
+
+    #[test]
+    fn gregorian_numeric_dates() {
+        assert_eq!(parse("2026/10/17 08:05"), Some(at(2026, 10, 17, 8, 5)));
+    }
+
+    #[test]
+    fn unparseable_text_is_none() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("   "), None);
+        assert_eq!(parse("posted by admin"), None);
+        assert_eq!(parse("3 bananas ago"), None);
+    }
+}
@@ -0,0 +1,458 @@
+use crate::features::sites::model::posts;
+use crate::utility::url::url;
+use chrono::{NaiveDateTime, SecondsFormat, Utc};
+use serde_json::{Value, json};
+use std::fmt::Write;
+
+/// Characters of `body_text` kept for an item's summary.
+const SUMMARY_CHARS: usize = 280;
+
+const GENERATOR: &str = "rust-crawler";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+impl FeedFormat {
+    /// Split a feed file name such as `12.rss` into its stem and format.
+    pub fn split(file: &str) -> Option<(&str, Self)> {
+        let (stem, extension) = file.rsplit_once('.')?;
+        let format = match extension {
+            "rss" => Self::Rss,
+            "atom" => Self::Atom,
+            "json" => Self::Json,
+            _ => return None,
+        };
+        Some((stem, format))
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Rss => "rss",
+            Self::Atom => "atom",
+            Self::Json => "json",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Rss => "application/rss+xml; charset=utf-8",
+            Self::Atom => "application/atom+xml; charset=utf-8",
+            Self::Json => "application/feed+json; charset=utf-8",
+        }
+    }
+}
+
+/// Absolute URL of the feed at `path` (e.g. `feeds/all`), with the token feed readers need.
+pub fn feed_url(path: &str, format: FeedFormat, token: &str) -> String {
+    url(&format!("{path}.{}?token={token}", format.extension()))
+}
+
+/// A feed of completed posts, newest first.
+pub struct Feed<'a> {
+    pub title: String,
+    /// The page the feed stands for: the site, or this service for an aggregated feed.
+    pub home_url: String,
+    /// Where the feed itself is served, without the token.
+    pub self_url: String,
+    pub posts: &'a [posts::Model],
+}
+
+pub fn render(format: FeedFormat, feed: &Feed) -> String {
+    match format {
+        FeedFormat::Rss => rss(feed),
+        FeedFormat::Atom => atom(feed),
+        FeedFormat::Json => json_feed(feed).to_string(),
+    }
+}
+
+/// RSS 2.0, with the full body in `content:encoded`.
+fn rss(feed: &Feed) -> String {
+    let mut out = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    out.push_str(
+        r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:dc="http://purl.org/dc/elements/1.1/"><channel>"#,
+    );
+    let _ = write!(
+        out,
+        r#"<title>{title}</title><link>{home}</link><description>{title}</description><atom:link href="{this}" rel="self" type="application/rss+xml"/><generator>{GENERATOR}</generator><lastBuildDate>{built}</lastBuildDate>"#,
+        title = escape(&feed.title),
+        home = escape(&feed.home_url),
+        this = escape(&feed.self_url),
+        built = updated(feed).and_utc().to_rfc2822(),
+    );
+
+    for post in feed.posts {
+        let link = item_url(post);
+        let _ = write!(
+            out,
+            r#"<item><title>{}</title><link>{}</link><guid isPermaLink="{}">{}</guid><pubDate>{}</pubDate>"#,
+            escape(&item_title(post)),
+            escape(&link),
+            post.url.is_some(),
+            escape(&link),
+            published(post).and_utc().to_rfc2822(),
+        );
+        if let Some(author) = &post.author {
+            let _ = write!(out, "<dc:creator>{}</dc:creator>", escape(author));
+        }
+        if let Some(summary) = summary(post) {
+            let _ = write!(out, "<description>{}</description>", escape(&summary));
+        }
+        if let Some(body) = &post.body {
+            let _ = write!(out, "<content:encoded>{}</content:encoded>", escape(body));
+        }
+        if let Some(image) = &post.image {
+            // RSS requires a length; 0 is the usual stand-in when it isn't known.
+            let _ = write!(
+                out,
+                r#"<enclosure url="{}" type="{}" length="0"/>"#,
+                escape(image),
+                image_type(image),
+            );
+        }
+        out.push_str("</item>");
+    }
+
+    out.push_str("</channel></rss>");
+    out
+}
+
+fn atom(feed: &Feed) -> String {
+    let mut out = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = write!(
+        out,
+        r#"<feed xmlns="http://www.w3.org/2005/Atom"><id>{this}</id><title>{title}</title><updated>{updated}</updated><link rel="self" type="application/atom+xml" href="{this}"/><link rel="alternate" href="{home}"/><generator>{GENERATOR}</generator>"#,
+        this = escape(&feed.self_url),
+        title = escape(&feed.title),
+        updated = rfc3339(updated(feed)),
+        home = escape(&feed.home_url),
+    );
+
+    for post in feed.posts {
+        let link = item_url(post);
+        let _ = write!(
+            out,
+            r#"<entry><id>{link}</id><title>{title}</title><link rel="alternate" href="{link}"/><published>{published}</published><updated>{updated}</updated>"#,
+            link = escape(&link),
+            title = escape(&item_title(post)),
+            published = rfc3339(published(post)),
+            updated = rfc3339(modified(post)),
+        );
+        if let Some(author) = &post.author {
+            let _ = write!(out, "<author><name>{}</name></author>", escape(author));
+        }
+        if let Some(summary) = summary(post) {
+            let _ = write!(out, "<summary>{}</summary>", escape(&summary));
+        }
+        if let Some(body) = &post.body {
+            let _ = write!(out, r#"<content type="html">{}</content>"#, escape(body));
+        }
+        if let Some(image) = &post.image {
+            let _ = write!(
+                out,
+                r#"<link rel="enclosure" type="{}" href="{}"/>"#,
+                image_type(image),
+                escape(image),
+            );
+        }
+        out.push_str("</entry>");
+    }
+
+    out.push_str("</feed>");
+    out
+}
+
+/// JSON Feed 1.1.
+fn json_feed(feed: &Feed) -> Value {
+    let items: Vec<Value> = feed
+        .posts
+        .iter()
+        .map(|post| {
+            let mut item = json!({
+                "id": item_url(post),
+                "url": item_url(post),
+                "title": item_title(post),
+                "date_published": rfc3339(published(post)),
+                "date_modified": rfc3339(modified(post)),
+            });
+            // An item needs `content_html` or `content_text`, even if empty.
+            match (&post.body, &post.body_text) {
+                (Some(body), text) => {
+                    item["content_html"] = json!(body);
+                    if let Some(text) = text {
+                        item["content_text"] = json!(text);
+                    }
+                }
+                (None, text) => item["content_text"] = json!(text.clone().unwrap_or_default()),
+            }
+            if let Some(summary) = summary(post) {
+                item["summary"] = json!(summary);
+            }
+            if let Some(image) = &post.image {
+                item["image"] = json!(image);
+            }
+            if let Some(author) = &post.author {
+                item["authors"] = json!([{ "name": author }]);
+            }
+            item
+        })
+        .collect();
+
+    json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": feed.title,
+        "home_page_url": feed.home_url,
+        "feed_url": feed.self_url,
+        "items": items,
+    })
+}
+
+/// The post's own URL; posts without one point at their API record instead.
+fn item_url(post: &posts::Model) -> String {
+    post.url
+        .clone()
+        .unwrap_or_else(|| url(&format!("api/v1/posts/{}", post.id)))
+}
+
+fn item_title(post: &posts::Model) -> String {
+    post.title
+        .clone()
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or_else(|| item_url(post))
+}
+
+fn summary(post: &posts::Model) -> Option<String> {
+    let text = post.body_text.as_deref()?.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let mut summary: String = text.chars().take(SUMMARY_CHARS).collect();
+    if summary.len() < text.len() {
+        summary = format!("{}…", summary.trim_end());
+    }
+    Some(summary)
+}
+
+/// The MIME type an image URL's extension suggests, ignoring its query and fragment.
+fn image_type(image: &str) -> mime_guess::Mime {
+    let path = image.split(['?', '#']).next().unwrap_or_default();
+    mime_guess::from_path(path).first_or_octet_stream()
+}
+
+fn published(post: &posts::Model) -> NaiveDateTime {
+    post.published_at.unwrap_or(post.created_at)
+}
+
+fn modified(post: &posts::Model) -> NaiveDateTime {
+    post.updated_at
+        .unwrap_or(post.created_at)
+        .max(published(post))
+}
+
+/// When the feed last changed: its newest item, or now for an empty feed.
+fn updated(feed: &Feed) -> NaiveDateTime {
+    feed.posts
+        .iter()
+        .map(modified)
+        .max()
+        .unwrap_or_else(|| Utc::now().naive_utc())
+}
+
+fn rfc3339(date: NaiveDateTime) -> String {
+    date.and_utc().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab and newlines are not allowed in XML 1.0.
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .unwrap()
+            .and_hms_opt(hour, 30, 0)
+            .unwrap()
+    }
+
+    fn post(id: i64, url: &str) -> posts::Model {
+        posts::Model {
+            id,
+            title: None,
+            body: None,
+            body_text: None,
+            body_markdown: None,
+            body_source: None,
+            image: None,
+            video: None,
+            author: None,
+            published_at: None,
+            url: Some(url.to_string()),
+            retry: 0,
+            status: posts::PostStatus::COMPLETED,
+            content_hash: None,
+            revision: 1,
+            updated_at: None,
+            checked_at: None,
+            screenshot_path: None,
+            metadata: None,
+            site_id: 1,
+            user_id: 1,
+            api_key_id: 1,
+            created_at: at(15, 8),
+        }
+    }
+
+    fn posts() -> Vec<posts::Model> {
+        let full = posts::Model {
+            title: Some("Tom & Jerry <say> \"hi\"".to_string()),
+            body: Some("<p>Cats &amp; mice</p>".to_string()),
+            body_text: Some("Cats & mice".to_string()),
+            image: Some("https://example.com/a.jpg?w=1&h=2".to_string()),
+            author: Some("O'Brien".to_string()),
+            published_at: Some(at(16, 9)),
+            updated_at: Some(at(17, 10)),
+            ..post(2, "https://example.com/news?id=2&x=1")
+        };
+        vec![full, post(1, "https://example.com/bare")]
+    }
+
+    fn feed(posts: &[posts::Model]) -> Feed<'_> {
+        Feed {
+            title: "News & <more>".to_string(),
+            home_url: "https://example.com/".to_string(),
+            self_url: "https://crawler.test/feeds/sites/1".to_string(),
+            posts,
+        }
+    }
+
+    #[test]
+    fn rss_golden_output() {
+        let posts = posts();
+        assert_eq!(
+            render(FeedFormat::Rss, &feed(&posts)),
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:dc="http://purl.org/dc/elements/1.1/"><channel>"#,
+                r#"<title>News &amp; &lt;more&gt;</title><link>https://example.com/</link><description>News &amp; &lt;more&gt;</description>"#,
+                r#"<atom:link href="https://crawler.test/feeds/sites/1" rel="self" type="application/rss+xml"/>"#,
+                r#"<generator>rust-crawler</generator><lastBuildDate>Sat, 17 Oct 2026 10:30:00 +0000</lastBuildDate>"#,
+                r#"<item><title>Tom &amp; Jerry &lt;say&gt; &quot;hi&quot;</title>"#,
+                r#"<link>https://example.com/news?id=2&amp;x=1</link><guid isPermaLink="true">https://example.com/news?id=2&amp;x=1</guid>"#,
+                r#"<pubDate>Fri, 16 Oct 2026 09:30:00 +0000</pubDate><dc:creator>O&apos;Brien</dc:creator>"#,
+                r#"<description>Cats &amp; mice</description><content:encoded>&lt;p&gt;Cats &amp;amp; mice&lt;/p&gt;</content:encoded>"#,
+                r#"<enclosure url="https://example.com/a.jpg?w=1&amp;h=2" type="image/jpeg" length="0"/></item>"#,
+                r#"<item><title>https://example.com/bare</title><link>https://example.com/bare</link>"#,
+                r#"<guid isPermaLink="true">https://example.com/bare</guid><pubDate>Thu, 15 Oct 2026 08:30:00 +0000</pubDate></item>"#,
+                "</channel></rss>",
+            )
+        );
+    }
+
+    #[test]
+    fn atom_golden_output() {
+        let posts = posts();
+        assert_eq!(
+            render(FeedFormat::Atom, &feed(&posts)),
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?><feed xmlns="http://www.w3.org/2005/Atom">"#,
+                r#"<id>https://crawler.test/feeds/sites/1</id><title>News &amp; &lt;more&gt;</title><updated>2026-10-17T10:30:00Z</updated>"#,
+                r#"<link rel="self" type="application/atom+xml" href="https://crawler.test/feeds/sites/1"/><link rel="alternate" href="https://example.com/"/>"#,
+                r#"<generator>rust-crawler</generator>"#,
+                r#"<entry><id>https://example.com/news?id=2&amp;x=1</id><title>Tom &amp; Jerry &lt;say&gt; &quot;hi&quot;</title>"#,
+                r#"<link rel="alternate" href="https://example.com/news?id=2&amp;x=1"/>"#,
+                r#"<published>2026-10-16T09:30:00Z</published><updated>2026-10-17T10:30:00Z</updated>"#,
+                r#"<author><name>O&apos;Brien</name></author><summary>Cats &amp; mice</summary>"#,
+                r#"<content type="html">&lt;p&gt;Cats &amp;amp; mice&lt;/p&gt;</content>"#,
+                r#"<link rel="enclosure" type="image/jpeg" href="https://example.com/a.jpg?w=1&amp;h=2"/></entry>"#,
+                r#"<entry><id>https://example.com/bare</id><title>https://example.com/bare</title>"#,
+                r#"<link rel="alternate" href="https://example.com/bare"/>"#,
+                r#"<published>2026-10-15T08:30:00Z</published><updated>2026-10-15T08:30:00Z</updated></entry>"#,
+                "</feed>",
+            )
+        );
+    }
+
+    #[test]
+    fn json_feed_golden_output() {
+        let posts = posts();
+        assert_eq!(
+            json_feed(&feed(&posts)),
+            json!({
+                "version": "https://jsonfeed.org/version/1.1",
+                "title": "News & <more>",
+                "home_page_url": "https://example.com/",
+                "feed_url": "https://crawler.test/feeds/sites/1",
+                "items": [
+                    {
+                        "id": "https://example.com/news?id=2&x=1",
+                        "url": "https://example.com/news?id=2&x=1",
+                        "title": "Tom & Jerry <say> \"hi\"",
+                        "date_published": "2026-10-16T09:30:00Z",
+                        "date_modified": "2026-10-17T10:30:00Z",
+                        "content_html": "<p>Cats &amp; mice</p>",
+                        "content_text": "Cats & mice",
+                        "summary": "Cats & mice",
+                        "image": "https://example.com/a.jpg?w=1&h=2",
+                        "authors": [{ "name": "O'Brien" }],
+                    },
+                    {
+                        "id": "https://example.com/bare",
+                        "url": "https://example.com/bare",
+                        "title": "https://example.com/bare",
+                        "date_published": "2026-10-15T08:30:00Z",
+                        "date_modified": "2026-10-15T08:30:00Z",
+                        "content_text": "",
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn control_characters_are_dropped_from_xml() {
+        assert_eq!(escape("a\u{7}b\u{0}\tc\n"), "ab\tc\n");
+    }
+
+    #[test]
+    fn long_summaries_are_cut() {
+        let post = posts::Model {
+            body_text: Some(format!("{} tail", "word ".repeat(60))),
+            ..post(1, "https://example.com/long")
+        };
+        let summary = summary(&post).unwrap();
+        assert_eq!(summary.chars().count(), 280);
+        assert!(summary.ends_with("word…"));
+    }
+
+    #[test]
+    fn feed_file_names_split_into_stem_and_format() {
+        assert_eq!(FeedFormat::split("12.rss"), Some(("12", FeedFormat::Rss)));
+        assert_eq!(
+            FeedFormat::split("all.json"),
+            Some(("all", FeedFormat::Json))
+        );
+        assert_eq!(FeedFormat::split("12.xml"), None);
+        assert_eq!(FeedFormat::split("12"), None);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::core::state::APP_STATE;
+use ammonia::{Builder, Url, UrlRelative};
+use scraper::{Html, Selector};
+use std::collections::HashSet;
+
+/// Tags allowed on top of ammonia's defaults, so embedded media survive.
+const EXTRA_TAGS: [&str; 3] = ["video", "source", "picture"];
+const EXTRA_TAG_ATTRIBUTES: [(&str, &[&str]); 2] = [
+    ("video", &["src", "poster", "controls", "width", "height"]),
+    ("source", &["src", "type"]),
+];
+
+/// Clean an extracted post body for consumers that render it as-is: only allow-listed tags
+/// and attributes survive (`SANITIZE_ALLOWED_TAGS` / `SANITIZE_ALLOWED_ATTRIBUTES`, or
+/// ammonia's defaults plus video), scripts, styles, event handlers and 1×1 tracking pixels
+/// are dropped, and relative `href`/`src` values are made absolute against `base_url`.
+pub fn sanitize_html(html: &str, base_url: Option<&str>) -> String {
+    if html.trim().is_empty() {
+        return String::new();
+    }
+
+    let without_pixels = remove_tracking_pixels(html);
+
+    let mut builder = Builder::default();
+    builder.add_tags(EXTRA_TAGS);
+    for (tag, attributes) in EXTRA_TAG_ATTRIBUTES {
+        builder.add_tag_attributes(tag, attributes.iter().copied());
+    }
+
+    if let Some(config) = APP_STATE.get().map(|state| &state.config) {
+        // ammonia refuses to allow tags whose content it strips, and `rel`, which it sets itself.
+        let mut tags = split_list(&config.sanitize_allowed_tags);
+        tags.retain(|tag| !matches!(*tag, "script" | "style"));
+        if !tags.is_empty() {
+            builder.tags(tags);
+        }
+
+        let mut attributes = split_list(&config.sanitize_allowed_attributes);
+        attributes.retain(|entry| entry.rsplit(':').next() != Some("rel"));
+        if !attributes.is_empty() {
+            // `tag:attr` allows an attribute on one tag, a bare name allows it everywhere.
+            let mut generic = HashSet::new();
+            builder.tag_attributes(Default::default());
+            for entry in attributes {
+                match entry.split_once(':') {
+                    Some((tag, attribute)) => {
+                        builder.add_tag_attributes(tag, [attribute]);
+                    }
+                    None => {
+                        generic.insert(entry);
+                    }
+                }
+            }
+            builder.generic_attributes(generic);
+        }
+    }
+
+    let relative = base_url
+        .and_then(|base| Url::parse(base).ok())
+        .map(UrlRelative::RewriteWithBase)
+        .unwrap_or(UrlRelative::PassThrough);
+    builder.url_relative(relative);
+
+    builder.clean(&without_pixels).to_string()
+}
+
+/// Drop `<img>` elements sized 0 or 1 pixel, which only exist to report page views.
+fn remove_tracking_pixels(html: &str) -> String {
+    let mut fragment = Html::parse_fragment(html);
+    let selector = Selector::parse("img").expect("valid selector");
+
+    let pixels: Vec<_> = fragment
+        .select(&selector)
+        .filter(|img| {
+            let tiny = |name: &str| {
+                img.value()
+                    .attr(name)
+                    .and_then(|value| value.trim().trim_end_matches("px").parse::<u32>().ok())
+                    .is_some_and(|size| size <= 1)
+            };
+            tiny("width") && tiny("height")
+        })
+        .map(|img| img.id())
+        .collect();
+
+    if pixels.is_empty() {
+        return html.to_string();
+    }
+
+    for id in pixels {
+        if let Some(mut node) = fragment.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+
+    fragment.root_element().inner_html()
+}
+
+fn split_list(raw: &str) -> HashSet<&str> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: Option<&str> = Some("https://example.com/news/post");
+
+    #[test]
+    fn scripts_styles_and_event_handlers_are_dropped() {
+        let clean = sanitize_html(
+            r#"<p onclick="steal()">Hi<script>steal()</script><style>p{}</style></p><iframe src="/x"></iframe>"#,
+            BASE,
+        );
+        assert_eq!(clean, "<p>Hi</p>");
+    }
+
+    #[test]
+    fn allow_listed_markup_and_media_survive() {
+        let clean = sanitize_html(
+            r#"<h2>T</h2><p><b>b</b> <em>e</em></p><video src="/v.mp4" poster="/p.jpg" controls="" onplay="x()"><source src="/v.webm" type="video/webm"></video>"#,
+            BASE,
+        );
+        assert_eq!(
+            clean,
+            r#"<h2>T</h2><p><b>b</b> <em>e</em></p><video src="https://example.com/v.mp4" poster="https://example.com/p.jpg" controls=""><source src="https://example.com/v.webm" type="video/webm"></video>"#
+        );
+    }
+
+    #[test]
+    fn javascript_urls_are_removed() {
+        let clean = sanitize_html(
+            r#"<a href="javascript:alert(1)">x</a><a href=" JaVaScRiPt:alert(1)">y</a><img src="data:text/html,x">"#,
+            BASE,
+        );
+        assert!(
+            !clean.to_ascii_lowercase().contains("javascript"),
+            "{clean}"
+        );
+        assert!(!clean.contains("data:"), "{clean}");
+    }
+
+    #[test]
+    fn relative_urls_are_made_absolute() {
+        let clean = sanitize_html(
+            r#"<a href="../about">a</a><a href="/top">b</a><img src="pic.jpg"><a href="https://other.org/x">c</a>"#,
+            BASE,
+        );
+        assert!(
+            clean.contains(r#"href="https://example.com/about""#),
+            "{clean}"
+        );
+        assert!(
+            clean.contains(r#"href="https://example.com/top""#),
+            "{clean}"
+        );
+        assert!(
+            clean.contains(r#"src="https://example.com/news/pic.jpg""#),
+            "{clean}"
+        );
+        assert!(clean.contains(r#"href="https://other.org/x""#), "{clean}");
+
+        let without_base = sanitize_html(r#"<a href="/top">b</a>"#, None);
+        assert!(without_base.contains(r#"href="/top""#), "{without_base}");
+    }
+
+    #[test]
+    fn tracking_pixels_are_dropped() {
+        let clean = sanitize_html(
+            r#"<p>x<img src="/t.gif" width="1" height="1px"><img src="/a.jpg" width="1" height="300"></p>"#,
+            BASE,
+        );
+        assert!(!clean.contains("t.gif"), "{clean}");
+        assert!(clean.contains("a.jpg"), "{clean}");
+    }
+
+    #[test]
+    fn blank_input_stays_blank() {
+        assert_eq!(sanitize_html("  \n", BASE), "");
+    }
+}
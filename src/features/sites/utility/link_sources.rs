@@ -0,0 +1,649 @@
+use crate::core::logger::targets;
+use crate::features::crawler::AnyError;
+use crate::features::crawler::http_page::download;
+use crate::features::sites::model::site::SourceType;
+use crate::features::sites::utility::date_parser::parse_date;
+use crate::features::sites::utility::normalize_link::resolve_link;
+use chrono::{Duration, NaiveDateTime, Utc};
+use flate2::read::GzDecoder;
+use scraper::{Html, Selector};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::io::Read;
+use sxd_document::dom::{ChildOfElement, ChildOfRoot, Element};
+use sxd_document::parser;
+use tracing::warn;
+
+/// Entries older than this many hours are skipped unless the site sets `max_age_hours`.
+pub const DEFAULT_MAX_AGE_HOURS: i32 = 48;
+
+/// The sitemap protocol caps a file at 50 MB uncompressed; feeds are far smaller.
+const MAX_DOCUMENT_BYTES: u64 = 50 * 1024 * 1024;
+/// Child sitemaps read from an index per crawl, newest first.
+const MAX_CHILD_SITEMAPS: usize = 10;
+/// Sitemap indexes may point at further indexes, but not endlessly.
+const MAX_SITEMAP_DEPTH: usize = 3;
+/// Links taken from one source per crawl.
+const MAX_LINKS: usize = 1_000;
+
+const FEED_LINK_SELECTOR: &str = "link[rel~=alternate][type*=rss], \
+                                  link[rel~=alternate][type*=atom], \
+                                  link[rel~=alternate][type*=feed]";
+
+/// Post URLs read from a sitemap or feed, and how many documents were fetched for them.
+#[derive(Debug, Default)]
+pub struct SourceLinks {
+    pub links: Vec<String>,
+    pub documents: i32,
+}
+
+/// A `<loc>`/`<link>` with the date the source gives for it, if any.
+struct Entry {
+    url: String,
+    date: Option<NaiveDateTime>,
+}
+
+/// Read post URLs from a site's sitemap or feed at `url`. Entries dated before
+/// `max_age_hours` ago are dropped; undated ones are kept.
+pub async fn discover_links(
+    source_type: &SourceType,
+    url: &str,
+    max_age_hours: Option<i32>,
+) -> Result<SourceLinks, AnyError> {
+    let hours = max_age_hours.unwrap_or(DEFAULT_MAX_AGE_HOURS).max(1);
+    let cutoff = Utc::now().naive_utc() - Duration::hours(hours as i64);
+
+    let mut result = SourceLinks::default();
+    let entries = match source_type {
+        SourceType::Sitemap => read_sitemap(url, cutoff, &mut result).await?,
+        SourceType::Feed => read_feed(url, &mut result).await?,
+        SourceType::Listing => return Err("Listing sites have no sitemap or feed".into()),
+    };
+
+    let mut seen = HashSet::new();
+    result.links = entries
+        .into_iter()
+        .filter(|entry| entry.date.is_none_or(|date| date >= cutoff))
+        .map(|entry| entry.url)
+        .filter(|url| seen.insert(url.clone()))
+        .take(MAX_LINKS)
+        .collect();
+
+    Ok(result)
+}
+
+/// Read a sitemap, sitemap index, plain-text sitemap or the `Sitemap:` lines of a robots.txt.
+/// Only a failure of `url` itself fails the read; a child sitemap that can't be fetched or
+/// parsed is logged and skipped, so one broken file doesn't hide the others.
+async fn read_sitemap(
+    url: &str,
+    cutoff: NaiveDateTime,
+    result: &mut SourceLinks,
+) -> Result<Vec<Entry>, AnyError> {
+    read_sitemap_with(url, cutoff, result, fetch_document).await
+}
+
+/// [`read_sitemap`] with the documents fetched by `fetch`.
+async fn read_sitemap_with(
+    url: &str,
+    cutoff: NaiveDateTime,
+    result: &mut SourceLinks,
+    fetch: impl AsyncFn(&str) -> Result<String, AnyError>,
+) -> Result<Vec<Entry>, AnyError> {
+    let mut entries = Vec::new();
+    let mut queue = vec![(url.to_string(), 0)];
+    let mut visited = HashSet::new();
+
+    while let Some((url, depth)) = queue.pop() {
+        if !visited.insert(url.clone()) {
+            continue;
+        }
+
+        let read = match fetch(&url).await {
+            Ok(text) => {
+                result.documents += 1;
+                parse_sitemap(&url, &text, depth, cutoff, &mut entries)
+            }
+            Err(e) => Err(e),
+        };
+        match read {
+            Ok(children) => queue.extend(children),
+            Err(e) if depth == 0 => return Err(e),
+            Err(e) => warn!(
+                target: targets::CRAWLER_SITE,
+                url = %url,
+                error = %e,
+                "Skipping child sitemap"
+            ),
+        }
+    }
+
+    // Newest first, so the link cap keeps the recent posts of large sitemaps.
+    entries.sort_by_key(|entry| Reverse(entry.date));
+    Ok(entries)
+}
+
+/// Read one sitemap document into `entries`, returning the child sitemaps it points at.
+fn parse_sitemap(
+    url: &str,
+    text: &str,
+    depth: usize,
+    cutoff: NaiveDateTime,
+    entries: &mut Vec<Entry>,
+) -> Result<Vec<(String, usize)>, AnyError> {
+    let trimmed = text.trim_start();
+    if !trimmed.starts_with('<') {
+        // robots.txt lists sitemaps; a text sitemap lists one page URL per line.
+        let sitemaps = robots_sitemaps(trimmed);
+        if sitemaps.is_empty() {
+            entries.extend(text_sitemap(trimmed));
+        } else if depth < MAX_SITEMAP_DEPTH {
+            return Ok(sitemaps
+                .into_iter()
+                .map(|child| (child, depth + 1))
+                .collect());
+        }
+        return Ok(Vec::new());
+    }
+
+    let package = parser::parse(trimmed).map_err(|e| format!("Invalid sitemap {url}: {e}"))?;
+    let document = package.as_document();
+    let root = root_element(&document).ok_or_else(|| format!("Empty sitemap {url}"))?;
+
+    match root.name().local_part() {
+        "urlset" => {
+            entries.extend(children_named(root, "url").filter_map(|item| sitemap_entry(item, url)));
+            Ok(Vec::new())
+        }
+        "sitemapindex" => {
+            if depth >= MAX_SITEMAP_DEPTH {
+                return Ok(Vec::new());
+            }
+
+            let mut children: Vec<Entry> = children_named(root, "sitemap")
+                .filter_map(|item| sitemap_entry(item, url))
+                .filter(|child| child.date.is_none_or(|date| date >= cutoff))
+                .collect();
+            // Undated children sort last; the newest dated ones most likely hold new posts.
+            children.sort_by_key(|child| Reverse(child.date));
+            Ok(children
+                .into_iter()
+                .take(MAX_CHILD_SITEMAPS)
+                .rev()
+                .map(|child| (child.url, depth + 1))
+                .collect())
+        }
+        other => Err(format!("Not a sitemap {url}: <{other}>").into()),
+    }
+}
+
+/// `<url>`/`<sitemap>` entry: its `<loc>` and the `news:publication_date` or `<lastmod>`.
+fn sitemap_entry(item: Element, base: &str) -> Option<Entry> {
+    let url = child_text(item, "loc").and_then(|loc| resolve_link(base, &loc))?;
+    let now = Utc::now();
+    let date = children_named(item, "news")
+        .find_map(|news| child_text(news, "publication_date"))
+        .or_else(|| child_text(item, "lastmod"))
+        .and_then(|raw| parse_date(&raw, now));
+
+    Some(Entry { url, date })
+}
+
+fn robots_sitemaps(text: &str) -> Vec<String> {
+    text.lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.trim()
+                .eq_ignore_ascii_case("sitemap")
+                .then(|| value.trim().to_string())
+        })
+        .filter(|value| value.starts_with("http"))
+        .collect()
+}
+
+fn text_sitemap(text: &str) -> Vec<Entry> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("http://") || line.starts_with("https://"))
+        .map(|line| Entry {
+            url: line.to_string(),
+            date: None,
+        })
+        .collect()
+}
+
+/// Read an RSS 2.0, RSS 1.0 (RDF) or Atom feed. An HTML page is searched for its
+/// `<link rel="alternate">` feed, so `url_list` may also be the site's home page.
+async fn read_feed(url: &str, result: &mut SourceLinks) -> Result<Vec<Entry>, AnyError> {
+    let mut url = url.to_string();
+    let mut text = fetch_document(&url).await?;
+    result.documents += 1;
+
+    if looks_like_html(&text) {
+        url = discover_feed(&text, &url).ok_or_else(|| format!("No feed linked from {url}"))?;
+        text = fetch_document(&url).await?;
+        result.documents += 1;
+    }
+
+    parse_feed(&url, &text)
+}
+
+fn parse_feed(url: &str, text: &str) -> Result<Vec<Entry>, AnyError> {
+    let package =
+        parser::parse(text.trim_start()).map_err(|e| format!("Invalid feed {url}: {e}"))?;
+    let document = package.as_document();
+    let root = root_element(&document).ok_or_else(|| format!("Empty feed {url}"))?;
+
+    let entries = match root.name().local_part() {
+        "rss" => children_named(root, "channel")
+            .flat_map(|channel| children_named(channel, "item"))
+            .filter_map(|item| rss_entry(item, url))
+            .collect(),
+        // RSS 1.0 keeps its items next to the channel.
+        "RDF" => children_named(root, "item")
+            .filter_map(|item| rss_entry(item, url))
+            .collect(),
+        "feed" => children_named(root, "entry")
+            .filter_map(|entry| atom_entry(entry, url))
+            .collect(),
+        other => return Err(format!("Not a feed {url}: <{other}>").into()),
+    };
+
+    Ok(entries)
+}
+
+fn rss_entry(item: Element, base: &str) -> Option<Entry> {
+    let link = child_text(item, "link").or_else(|| {
+        // A permalink guid is the item URL when `<link>` is missing.
+        children_named(item, "guid")
+            .find(|guid| guid.attribute_value("isPermaLink") != Some("false"))
+            .map(element_text)
+            .filter(|guid| guid.starts_with("http"))
+    })?;
+    let now = Utc::now();
+    let date = child_text(item, "pubDate")
+        .or_else(|| child_text(item, "date"))
+        .and_then(|raw| parse_date(&raw, now));
+
+    Some(Entry {
+        url: resolve_link(base, &link)?,
+        date,
+    })
+}
+
+fn atom_entry(entry: Element, base: &str) -> Option<Entry> {
+    let href = children_named(entry, "link")
+        .filter(|link| {
+            link.attribute_value("rel")
+                .is_none_or(|rel| rel == "alternate")
+        })
+        .find_map(|link| link.attribute_value("href"))?;
+    let now = Utc::now();
+    let date = child_text(entry, "published")
+        .or_else(|| child_text(entry, "updated"))
+        .and_then(|raw| parse_date(&raw, now));
+
+    Some(Entry {
+        url: resolve_link(base, href)?,
+        date,
+    })
+}
+
+fn looks_like_html(text: &str) -> bool {
+    let head: String = text.trim_start().chars().take(256).collect();
+    let head = head.to_ascii_lowercase();
+    head.starts_with("<!doctype html") || head.contains("<html")
+}
+
+fn discover_feed(html: &str, base: &str) -> Option<String> {
+    let selector = Selector::parse(FEED_LINK_SELECTOR).expect("valid selector");
+    Html::parse_document(html)
+        .select(&selector)
+        .filter_map(|link| link.value().attr("href"))
+        .find_map(|href| resolve_link(base, href))
+}
+
+/// Fetch a sitemap or feed as text, gunzipping `.xml.gz` files.
+async fn fetch_document(url: &str) -> Result<String, AnyError> {
+    let bytes = download(url, MAX_DOCUMENT_BYTES).await?.bytes;
+
+    let bytes = if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut unpacked = Vec::new();
+        GzDecoder::new(bytes.as_slice())
+            .take(MAX_DOCUMENT_BYTES + 1)
+            .read_to_end(&mut unpacked)?;
+        if unpacked.len() as u64 > MAX_DOCUMENT_BYTES {
+            return Err(format!("{url} is larger than {MAX_DOCUMENT_BYTES} bytes").into());
+        }
+        unpacked
+    } else {
+        bytes
+    };
+
+    let text = String::from_utf8_lossy(&bytes);
+    Ok(text.trim_start_matches('\u{feff}').to_string())
+}
+
+fn root_element<'d>(document: &sxd_document::dom::Document<'d>) -> Option<Element<'d>> {
+    document
+        .root()
+        .children()
+        .into_iter()
+        .find_map(|child| match child {
+            ChildOfRoot::Element(element) => Some(element),
+            _ => None,
+        })
+}
+
+/// Child elements by local name, whatever namespace (or prefix) they use.
+fn children_named<'d>(
+    element: Element<'d>,
+    name: &'static str,
+) -> impl Iterator<Item = Element<'d>> {
+    element
+        .children()
+        .into_iter()
+        .filter_map(|child| child.element())
+        .filter(move |child| child.name().local_part() == name)
+}
+
+fn child_text(element: Element, name: &'static str) -> Option<String> {
+    children_named(element, name)
+        .map(element_text)
+        .find(|text| !text.is_empty())
+}
+
+fn element_text(element: Element) -> String {
+    element
+        .children()
+        .into_iter()
+        .filter_map(|child| match child {
+            ChildOfElement::Text(text) => Some(text.text()),
+            _ => None,
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::collections::HashMap;
+
+    const BASE: &str = "https://example.com/sitemap.xml";
+
+    fn cutoff() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    fn urls(entries: &[Entry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.url.as_str()).collect()
+    }
+
+    type Children = Result<Vec<(String, usize)>, AnyError>;
+
+    fn sitemap(text: &str, depth: usize) -> (Vec<Entry>, Children) {
+        let mut entries = Vec::new();
+        let children = parse_sitemap(BASE, text, depth, cutoff(), &mut entries);
+        (entries, children)
+    }
+
+    /// Read a sitemap tree from inline documents; URLs missing from `documents` fail to load.
+    async fn read(url: &str, documents: &[(&str, &str)]) -> (Result<Vec<String>, String>, i32) {
+        let documents: HashMap<&str, &str> = documents.iter().copied().collect();
+        let mut result = SourceLinks::default();
+        let read = read_sitemap_with(url, cutoff(), &mut result, async |url: &str| {
+            documents
+                .get(url)
+                .map(|text| text.to_string())
+                .ok_or_else(|| format!("{url} not found").into())
+        })
+        .await;
+        let urls = read
+            .map(|entries| entries.into_iter().map(|entry| entry.url).collect())
+            .map_err(|e| e.to_string());
+        (urls, result.documents)
+    }
+
+    #[test]
+    fn urlset_entries_are_dated_by_news_date_then_lastmod() {
+        let (entries, children) = sitemap(
+            r#"<?xml version="1.0"?>
+            <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9" xmlns:news="http://www.google.com/schemas/sitemap-news/0.9">
+                <url><loc>https://example.com/a</loc><lastmod>2026-09-01</lastmod>
+                    <news:news><news:publication_date>2026-10-16T08:00:00Z</news:publication_date></news:news></url>
+                <url><loc> /b </loc><lastmod>2026-10-02T10:00:00+00:00</lastmod></url>
+                <url><loc>/c</loc></url>
+                <url><lastmod>2026-10-02</lastmod></url>
+            </urlset>"#,
+            0,
+        );
+
+        assert!(children.unwrap().is_empty());
+        assert_eq!(
+            urls(&entries),
+            vec![
+                "https://example.com/a",
+                "https://example.com/b",
+                "https://example.com/c"
+            ]
+        );
+        let dates: Vec<_> = entries
+            .iter()
+            .map(|entry| entry.date.map(|date| date.to_string()))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![
+                Some("2026-10-16 08:00:00".to_string()),
+                Some("2026-10-02 10:00:00".to_string()),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn sitemap_index_lists_recent_children_newest_first() {
+        let (entries, children) = sitemap(
+            r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+                <sitemap><loc>/old.xml</loc><lastmod>2026-08-01</lastmod></sitemap>
+                <sitemap><loc>/undated.xml</loc></sitemap>
+                <sitemap><loc>/new.xml</loc><lastmod>2026-10-16</lastmod></sitemap>
+                <sitemap><loc>/mid.xml</loc><lastmod>2026-10-05</lastmod></sitemap>
+            </sitemapindex>"#,
+            1,
+        );
+
+        assert!(entries.is_empty());
+        // Popped from the end of the queue, so the newest comes last.
+        assert_eq!(
+            children.unwrap(),
+            vec![
+                ("https://example.com/undated.xml".to_string(), 2),
+                ("https://example.com/mid.xml".to_string(), 2),
+                ("https://example.com/new.xml".to_string(), 2),
+            ]
+        );
+        let (_, too_deep) = sitemap(
+            "<sitemapindex><sitemap><loc>/x.xml</loc></sitemap></sitemapindex>",
+            MAX_SITEMAP_DEPTH,
+        );
+        assert!(too_deep.unwrap().is_empty());
+    }
+
+    #[test]
+    fn text_sitemaps_and_robots_files() {
+        let (entries, children) = sitemap(
+            "https://example.com/a\n# note\n https://example.com/b \n",
+            0,
+        );
+        assert!(children.unwrap().is_empty());
+        assert_eq!(
+            urls(&entries),
+            vec!["https://example.com/a", "https://example.com/b"]
+        );
+
+        let (entries, children) = sitemap(
+            "User-agent: *\nDisallow: /admin\nSitemap: https://example.com/news.xml\nsitemap:https://example.com/pages.xml\n",
+            0,
+        );
+        assert!(entries.is_empty());
+        assert_eq!(
+            children.unwrap(),
+            vec![
+                ("https://example.com/news.xml".to_string(), 1),
+                ("https://example.com/pages.xml".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn other_documents_are_not_sitemaps() {
+        assert!(sitemap("<html><body>hi</body></html>", 0).1.is_err());
+        assert!(sitemap("<urlset><url>", 0).1.is_err());
+    }
+
+    #[tokio::test]
+    async fn sitemap_trees_are_followed_and_broken_children_skipped() {
+        let (urls, documents) = read(
+            "https://example.com/robots.txt",
+            &[
+                (
+                    "https://example.com/robots.txt",
+                    "Sitemap: https://example.com/index.xml",
+                ),
+                (
+                    "https://example.com/index.xml",
+                    r#"<sitemapindex>
+                        <sitemap><loc>/broken.xml</loc></sitemap>
+                        <sitemap><loc>/missing.xml</loc></sitemap>
+                        <sitemap><loc>/index.xml</loc></sitemap>
+                        <sitemap><loc>/posts.xml</loc><lastmod>2026-10-16</lastmod></sitemap>
+                    </sitemapindex>"#,
+                ),
+                (
+                    "https://example.com/broken.xml",
+                    "<urlset><url><loc>/x</loc>",
+                ),
+                (
+                    "https://example.com/posts.xml",
+                    r#"<urlset>
+                        <url><loc>/undated</loc></url>
+                        <url><loc>/new</loc><lastmod>2026-10-16</lastmod></url>
+                        <url><loc>/newer</loc><lastmod>2026-10-17</lastmod></url>
+                    </urlset>"#,
+                ),
+            ],
+        )
+        .await;
+
+        assert_eq!(
+            urls.unwrap(),
+            vec![
+                "https://example.com/newer",
+                "https://example.com/new",
+                "https://example.com/undated"
+            ]
+        );
+        // robots.txt, the index (once, despite pointing at itself), posts.xml and broken.xml.
+        assert_eq!(documents, 4);
+    }
+
+    #[tokio::test]
+    async fn a_failing_root_fails_the_read() {
+        let (urls, _) = read("https://example.com/sitemap.xml", &[]).await;
+        assert_eq!(
+            urls.unwrap_err(),
+            "https://example.com/sitemap.xml not found"
+        );
+
+        let (urls, _) = read(
+            "https://example.com/sitemap.xml",
+            &[("https://example.com/sitemap.xml", "<rss></rss>")],
+        )
+        .await;
+        assert!(urls.unwrap_err().starts_with("Not a sitemap"));
+    }
+
+    #[test]
+    fn rss_items_use_their_link_or_permalink_guid() {
+        let entries = parse_feed(
+            "https://example.com/feed",
+            r#"<?xml version="1.0"?><rss version="2.0"><channel><title>T</title>
+                <item><link>https://example.com/a</link><pubDate>Fri, 16 Oct 2026 08:00:00 GMT</pubDate></item>
+                <item><guid>https://example.com/b</guid></item>
+                <item><guid isPermaLink="false">https://example.com/c</guid></item>
+                <item><link>/d</link></item>
+            </channel></rss>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            urls(&entries),
+            vec![
+                "https://example.com/a",
+                "https://example.com/b",
+                "https://example.com/d"
+            ]
+        );
+        assert_eq!(
+            entries[0].date.map(|date| date.to_string()).as_deref(),
+            Some("2026-10-16 08:00:00")
+        );
+    }
+
+    #[test]
+    fn rdf_feeds_keep_items_beside_the_channel() {
+        let entries = parse_feed(
+            "https://example.com/feed",
+            r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns="http://purl.org/rss/1.0/" xmlns:dc="http://purl.org/dc/elements/1.1/">
+                <channel><title>T</title></channel>
+                <item><link>https://example.com/a</link><dc:date>2026-10-16T08:00:00Z</dc:date></item>
+            </rdf:RDF>"#,
+        )
+        .unwrap();
+
+        assert_eq!(urls(&entries), vec!["https://example.com/a"]);
+        assert!(entries[0].date.is_some());
+    }
+
+    #[test]
+    fn atom_entries_use_their_alternate_link() {
+        let entries = parse_feed(
+            "https://example.com/feed.atom",
+            r#"<feed xmlns="http://www.w3.org/2005/Atom">
+                <entry><link rel="enclosure" href="/a.jpg"/><link rel="alternate" href="/a"/><updated>2026-10-16T08:00:00Z</updated></entry>
+                <entry><link href="https://example.com/b"/></entry>
+                <entry><link rel="self" href="/c"/></entry>
+            </feed>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            urls(&entries),
+            vec!["https://example.com/a", "https://example.com/b"]
+        );
+        assert!(entries[0].date.is_some());
+        assert!(parse_feed("https://example.com/feed", "<urlset/>").is_err());
+    }
+
+    #[test]
+    fn html_pages_link_to_their_feed() {
+        let html = r#"<!DOCTYPE html><html><head>
+            <link rel="stylesheet" href="/s.css">
+            <link rel="alternate" type="application/rss+xml" href="/feed.rss">
+        </head></html>"#;
+        assert!(looks_like_html(html));
+        assert!(!looks_like_html("<?xml version=\"1.0\"?><rss/>"));
+        assert_eq!(
+            discover_feed(html, "https://example.com/news/").as_deref(),
+            Some("https://example.com/feed.rss")
+        );
+        assert_eq!(discover_feed("<html></html>", "https://example.com/"), None);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::features::crawler::fetcher::Fetcher;
+use crate::features::sites::model::site;
+use crate::features::sites::utility::normalize_link::resolve_link;
+use crate::features::sites::utility::selector_chain::SelectorChain;
+use std::borrow::Cow;
+use validator::ValidationError;
+
+/// Stands for the page number in `page_url_template`.
+pub const PAGE_NUMBER_PLACEHOLDER: &str = "{n}";
+
+/// How a site's listing continues past `url_list`.
+pub struct Pagination {
+    next_page: SelectorChain,
+    template: Option<String>,
+    pub max_pages: u32,
+}
+
+impl Pagination {
+    pub fn new(next_page: Option<&str>, template: Option<&str>, max_pages: Option<i32>) -> Self {
+        Self {
+            next_page: SelectorChain::new(next_page.unwrap_or("")),
+            template: template
+                .filter(|template| template.contains(PAGE_NUMBER_PLACEHOLDER))
+                .map(str::to_string),
+            max_pages: max_pages.unwrap_or(1).max(1) as u32,
+        }
+    }
+
+    pub fn from_site(site: &site::Model) -> Self {
+        Self::new(
+            site.path_next_page.as_deref(),
+            site.page_url_template.as_deref(),
+            site.max_pages,
+        )
+    }
+
+    /// The URL of listing page `number + 1`, read from the loaded page `number` at
+    /// `current_url`: the `path_next_page` link when it matches, otherwise the template.
+    pub async fn next_url(
+        &self,
+        page: &dyn Fetcher,
+        url_list: &str,
+        current_url: &str,
+        number: u32,
+    ) -> Option<String> {
+        if number >= self.max_pages {
+            return None;
+        }
+
+        if !self.next_page.is_empty()
+            && let Ok(links) = self.next_page.attrs(page, "href").await
+            && let Some(next) = links
+                .iter()
+                .find_map(|href| resolve_link(current_url, href))
+        {
+            return Some(next);
+        }
+
+        let template = self.template.as_deref()?;
+        resolve_link(
+            url_list,
+            &template.replace(PAGE_NUMBER_PLACEHOLDER, &(number + 1).to_string()),
+        )
+    }
+}
+
+/// Form validator for `page_url_template`. A blank value disables it.
+pub fn validate_page_url_template(template: &str) -> Result<(), ValidationError> {
+    if template.trim().is_empty() || template.contains(PAGE_NUMBER_PLACEHOLDER) {
+        return Ok(());
+    }
+
+    Err(
+        ValidationError::new("page_url_template").with_message(Cow::Owned(format!(
+            "must contain {PAGE_NUMBER_PLACEHOLDER} where the page number goes"
+        ))),
+    )
+}
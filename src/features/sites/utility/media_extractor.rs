@@ -0,0 +1,241 @@
+use crate::features::crawler::fetcher::{ElementAttrs, Fetcher};
+use crate::features::sites::model::post_media::MediaKind;
+use crate::features::sites::utility::normalize_link::normalize_link;
+use crate::features::sites::utility::selector_chain::{SelectorChain, SelectorRule};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Galleries beyond this many items per kind are truncated.
+const MAX_MEDIA_PER_KIND: usize = 50;
+
+// Lazy-loading attributes first: `src` often holds a placeholder until scripts run.
+const IMAGE_URL_ATTRS: [&str; 6] = [
+    "data-src",
+    "data-lazy-src",
+    "data-original",
+    "src",
+    "content",
+    "href",
+];
+const IMAGE_SRCSET_ATTRS: [&str; 2] = ["data-srcset", "srcset"];
+const VIDEO_URL_ATTRS: [&str; 4] = ["data-src", "src", "content", "href"];
+
+/// One image or video found on an article page, in page order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExtractedMedia {
+    pub kind: MediaKind,
+    pub url: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub alt: Option<String>,
+}
+
+/// Every image matched by `images` and video matched by `videos`, including the candidates
+/// of `srcset` and of nested `<source>` elements (the largest one wins). Of each chain, the
+/// first alternative that finds anything is used.
+pub async fn collect_media(
+    page: &dyn Fetcher,
+    base_url: &str,
+    images: &SelectorChain,
+    videos: &SelectorChain,
+) -> Vec<ExtractedMedia> {
+    let mut media = Vec::new();
+
+    for (kind, chain) in [(MediaKind::Image, images), (MediaKind::Video, videos)] {
+        for rule in chain.rules() {
+            let found = media_of_rule(page, base_url, kind, rule).await;
+            if !found.is_empty() {
+                media.extend(found.into_iter().take(MAX_MEDIA_PER_KIND));
+                break;
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    media.retain(|item| seen.insert((item.kind, item.url.clone())));
+    media
+}
+
+/// Media of one selector alternative. A rule naming an attribute takes its URLs from that
+/// attribute as-is; otherwise the URL attributes are guessed from the element.
+async fn media_of_rule(
+    page: &dyn Fetcher,
+    base_url: &str,
+    kind: MediaKind,
+    rule: &SelectorRule,
+) -> Vec<ExtractedMedia> {
+    if let Some(attr) = rule.attr() {
+        return rule
+            .attrs(page, attr)
+            .await
+            .unwrap_or_default()
+            .iter()
+            .map(|url| url.trim())
+            .filter(|url| usable_url(url))
+            .map(|url| ExtractedMedia {
+                kind,
+                url: normalize_link(base_url, url),
+                width: None,
+                height: None,
+                alt: None,
+            })
+            .collect();
+    }
+
+    let elements = page.get_elements(&rule.selector).await.unwrap_or_default();
+    elements
+        .iter()
+        .filter_map(|element| match kind {
+            MediaKind::Image => image_from(element, base_url),
+            MediaKind::Video => video_from(element, base_url),
+        })
+        .collect()
+}
+
+/// The first item of a kind, which becomes the post's `image`/`video`.
+pub fn first_url_of(media: &[ExtractedMedia], kind: MediaKind) -> Option<String> {
+    media
+        .iter()
+        .find(|item| item.kind == kind)
+        .map(|item| item.url.clone())
+}
+
+/// Make sure the post's single `image`/`video` (which may come from page metadata)
+/// is part of its media list.
+pub fn include_primary(media: &mut Vec<ExtractedMedia>, kind: MediaKind, url: &str) {
+    if url.is_empty() || media.iter().any(|m| m.kind == kind && m.url == url) {
+        return;
+    }
+
+    let position = media
+        .iter()
+        .position(|m| m.kind == kind)
+        .unwrap_or(media.len());
+    media.insert(
+        position,
+        ExtractedMedia {
+            kind,
+            url: url.to_string(),
+            width: None,
+            height: None,
+            alt: None,
+        },
+    );
+}
+
+fn image_from(element: &ElementAttrs, base_url: &str) -> Option<ExtractedMedia> {
+    let nested_img = element.nested.iter().find(|n| n.tag == "img");
+
+    let (url, srcset_width) = best_image_url(&element.attrs).or_else(|| {
+        // <picture> and wrappers keep their URLs on nested <source>/<img> elements.
+        element
+            .nested
+            .iter()
+            .filter_map(|nested| best_image_url(&nested.attrs))
+            // `<source>` elements come first and win ties, as they do in the browser.
+            .reduce(|best, next| {
+                if next.1.unwrap_or(0) > best.1.unwrap_or(0) {
+                    next
+                } else {
+                    best
+                }
+            })
+    })?;
+
+    let alt = text_attr(&element.attrs, "alt")
+        .or_else(|| nested_img.and_then(|img| text_attr(&img.attrs, "alt")));
+    let dimension = |name: &str| {
+        dimension_attr(&element.attrs, name)
+            .or_else(|| nested_img.and_then(|img| dimension_attr(&img.attrs, name)))
+    };
+
+    Some(ExtractedMedia {
+        kind: MediaKind::Image,
+        url: normalize_link(base_url, &url),
+        width: dimension("width").or(srcset_width),
+        height: dimension("height"),
+        alt,
+    })
+}
+
+fn video_from(element: &ElementAttrs, base_url: &str) -> Option<ExtractedMedia> {
+    let url = first_url(&element.attrs, &VIDEO_URL_ATTRS).or_else(|| {
+        element
+            .nested
+            .iter()
+            .filter(|nested| nested.tag == "source")
+            .find_map(|nested| first_url(&nested.attrs, &VIDEO_URL_ATTRS))
+    })?;
+
+    Some(ExtractedMedia {
+        kind: MediaKind::Video,
+        url: normalize_link(base_url, &url),
+        width: dimension_attr(&element.attrs, "width"),
+        height: dimension_attr(&element.attrs, "height"),
+        alt: text_attr(&element.attrs, "title").or_else(|| text_attr(&element.attrs, "aria-label")),
+    })
+}
+
+/// The largest `srcset` candidate, or else the first plain URL attribute.
+fn best_image_url(attrs: &HashMap<String, String>) -> Option<(String, Option<i32>)> {
+    IMAGE_SRCSET_ATTRS
+        .iter()
+        .filter_map(|name| attrs.get(*name))
+        .find_map(|srcset| best_srcset_candidate(srcset))
+        .or_else(|| first_url(attrs, &IMAGE_URL_ATTRS).map(|url| (url, None)))
+}
+
+/// Pick the widest (`800w`) or densest (`2x`) candidate of a `srcset` value.
+fn best_srcset_candidate(srcset: &str) -> Option<(String, Option<i32>)> {
+    srcset
+        .split(',')
+        .filter_map(|candidate| {
+            let mut parts = candidate.split_whitespace();
+            let url = parts.next().filter(|url| usable_url(url))?;
+            let descriptor = parts.next().unwrap_or("1x");
+
+            let (score, width) = if let Some(w) = descriptor.strip_suffix('w') {
+                let w = w.parse::<i32>().ok()?;
+                (w as f64, Some(w))
+            } else {
+                let x = descriptor.strip_suffix('x').unwrap_or(descriptor);
+                (x.parse::<f64>().ok()? * 1_000.0, None)
+            };
+
+            Some((url.to_string(), width, score))
+        })
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(url, width, _)| (url, width))
+}
+
+fn first_url(attrs: &HashMap<String, String>, names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .filter_map(|name| attrs.get(*name))
+        .map(|value| value.trim())
+        .find(|value| usable_url(value))
+        .map(str::to_string)
+}
+
+fn usable_url(value: &str) -> bool {
+    !value.is_empty() && !value.starts_with("data:") && !value.starts_with("about:")
+}
+
+fn text_attr(attrs: &HashMap<String, String>, name: &str) -> Option<String> {
+    attrs
+        .get(name)
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn dimension_attr(attrs: &HashMap<String, String>, name: &str) -> Option<i32> {
+    attrs
+        .get(name)?
+        .trim()
+        .trim_end_matches("px")
+        .parse::<i32>()
+        .ok()
+        .filter(|value| *value > 0)
+}
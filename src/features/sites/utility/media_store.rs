@@ -0,0 +1,151 @@
+use crate::features::sites::utility::screenshot_store::media_path;
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const OBJECTS_DIRECTORY: &str = "objects";
+/// Extension of files still being written.
+const PARTIAL_EXTENSION: &str = "part";
+
+/// A mirrored file as stored under `<MEDIA_DIRECTORY>/objects/`.
+pub struct StoredMedia {
+    /// Hex BLAKE3 hash of the content, which is also the file name.
+    pub checksum: String,
+    pub size: i64,
+    pub mime: String,
+    /// `<checksum>.<ext>`, the name the `/media` route serves it under.
+    pub file_name: String,
+}
+
+/// Store `bytes` by content. Identical files from different posts or sites share one copy.
+pub async fn store_media(bytes: &[u8], mime: &str) -> io::Result<StoredMedia> {
+    let checksum = blake3::hash(bytes).to_hex().to_string();
+    let file_name = format!("{checksum}.{}", extension_for(mime));
+    let path = object_path(&file_name).ok_or_else(|| io::Error::other("invalid media name"))?;
+
+    // A reused copy may be an orphan past its grace period; making it recent again keeps
+    // the cleanup from removing it before this mirror is recorded.
+    match touch(path.clone()).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => write_new(&path, bytes).await?,
+        Err(e) => return Err(e),
+    }
+
+    Ok(StoredMedia {
+        checksum,
+        size: bytes.len() as i64,
+        mime: mime.to_string(),
+        file_name,
+    })
+}
+
+async fn touch(path: PathBuf) -> io::Result<()> {
+    tokio::task::spawn_blocking(move || {
+        std::fs::File::options()
+            .write(true)
+            .open(path)?
+            .set_modified(SystemTime::now())
+    })
+    .await
+    .map_err(io::Error::other)?
+}
+
+/// Write beside the target under a name of our own and rename, so readers never see a
+/// half-written file and concurrent downloads of the same content don't collide.
+async fn write_new(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(format!(
+        ".{:016x}.{PARTIAL_EXTENSION}",
+        rand::random::<u64>()
+    ));
+    let partial = PathBuf::from(partial);
+
+    if let Err(e) = tokio::fs::write(&partial, bytes).await {
+        let _ = tokio::fs::remove_file(&partial).await;
+        return Err(e);
+    }
+    tokio::fs::rename(&partial, path).await
+}
+
+/// Where a served file name lives on disk, fanned out by the first two hash characters.
+/// `None` for anything that is not a `<blake3 hex>.<ext>` name, so requests can't escape the store.
+pub fn object_path(file_name: &str) -> Option<PathBuf> {
+    let (checksum, extension) = file_name.split_once('.')?;
+    let valid_checksum = checksum.len() == 64
+        && checksum
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+    let valid_extension = !extension.is_empty()
+        && extension.len() <= 8
+        && extension.bytes().all(|b| b.is_ascii_alphanumeric());
+
+    if !valid_checksum || !valid_extension {
+        return None;
+    }
+
+    Some(media_path(&format!(
+        "{OBJECTS_DIRECTORY}/{}/{file_name}",
+        &checksum[..2]
+    )))
+}
+
+/// Delete stored files whose checksum no post media references any more, and partial files
+/// left behind by interrupted writes. Files younger than `grace` are kept, since a mirror
+/// run may have written them before recording them, or still be writing them.
+pub async fn remove_orphans(referenced: &HashSet<String>, grace: Duration) -> io::Result<u64> {
+    let root = media_path(OBJECTS_DIRECTORY);
+    let Ok(mut buckets) = tokio::fs::read_dir(&root).await else {
+        return Ok(0);
+    };
+
+    let cutoff = SystemTime::now() - grace;
+    let mut removed = 0;
+
+    while let Some(bucket) = buckets.next_entry().await? {
+        if !bucket.file_type().await?.is_dir() {
+            continue;
+        }
+
+        let mut files = tokio::fs::read_dir(bucket.path()).await?;
+        while let Some(file) = files.next_entry().await? {
+            let name = file.file_name();
+            let name = name.to_string_lossy();
+            let partial = name.ends_with(&format!(".{PARTIAL_EXTENSION}"));
+            let checksum = name.split('.').next().unwrap_or_default();
+            if !partial && referenced.contains(checksum) {
+                continue;
+            }
+
+            let modified = file.metadata().await?.modified()?;
+            if modified < cutoff {
+                tokio::fs::remove_file(file.path()).await?;
+                removed += 1;
+            }
+        }
+    }
+
+    Ok(removed)
+}
+
+fn extension_for(mime: &str) -> &'static str {
+    match mime {
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/avif" => "avif",
+        "image/svg+xml" => "svg",
+        "video/mp4" => "mp4",
+        "video/webm" => "webm",
+        "video/ogg" => "ogv",
+        "video/quicktime" => "mov",
+        _ => mime_guess::get_mime_extensions_str(mime)
+            .and_then(|extensions| extensions.first().copied())
+            .unwrap_or("bin"),
+    }
+}
@@ -1,2 +1,19 @@
+pub mod content_hash;
+pub mod crawl_schedule;
+pub mod date_parser;
+pub mod feed_writer;
+pub mod html_sanitizer;
+pub mod link_sources;
+pub mod listing_pages;
+pub mod media_extractor;
+pub mod media_store;
 pub mod normalize_link;
+pub mod page_loader;
+pub mod page_metadata;
+pub mod post_extractor;
+pub mod readable_text;
+pub mod screenshot_store;
+pub mod search_query;
+pub mod selector_chain;
 pub(crate) mod site_error_tracker;
+pub mod site_lock;
@@ -1,3 +1,5 @@
+use reqwest::Url;
+
 pub fn normalize_link(base_url: &str, link: &str) -> String {
     if link.is_empty() {
         return link.to_string();
@@ -19,3 +21,15 @@ pub fn normalize_link(base_url: &str, link: &str) -> String {
         l
     }
 }
+
+/// Resolve a link found on (or configured for) a page against that page's URL.
+/// Fragment-only, script and other non-HTTP links lead nowhere and give `None`.
+pub fn resolve_link(base: &str, href: &str) -> Option<String> {
+    let href = href.trim();
+    if href.is_empty() || href.starts_with('#') || href.starts_with("javascript:") {
+        return None;
+    }
+
+    let url = Url::parse(base.trim()).ok()?.join(href).ok()?;
+    matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+}
@@ -0,0 +1,12 @@
+use crate::features::crawler::fetcher::Fetcher;
+use crate::features::crawler::http_page::HttpPage;
+use crate::features::crawler::{AnyError, Browser};
+use crate::features::sites::model::site::FetchMode;
+
+/// Load `url` with the backend configured for the site.
+pub async fn load_page(fetch_mode: &FetchMode, url: &str) -> Result<Box<dyn Fetcher>, AnyError> {
+    match fetch_mode {
+        FetchMode::Browser => Ok(Box::new(Browser::new(url, None, None).await?)),
+        FetchMode::Http => Ok(Box::new(HttpPage::new(url).await?)),
+    }
+}
@@ -0,0 +1,300 @@
+use crate::features::crawler::fetcher::Fetcher;
+use crate::features::sites::utility::normalize_link::normalize_link;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Metadata a page publishes about itself: OpenGraph/Twitter card tags, standard meta tags,
+/// `<link rel=canonical>` and the schema.org JSON-LD article object, merged in that order
+/// of preference. Stored as JSON on the post and used as fallback for blank selectors.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub site_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    /// Plain-text `articleBody` of the JSON-LD article, when the site publishes one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub article_body: Option<String>,
+    /// The JSON-LD article object as published.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_ld: Option<Value>,
+}
+
+impl PageMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The `articleBody` as HTML paragraphs, to stand in for a missing content selector.
+    pub fn article_body_html(&self) -> Option<String> {
+        let body = self.article_body.as_deref()?;
+        let paragraphs: String = body
+            .split('\n')
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| format!("<p>{}</p>", escape_html(line)))
+            .collect();
+
+        (!paragraphs.is_empty()).then_some(paragraphs)
+    }
+}
+
+/// Read the metadata of a loaded page. Call it before `path_remove` runs, since
+/// removal selectors such as `script` would also strip the JSON-LD blocks.
+pub async fn read_metadata(page: &dyn Fetcher, base_url: &str) -> PageMetadata {
+    match page.get_element_html("html").await {
+        Ok(html) => parse_metadata(&html, base_url),
+        Err(_) => PageMetadata::default(),
+    }
+}
+
+pub fn parse_metadata(html: &str, base_url: &str) -> PageMetadata {
+    let document = Html::parse_document(html);
+    let meta = MetaTags::collect(&document);
+    let article = json_ld_article(&document);
+    let ld = |key: &str| article.as_ref().and_then(|a| a.get(key));
+
+    let link = |value: Option<String>| {
+        value
+            .map(|v| normalize_link(base_url, &v))
+            .filter(|v| !v.is_empty())
+    };
+
+    let keyword_lists = meta.all(&["keywords", "news_keywords"]);
+    let tags = meta.all(&["article:tag"]);
+    let mut keywords: Vec<String> = keyword_lists
+        .iter()
+        .flat_map(|value| value.split(','))
+        .chain(tags.iter().map(String::as_str))
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .map(str::to_string)
+        .collect();
+    if keywords.is_empty() {
+        keywords = ld("keywords").map(keyword_list).unwrap_or_default();
+    }
+    let mut seen = HashSet::new();
+    keywords.retain(|keyword| seen.insert(keyword.to_lowercase()));
+
+    PageMetadata {
+        title: meta
+            .first(&["og:title"])
+            .or_else(|| ld("headline").and_then(text))
+            .or_else(|| meta.first(&["twitter:title"]))
+            .or_else(|| ld("name").and_then(text))
+            .or_else(|| element_text(&document, "title")),
+        description: meta
+            .first(&["og:description", "twitter:description", "description"])
+            .or_else(|| ld("description").and_then(text)),
+        image: link(
+            meta.first(&["og:image:secure_url", "og:image", "og:image:url"])
+                .or_else(|| meta.first(&["twitter:image", "twitter:image:src"]))
+                .or_else(|| ld("image").and_then(url_of)),
+        ),
+        video: link(
+            meta.first(&["og:video:secure_url", "og:video", "og:video:url"])
+                .or_else(|| meta.first(&["twitter:player:stream"]))
+                .or_else(|| ld("video").and_then(video_url)),
+        ),
+        canonical_url: link(
+            element_attr(&document, "link[rel~=canonical]", "href")
+                .or_else(|| meta.first(&["og:url"]))
+                .or_else(|| ld("mainEntityOfPage").and_then(url_of))
+                .or_else(|| ld("url").and_then(text)),
+        ),
+        site_name: meta
+            .first(&["og:site_name"])
+            .or_else(|| ld("publisher").and_then(name_of)),
+        published_time: meta
+            .first(&["article:published_time", "og:published_time"])
+            .or_else(|| ld("datePublished").and_then(text))
+            .or_else(|| meta.first(&["date", "pubdate", "publish-date", "dc.date"])),
+        modified_time: meta
+            .first(&["article:modified_time", "og:updated_time"])
+            .or_else(|| ld("dateModified").and_then(text)),
+        author: meta
+            .first(&["author", "article:author", "twitter:creator"])
+            .or_else(|| ld("author").and_then(name_of)),
+        section: meta
+            .first(&["article:section"])
+            .or_else(|| ld("articleSection").and_then(name_of)),
+        keywords,
+        article_body: ld("articleBody").and_then(text),
+        json_ld: article,
+    }
+}
+
+/// `<meta>` tags keyed by their lowercased `property` or `name`, in document order.
+struct MetaTags(Vec<(String, String)>);
+
+impl MetaTags {
+    fn collect(document: &Html) -> Self {
+        let selector = Selector::parse("meta[content]").expect("valid selector");
+        let tags = document
+            .select(&selector)
+            .filter_map(|element| {
+                let value = element.value();
+                let key = value.attr("property").or_else(|| value.attr("name"))?;
+                let content = value.attr("content")?.trim();
+                (!content.is_empty())
+                    .then(|| (key.trim().to_ascii_lowercase(), content.to_string()))
+            })
+            .collect();
+
+        Self(tags)
+    }
+
+    /// The first non-empty value among `keys`, trying the keys in order.
+    fn first(&self, keys: &[&str]) -> Option<String> {
+        keys.iter().find_map(|key| {
+            self.0
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, content)| content.clone())
+        })
+    }
+
+    fn all(&self, keys: &[&str]) -> Vec<String> {
+        self.0
+            .iter()
+            .filter(|(name, _)| keys.contains(&name.as_str()))
+            .map(|(_, content)| content.clone())
+            .collect()
+    }
+}
+
+/// The first schema.org article object among the page's JSON-LD blocks, looking inside
+/// arrays and `@graph` containers.
+fn json_ld_article(document: &Html) -> Option<Value> {
+    let selector =
+        Selector::parse(r#"script[type="application/ld+json"]"#).expect("valid selector");
+
+    document
+        .select(&selector)
+        .filter_map(|script| serde_json::from_str::<Value>(&script.text().collect::<String>()).ok())
+        .find_map(find_article)
+}
+
+fn find_article(value: Value) -> Option<Value> {
+    match value {
+        Value::Array(items) => items.into_iter().find_map(find_article),
+        Value::Object(mut object) => {
+            if object.get("@type").is_some_and(is_article_type) {
+                return Some(Value::Object(object));
+            }
+            object.remove("@graph").and_then(find_article)
+        }
+        _ => None,
+    }
+}
+
+fn is_article_type(value: &Value) -> bool {
+    match value {
+        Value::String(kind) => kind.ends_with("Article") || kind == "BlogPosting",
+        Value::Array(kinds) => kinds.iter().any(is_article_type),
+        _ => false,
+    }
+}
+
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Array(items) => items.iter().find_map(text),
+        _ => None,
+    }
+}
+
+/// A URL given as a string, an object with `url`/`@id`, or a list of either.
+fn url_of(value: &Value) -> Option<String> {
+    match value {
+        Value::Object(object) => object
+            .get("url")
+            .or_else(|| object.get("@id"))
+            .and_then(text),
+        Value::Array(items) => items.iter().find_map(url_of),
+        _ => text(value),
+    }
+}
+
+fn video_url(value: &Value) -> Option<String> {
+    match value {
+        Value::Object(object) => object
+            .get("contentUrl")
+            .or_else(|| object.get("embedUrl"))
+            .and_then(text),
+        Value::Array(items) => items.iter().find_map(video_url),
+        _ => text(value),
+    }
+}
+
+/// A name given as a string, an object with `name`, or a list of either (joined).
+fn name_of(value: &Value) -> Option<String> {
+    match value {
+        Value::Object(object) => object.get("name").and_then(text),
+        Value::Array(items) => {
+            let names: Vec<String> = items.iter().filter_map(name_of).collect();
+            (!names.is_empty()).then(|| names.join(", "))
+        }
+        _ => text(value),
+    }
+}
+
+fn keyword_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) => s
+            .split(',')
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Value::Array(items) => items.iter().filter_map(text).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn element_text(document: &Html, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    let text = document
+        .select(&selector)
+        .next()?
+        .text()
+        .collect::<String>();
+    Some(text.trim().to_string()).filter(|t| !t.is_empty())
+}
+
+fn element_attr(document: &Html, selector: &str, attr: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    document
+        .select(&selector)
+        .next()?
+        .value()
+        .attr(attr)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,313 @@
+use crate::features::crawler::AnyError;
+use crate::features::crawler::fetcher::Fetcher;
+use crate::features::crawler::readability::extract_main_content;
+use crate::features::sites::model::post_media::MediaKind;
+use crate::features::sites::model::posts::BodySource;
+use crate::features::sites::model::site;
+use crate::features::sites::utility::date_parser::parse_date;
+use crate::features::sites::utility::html_sanitizer::sanitize_html;
+use crate::features::sites::utility::media_extractor::{
+    ExtractedMedia, collect_media, first_url_of, include_primary,
+};
+use crate::features::sites::utility::page_metadata::PageMetadata;
+use crate::features::sites::utility::selector_chain::{SelectorChain, Source};
+use chrono::{NaiveDateTime, Utc};
+use serde::Serialize;
+
+/// The per-post selectors of a site, borrowed from a saved site or an unsaved form.
+/// Each is a selector chain (see `SelectorChain`), except `remove`, a comma-separated list.
+pub struct PostSelectors<'a> {
+    pub title: &'a str,
+    pub content: &'a str,
+    pub image: &'a str,
+    pub video: &'a str,
+    pub date: &'a str,
+    pub author: &'a str,
+    pub remove: &'a str,
+    /// Try the automatic main-content extractor before `content`. Without it the extractor
+    /// only runs when `content` is blank or matches nothing.
+    pub auto_content: bool,
+}
+
+/// What the selectors, backed by the page metadata, produced for one article page.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ExtractedPost {
+    pub title: String,
+    pub image: String,
+    pub video: String,
+    pub body: String,
+    /// What produced `body`; `None` when it is empty.
+    pub body_source: Option<BodySource>,
+    pub author: String,
+    pub published_at: Option<NaiveDateTime>,
+    /// Every image and video found, with `image`/`video` above among them.
+    pub media: Vec<ExtractedMedia>,
+    pub metadata: PageMetadata,
+    /// Whether there is a title, body or image to store, from the page or its metadata.
+    #[serde(skip)]
+    pub selected: bool,
+    /// Configured selectors that matched nothing (`title`, `content`, `image`, `video`,
+    /// `date`, `author`); their fields came from a fallback, if any.
+    pub missed_selectors: Vec<&'static str>,
+}
+
+impl<'a> PostSelectors<'a> {
+    pub fn from_site(site: &'a site::Model) -> Self {
+        Self {
+            title: site.path_title.as_deref().unwrap_or(""),
+            content: site.path_content.as_deref().unwrap_or(""),
+            image: site.path_image.as_deref().unwrap_or(""),
+            video: site.path_video.as_deref().unwrap_or(""),
+            date: site.path_date.as_deref().unwrap_or(""),
+            author: site.path_author.as_deref().unwrap_or(""),
+            remove: site.path_remove.as_deref().unwrap_or(""),
+            auto_content: site.auto_content == Some(true),
+        }
+    }
+}
+
+impl ExtractedPost {
+    /// Why the page can't be stored as a post, if it can't: neither the selectors nor the
+    /// metadata gave it a title, body or image. A missed selector is only a warning (see
+    /// `missed_selectors`), except that a configured `path_content` fails the post when the
+    /// extractor and the metadata come back empty too.
+    pub fn failure(&self) -> Option<&'static str> {
+        if !self.selected {
+            Some("no content extracted")
+        } else if self.content_missed() && self.body.trim().is_empty() {
+            Some("content selector matched nothing")
+        } else {
+            None
+        }
+    }
+
+    /// Whether a failure counts towards disabling the site. A missed `path_content` alone
+    /// doesn't: one video-only or differently laid out article says little about the site.
+    pub fn counts_against_site(&self) -> bool {
+        !self.selected
+    }
+
+    pub fn content_missed(&self) -> bool {
+        self.missed_selectors.contains(&"content")
+    }
+}
+
+/// Split a comma-separated `path_remove` value into individual selectors. Commas inside
+/// brackets, parentheses or quotes belong to a selector, so `:is(a, b)`, attribute values
+/// and XPath predicates survive.
+pub fn split_selectors(raw: &str) -> Vec<String> {
+    let mut selectors = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+
+    for c in raw.chars() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[' | '(') => depth += 1,
+            (None, ']' | ')') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                selectors.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    selectors.push(current);
+
+    selectors
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Strip the `path_remove` elements from a loaded page.
+pub async fn remove_elements(page: &dyn Fetcher, raw: &str) -> Result<(), AnyError> {
+    let selectors = split_selectors(raw);
+    if selectors.is_empty() {
+        return Ok(());
+    }
+
+    page.remove_elements(selectors).await
+}
+
+/// Run the post selectors against a loaded article page. Links are made absolute against `base_url`.
+/// A field whose selector is blank or matches nothing is taken from `metadata` instead; see
+/// [`extract_body`] for the body. Check [`ExtractedPost::failure`] before storing the result.
+/// The body comes back sanitized, with relative links made absolute.
+pub async fn extract_post(
+    page: &dyn Fetcher,
+    base_url: &str,
+    selectors: &PostSelectors<'_>,
+    metadata: PageMetadata,
+) -> ExtractedPost {
+    let title = SelectorChain::new(selectors.title)
+        .text(page)
+        .await
+        .unwrap_or_default();
+    let (found_body, content_missed) = extract_body(page, selectors).await;
+
+    let author = SelectorChain::new(selectors.author)
+        .text(page)
+        .await
+        .unwrap_or_default();
+    let selected_date = extract_date(page, &SelectorChain::new(selectors.date)).await;
+
+    // The first image/video matched is the post's main one, as before media lists existed.
+    let mut media = collect_media(
+        page,
+        base_url,
+        &SelectorChain::new(selectors.image),
+        &SelectorChain::new(selectors.video),
+    )
+    .await;
+    let selected_image = first_url_of(&media, MediaKind::Image);
+    let selected_video = first_url_of(&media, MediaKind::Video);
+
+    let missed_selectors = [
+        ("title", selectors.title, !title.trim().is_empty()),
+        ("content", selectors.content, !content_missed),
+        ("image", selectors.image, selected_image.is_some()),
+        ("video", selectors.video, selected_video.is_some()),
+        ("date", selectors.date, selected_date.is_some()),
+        ("author", selectors.author, !author.trim().is_empty()),
+    ]
+    .into_iter()
+    .filter(|(_, selector, found)| !selector.trim().is_empty() && !found)
+    .map(|(field, _, _)| field)
+    .collect();
+
+    let title = or_metadata(title, metadata.title.clone());
+    let author = or_metadata(author, metadata.author.clone());
+    let published_at =
+        selected_date.or_else(|| parse_date(metadata.published_time.as_deref()?, Utc::now()));
+    let image = selected_image
+        .or_else(|| metadata.image.clone())
+        .unwrap_or_default();
+    let video = selected_video
+        .or_else(|| metadata.video.clone())
+        .unwrap_or_default();
+    let (body, body_source) = match found_body {
+        Some((body, source)) => (Some(body), Some(source)),
+        None => metadata
+            .article_body_html()
+            .map(|body| (Some(body), Some(BodySource::Metadata)))
+            .unwrap_or_default(),
+    };
+    let body = sanitize_html(&body.unwrap_or_default(), Some(base_url));
+    let selected = !title.trim().is_empty() || !body.trim().is_empty() || !image.trim().is_empty();
+    include_primary(&mut media, MediaKind::Image, &image);
+    include_primary(&mut media, MediaKind::Video, &video);
+
+    ExtractedPost {
+        title,
+        image,
+        video,
+        body_source: body_source.filter(|_| !body.trim().is_empty()),
+        body,
+        author,
+        published_at,
+        media,
+        metadata,
+        selected,
+        missed_selectors,
+    }
+}
+
+/// The `path_content` element, falling back to the automatic extractor's pick when the
+/// selector is blank or matches nothing; sites with `auto_content` try the extractor first.
+/// Also says whether a configured selector was tried and matched nothing.
+async fn extract_body(
+    page: &dyn Fetcher,
+    selectors: &PostSelectors<'_>,
+) -> (Option<(String, BodySource)>, bool) {
+    let automatic = async || {
+        let html = page.get_element_html("html").await.ok()?;
+        extract_main_content(&html).map(|body| (body, BodySource::Extractor))
+    };
+
+    if selectors.auto_content
+        && let Some(body) = automatic().await
+    {
+        return (Some(body), false);
+    }
+
+    let configured = !selectors.content.trim().is_empty();
+    if configured
+        && let Some(body) = SelectorChain::new(selectors.content)
+            .html(page)
+            .await
+            .filter(|body| !body.trim().is_empty())
+    {
+        return (Some((body, BodySource::Selector)), false);
+    }
+
+    if selectors.auto_content {
+        return (None, configured);
+    }
+    (automatic().await, configured)
+}
+
+/// The first alternative that yields a parseable date. A bare selector reads the element's
+/// machine-readable `datetime`/`content` attribute, falling back to its text; a rule with
+/// steps parses whatever its steps produce.
+async fn extract_date(page: &dyn Fetcher, chain: &SelectorChain) -> Option<NaiveDateTime> {
+    let now = Utc::now();
+
+    for rule in chain.rules() {
+        if !rule.is_plain() {
+            let value = rule.value(page, &Source::Text).await;
+            if let Some(date) = value.and_then(|v| parse_date(&v, now)) {
+                return Some(date);
+            }
+            continue;
+        }
+
+        for attr in ["datetime", "content"] {
+            let value = page
+                .get_attr(&rule.selector, attr)
+                .await
+                .unwrap_or_default();
+            if let Some(date) = sanitize_attribute_value(value).and_then(|v| parse_date(&v, now)) {
+                return Some(date);
+            }
+        }
+
+        let text = page
+            .get_element_text(&rule.selector)
+            .await
+            .unwrap_or_default();
+        if let Some(date) = parse_date(&text, now) {
+            return Some(date);
+        }
+    }
+
+    None
+}
+
+fn or_metadata(value: String, fallback: Option<String>) -> String {
+    if value.trim().is_empty() {
+        fallback.unwrap_or_default()
+    } else {
+        value
+    }
+}
+
+fn sanitize_attribute_value(raw_value: String) -> Option<String> {
+    // Some browsers return literal "null" or "undefined" strings for missing attributes,
+    // so we normalize them to None to allow proper fallback checks.
+    let trimmed_value = raw_value.trim();
+
+    if trimmed_value.is_empty() {
+        return None;
+    }
+
+    match trimmed_value {
+        "null" | "undefined" => None,
+        _ => Some(trimmed_value.to_string()),
+    }
+}
@@ -0,0 +1,413 @@
+use scraper::node::Node;
+use scraper::{ElementRef, Html};
+
+/// A post body rendered for readers that don't display HTML.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReadableBody {
+    pub text: String,
+    pub markdown: String,
+}
+
+/// Render sanitized body HTML as Markdown and as plain text (the same layout without markup).
+pub fn readable_body(html: &str) -> ReadableBody {
+    if html.trim().is_empty() {
+        return ReadableBody::default();
+    }
+
+    let fragment = Html::parse_fragment(html);
+    let root = fragment.root_element();
+
+    ReadableBody {
+        text: Renderer::render(root, false),
+        markdown: Renderer::render(root, true),
+    }
+}
+
+enum ListKind {
+    Bullet,
+    Ordered(usize),
+}
+
+struct Renderer {
+    out: String,
+    markdown: bool,
+    lists: Vec<ListKind>,
+    preformatted: bool,
+}
+
+impl Renderer {
+    fn render(root: ElementRef, markdown: bool) -> String {
+        let mut renderer = Self {
+            out: String::new(),
+            markdown,
+            lists: Vec::new(),
+            preformatted: false,
+        };
+        renderer.children(root);
+        collapse_blank_lines(&renderer.out)
+    }
+
+    fn children(&mut self, element: ElementRef) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.text(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.element(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn element(&mut self, element: ElementRef) {
+        let name = element.value().name();
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.block_break();
+                if self.markdown {
+                    let level = name[1..].parse::<usize>().unwrap_or(1);
+                    self.out.push_str(&"#".repeat(level));
+                    self.out.push(' ');
+                }
+                self.children(element);
+                self.block_break();
+            }
+            "p" | "div" | "section" | "article" | "header" | "footer" | "figure" | "figcaption"
+            | "table" | "details" | "summary" | "dl" | "dd" | "dt" | "aside" => {
+                self.block_break();
+                self.children(element);
+                self.block_break();
+            }
+            "br" => {
+                self.out.push_str(if self.markdown { "  \n" } else { "\n" });
+            }
+            "hr" => {
+                self.block_break();
+                self.out.push_str("---");
+                self.block_break();
+            }
+            "strong" | "b" => self.wrap(element, "**"),
+            "em" | "i" => self.wrap(element, "*"),
+            "del" | "s" | "strike" => self.wrap(element, "~~"),
+            "code" if !self.preformatted => self.wrap(element, "`"),
+            "a" => self.link(element),
+            "img" => self.image(element),
+            "ul" | "ol" => {
+                if self.lists.is_empty() {
+                    self.block_break();
+                } else {
+                    self.line_break();
+                }
+                self.lists.push(if name == "ol" {
+                    ListKind::Ordered(0)
+                } else {
+                    ListKind::Bullet
+                });
+                self.children(element);
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.block_break();
+                }
+            }
+            "li" => self.list_item(element),
+            "blockquote" => self.blockquote(element),
+            "pre" => self.preformatted(element),
+            "tr" => {
+                self.line_break();
+                self.children(element);
+            }
+            "td" | "th" => {
+                if !self.out.ends_with('\n') && !self.out.is_empty() {
+                    self.out.push_str(" | ");
+                }
+                self.children(element);
+            }
+            "script" | "style" | "template" | "noscript" => {}
+            _ => self.children(element),
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.preformatted {
+            self.out.push_str(text);
+            return;
+        }
+
+        let mut words = text.split_whitespace().peekable();
+        if words.peek().is_none() {
+            if !text.is_empty() && !self.at_line_start() && !self.out.ends_with(' ') {
+                self.out.push(' ');
+            }
+            return;
+        }
+
+        if text.starts_with(char::is_whitespace)
+            && !self.at_line_start()
+            && !self.out.ends_with(' ')
+        {
+            self.out.push(' ');
+        }
+        let collapsed = words.collect::<Vec<_>>().join(" ");
+        if self.markdown {
+            self.out.push_str(&escape_markdown(&collapsed));
+        } else {
+            self.out.push_str(&collapsed);
+        }
+        if text.ends_with(char::is_whitespace) {
+            self.out.push(' ');
+        }
+    }
+
+    fn wrap(&mut self, element: ElementRef, marker: &str) {
+        if !self.markdown {
+            self.children(element);
+            return;
+        }
+
+        let inner = self.capture(element);
+        let trimmed = inner.trim();
+        if trimmed.is_empty() {
+            return;
+        }
+        if inner.starts_with(' ') && !self.at_line_start() {
+            self.out.push(' ');
+        }
+        self.out.push_str(marker);
+        self.out.push_str(trimmed);
+        self.out.push_str(marker);
+        if inner.ends_with(' ') {
+            self.out.push(' ');
+        }
+    }
+
+    fn link(&mut self, element: ElementRef) {
+        let href = element.value().attr("href").unwrap_or_default().trim();
+        if !self.markdown || href.is_empty() || href.starts_with('#') {
+            self.children(element);
+            return;
+        }
+
+        let label = self.capture(element);
+        let label = label.trim();
+        let label = if label.is_empty() { href } else { label };
+        self.out.push_str(&format!("[{label}]({href})"));
+    }
+
+    fn image(&mut self, element: ElementRef) {
+        let alt = element.value().attr("alt").unwrap_or_default().trim();
+        if !self.markdown {
+            self.out.push_str(alt);
+            return;
+        }
+
+        let Some(src) = element
+            .value()
+            .attr("src")
+            .filter(|src| !src.trim().is_empty())
+        else {
+            return;
+        };
+        self.out
+            .push_str(&format!("![{}]({})", escape_markdown(alt), src.trim()));
+    }
+
+    fn list_item(&mut self, element: ElementRef) {
+        self.line_break();
+        let depth = self.lists.len().saturating_sub(1);
+        self.out.push_str(&"  ".repeat(depth));
+
+        let marker = match self.lists.last_mut() {
+            Some(ListKind::Ordered(n)) => {
+                *n += 1;
+                format!("{n}. ")
+            }
+            _ => "- ".to_string(),
+        };
+        self.out.push_str(&marker);
+        self.children(element);
+        self.line_break();
+    }
+
+    fn blockquote(&mut self, element: ElementRef) {
+        let inner = collapse_blank_lines(&self.capture(element));
+        if inner.is_empty() {
+            return;
+        }
+
+        self.block_break();
+        let prefix = if self.markdown { "> " } else { "    " };
+        let quoted: Vec<String> = inner
+            .lines()
+            .map(|line| format!("{prefix}{line}").trim_end().to_string())
+            .collect();
+        self.out.push_str(&quoted.join("\n"));
+        self.block_break();
+    }
+
+    fn preformatted(&mut self, element: ElementRef) {
+        self.block_break();
+        let was_preformatted = self.preformatted;
+        self.preformatted = true;
+        let code = self.capture(element);
+        self.preformatted = was_preformatted;
+
+        if self.markdown {
+            self.out.push_str("```\n");
+            self.out.push_str(code.trim_matches('\n'));
+            self.out.push_str("\n```");
+        } else {
+            self.out.push_str(code.trim_matches('\n'));
+        }
+        self.block_break();
+    }
+
+    /// Render an element's children on their own and return the result.
+    fn capture(&mut self, element: ElementRef) -> String {
+        let outer = std::mem::take(&mut self.out);
+        self.children(element);
+        std::mem::replace(&mut self.out, outer)
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n')
+    }
+
+    fn line_break(&mut self) {
+        self.trim_trailing_spaces();
+        if !self.at_line_start() {
+            self.out.push('\n');
+        }
+    }
+
+    fn block_break(&mut self) {
+        self.trim_trailing_spaces();
+        if self.out.is_empty() || self.out.ends_with("\n\n") {
+            return;
+        }
+        self.out.push_str(if self.out.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        });
+    }
+
+    fn trim_trailing_spaces(&mut self) {
+        // Markdown hard breaks ("  \n") end in a newline, so they are left alone.
+        let trimmed = self.out.trim_end_matches([' ', '\t']).len();
+        self.out.truncate(trimmed);
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Trim every line's trailing spaces (keeping Markdown hard breaks), keep at most one blank
+/// line in a row and drop leading/trailing blank lines.
+fn collapse_blank_lines(text: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        let line = if line.ends_with("  ") && !line.trim().is_empty() {
+            line
+        } else {
+            line.trim_end()
+        };
+        if line.trim().is_empty() && lines.last().is_none_or(|last| last.trim().is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let joined = lines.join("\n");
+    joined.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(html: &str) -> (String, String) {
+        let body = readable_body(html);
+        (body.markdown, body.text)
+    }
+
+    #[test]
+    fn headings_and_paragraphs_become_blocks() {
+        let (markdown, text) = render("<h2>Title</h2><p>One\n   two</p><div>Three</div>");
+        assert_eq!(markdown, "## Title\n\nOne two\n\nThree");
+        assert_eq!(text, "Title\n\nOne two\n\nThree");
+    }
+
+    #[test]
+    fn inline_markup_and_links() {
+        let (markdown, text) = render(
+            r##"<p>A <b>bold</b> and <em>soft</em> <a href="https://x.org/a">link</a>, <a href="#top">anchor</a> <code>x</code></p>"##,
+        );
+        assert_eq!(
+            markdown,
+            "A **bold** and *soft* [link](https://x.org/a), anchor `x`"
+        );
+        assert_eq!(text, "A bold and soft link, anchor x");
+    }
+
+    #[test]
+    fn nested_and_ordered_lists() {
+        let (markdown, text) =
+            render("<ul><li>a<ol><li>one</li><li>two</li></ol></li><li>b</li></ul><p>after</p>");
+        assert_eq!(markdown, "- a\n  1. one\n  2. two\n- b\n\nafter");
+        assert_eq!(text, markdown);
+    }
+
+    #[test]
+    fn quotes_code_and_images() {
+        let (markdown, text) = render(
+            r#"<blockquote><p>Said</p><p>twice</p></blockquote><pre>let  x = 1;
+x += 1;</pre><img src="https://x.org/i.jpg" alt="An [image]">"#,
+        );
+        assert_eq!(
+            markdown,
+            "> Said\n>\n> twice\n\n```\nlet  x = 1;\nx += 1;\n```\n\n![An \\[image\\]](https://x.org/i.jpg)"
+        );
+        assert_eq!(
+            text,
+            "    Said\n\n    twice\n\nlet  x = 1;\nx += 1;\n\nAn [image]"
+        );
+    }
+
+    #[test]
+    fn markdown_characters_in_text_are_escaped() {
+        let (markdown, text) = render("<p>2*3 = snake_case [x]</p>");
+        assert_eq!(markdown, "2\\*3 = snake\\_case \\[x\\]");
+        assert_eq!(text, "2*3 = snake_case [x]");
+    }
+
+    #[test]
+    fn line_breaks_tables_and_hidden_content() {
+        let (markdown, text) = render(
+            "<p>a<br>b</p><table><tr><td>1</td><td>2</td></tr><tr><td>3</td><td>4</td></tr></table><script>x()</script>",
+        );
+        assert_eq!(markdown, "a  \nb\n\n1 | 2\n3 | 4");
+        assert_eq!(text, "a\nb\n\n1 | 2\n3 | 4");
+    }
+
+    #[test]
+    fn blank_input_is_empty() {
+        assert_eq!(readable_body(" "), ReadableBody::default());
+        assert_eq!(
+            readable_body("<p> </p><div></div>"),
+            ReadableBody::default()
+        );
+    }
+}
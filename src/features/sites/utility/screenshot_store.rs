@@ -0,0 +1,103 @@
+use crate::features::crawler::fetcher::ScreenshotFormat;
+use crate::utility::state::app_state;
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const SCREENSHOTS_DIRECTORY: &str = "screenshots";
+
+/// Write a post screenshot to `<MEDIA_DIRECTORY>/screenshots/<site_id>/<post_id>.<ext>`
+/// and return the path relative to the media directory, which is what the post stores.
+pub async fn save_screenshot(
+    site_id: i64,
+    post_id: i64,
+    format: ScreenshotFormat,
+    bytes: &[u8],
+) -> io::Result<String> {
+    let relative = format!(
+        "{SCREENSHOTS_DIRECTORY}/{site_id}/{post_id}.{}",
+        format.extension()
+    );
+    let path = media_path(&relative);
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    // Write beside the target and rename, so readers never see a half-written image.
+    let partial = path.with_extension("part");
+    tokio::fs::write(&partial, bytes).await?;
+    tokio::fs::rename(&partial, &path).await?;
+
+    Ok(relative)
+}
+
+/// Delete a post's screenshot. One that is already gone is not an error.
+pub async fn remove_screenshot(relative: &str) -> io::Result<()> {
+    ignore_missing(tokio::fs::remove_file(media_path(relative)).await)
+}
+
+/// Delete every screenshot of a site.
+pub async fn remove_site_screenshots(site_id: i64) -> io::Result<()> {
+    let directory = media_path(&format!("{SCREENSHOTS_DIRECTORY}/{site_id}"));
+    ignore_missing(tokio::fs::remove_dir_all(directory).await)
+}
+
+/// Delete screenshots no post points at any more (posts removed by the cleanup job or with
+/// their site, replaced formats, interrupted writes). Files younger than `grace` are kept,
+/// since an extraction may have written them before recording them.
+pub async fn remove_orphan_screenshots(
+    referenced: &HashSet<String>,
+    grace: Duration,
+) -> io::Result<u64> {
+    let root = media_path(SCREENSHOTS_DIRECTORY);
+    let Ok(mut sites) = tokio::fs::read_dir(&root).await else {
+        return Ok(0);
+    };
+
+    let cutoff = SystemTime::now() - grace;
+    let mut removed = 0;
+
+    while let Some(site) = sites.next_entry().await? {
+        if !site.file_type().await?.is_dir() {
+            continue;
+        }
+
+        let site_name = site.file_name();
+        let mut kept = 0;
+        let mut files = tokio::fs::read_dir(site.path()).await?;
+        while let Some(file) = files.next_entry().await? {
+            let relative = format!(
+                "{SCREENSHOTS_DIRECTORY}/{}/{}",
+                site_name.to_string_lossy(),
+                file.file_name().to_string_lossy()
+            );
+            let modified = file.metadata().await?.modified()?;
+            if referenced.contains(&relative) || modified >= cutoff {
+                kept += 1;
+                continue;
+            }
+
+            tokio::fs::remove_file(file.path()).await?;
+            removed += 1;
+        }
+
+        if kept == 0 {
+            ignore_missing(tokio::fs::remove_dir(site.path()).await)?;
+        }
+    }
+
+    Ok(removed)
+}
+
+fn ignore_missing(result: io::Result<()>) -> io::Result<()> {
+    match result {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+pub fn media_path(relative: &str) -> PathBuf {
+    Path::new(&app_state().config.media_directory).join(relative)
+}
@@ -0,0 +1,182 @@
+/// Marks FTS5 puts around matched terms. Private-use characters can't come from the
+/// crawled text, so they survive HTML escaping and are then swapped for `<mark>` tags.
+pub const MATCH_START: char = '\u{E000}';
+pub const MATCH_END: char = '\u{E001}';
+
+/// Turn a user's search into an FTS5 `MATCH` expression, or `None` if it has no terms.
+///
+/// Supported: bare terms (all must match), `"quoted phrases"`, `term*` prefixes and `OR`
+/// between two terms. Everything else is quoted, so FTS5 operators and punctuation in the
+/// input can never produce a syntax error.
+pub fn match_expression(input: &str) -> Option<String> {
+    let mut parts: Vec<String> = Vec::new();
+    let mut pending_or = false;
+
+    for token in tokenize(input) {
+        match token {
+            Token::Or => pending_or = !parts.is_empty(),
+            Token::Term { text, prefix } => {
+                if pending_or {
+                    parts.push("OR".to_string());
+                    pending_or = false;
+                }
+                let quoted = format!("\"{}\"", text.replace('"', "\"\""));
+                parts.push(if prefix { format!("{quoted}*") } else { quoted });
+            }
+        }
+    }
+
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// FTS5 output with its match marks as `<mark>` tags and everything else HTML-escaped.
+pub fn mark_matches(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            MATCH_START => out.push_str("<mark>"),
+            MATCH_END => out.push_str("</mark>"),
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+enum Token {
+    Or,
+    Term { text: String, prefix: bool },
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if c == '"' {
+            chars.next();
+            // An unclosed quote runs to the end of the input.
+            let phrase: String = chars.by_ref().take_while(|&c| c != '"').collect();
+            push_term(&mut tokens, &phrase, false);
+            continue;
+        }
+
+        let mut word = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() || c == '"' {
+                break;
+            }
+            word.push(c);
+            chars.next();
+        }
+
+        if word == "OR" {
+            tokens.push(Token::Or);
+        } else {
+            let prefix = word.ends_with('*');
+            push_term(&mut tokens, word.trim_end_matches('*'), prefix);
+        }
+    }
+
+    tokens
+}
+
+fn push_term(tokens: &mut Vec<Token>, text: &str, prefix: bool) {
+    let text = text.trim();
+    // Only letters and digits are indexed; a term of punctuation alone would match nothing.
+    if text.chars().any(char::is_alphanumeric) {
+        tokens.push(Token::Term {
+            text: text.to_string(),
+            prefix,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terms_are_quoted() {
+        assert_eq!(
+            match_expression("rust  crawler").as_deref(),
+            Some("\"rust\" \"crawler\"")
+        );
+        // FTS5 operators and syntax are taken literally.
+        assert_eq!(
+            match_expression("NOT title:x AND").as_deref(),
+            Some("\"NOT\" \"title:x\" \"AND\"")
+        );
+    }
+
+    #[test]
+    fn phrases_keep_their_words_together() {
+        assert_eq!(
+            match_expression("\"new  release\" notes").as_deref(),
+            Some("\"new  release\" \"notes\"")
+        );
+        // An unclosed quote runs to the end.
+        assert_eq!(
+            match_expression("say \"hello world").as_deref(),
+            Some("\"say\" \"hello world\"")
+        );
+    }
+
+    #[test]
+    fn quotes_inside_terms_are_escaped() {
+        assert_eq!(match_expression("it\"s").as_deref(), Some("\"it\" \"s\""));
+        assert_eq!(match_expression("a'b").as_deref(), Some("\"a'b\""));
+    }
+
+    #[test]
+    fn prefixes_stay_outside_the_quotes() {
+        assert_eq!(
+            match_expression("craw* rust**").as_deref(),
+            Some("\"craw\"* \"rust\"*")
+        );
+        assert_eq!(match_expression("*"), None);
+    }
+
+    #[test]
+    fn or_only_joins_two_terms() {
+        assert_eq!(
+            match_expression("rust OR go").as_deref(),
+            Some("\"rust\" OR \"go\"")
+        );
+        assert_eq!(match_expression("OR rust").as_deref(), Some("\"rust\""));
+        assert_eq!(match_expression("rust OR").as_deref(), Some("\"rust\""));
+        assert_eq!(
+            match_expression("rust OR OR go").as_deref(),
+            Some("\"rust\" OR \"go\"")
+        );
+        // Only the uppercase keyword is an operator.
+        assert_eq!(
+            match_expression("rust or go").as_deref(),
+            Some("\"rust\" \"or\" \"go\"")
+        );
+    }
+
+    #[test]
+    fn punctuation_alone_is_dropped() {
+        assert_eq!(match_expression("  "), None);
+        assert_eq!(match_expression("- ... \"\" \"!?\""), None);
+        assert_eq!(match_expression("-- خبر").as_deref(), Some("\"خبر\""));
+    }
+
+    #[test]
+    fn matches_are_marked_and_the_rest_escaped() {
+        let text = format!("a <b> & {MATCH_START}\"rust\"{MATCH_END}");
+        assert_eq!(
+            mark_matches(&text),
+            "a &lt;b&gt; &amp; <mark>&quot;rust&quot;</mark>"
+        );
+    }
+}
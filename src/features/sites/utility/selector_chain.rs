@@ -0,0 +1,362 @@
+use crate::features::crawler::AnyError;
+use crate::features::crawler::fetcher::{Fetcher, XPATH_PREFIX};
+use regex::Regex;
+use std::borrow::Cow;
+use std::time::Duration;
+use validator::ValidationError;
+
+/// Separates the alternatives of a selector field.
+const ALTERNATIVE_SEPARATOR: &str = "||";
+/// Separates a selector from its post-processing steps, and the steps from each other.
+const STEP_SEPARATOR: &str = " >> ";
+
+/// How long alternatives after the first get to appear once the first one timed out.
+const FALLBACK_WAIT: Duration = Duration::from_secs(1);
+
+/// The value a rule reads from the element it selects.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Text,
+    Html,
+    Attr(String),
+}
+
+#[derive(Clone, Debug)]
+enum Transform {
+    /// Keep the first capture group, or the whole match when the pattern has none.
+    Regex(Regex),
+    Trim,
+}
+
+/// One alternative of a selector field: a CSS or `xpath:` selector, optionally followed by
+/// steps, e.g. `meta[property="og:title"] >> attr:content >> regex:^(.*?) \| Site$ >> trim`.
+#[derive(Clone, Debug)]
+pub struct SelectorRule {
+    pub selector: String,
+    source: Option<Source>,
+    transforms: Vec<Transform>,
+}
+
+/// A selector field as configured on a site: alternatives separated by `||`, tried in order
+/// until one produces a non-blank value, so a layout change or A/B test falls through to
+/// the next known layout instead of leaving the field empty.
+#[derive(Clone, Debug, Default)]
+pub struct SelectorChain {
+    rules: Vec<SelectorRule>,
+}
+
+impl SelectorChain {
+    /// Parse a selector field, rejecting unknown steps, bad patterns and invalid XPath.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let rules = raw
+            .split(ALTERNATIVE_SEPARATOR)
+            .filter(|alternative| !alternative.trim().is_empty())
+            .map(SelectorRule::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { rules })
+    }
+
+    /// Parse a stored selector field, skipping alternatives that don't parse. Site forms are
+    /// validated, so this only drops rules saved before validation existed.
+    pub fn new(raw: &str) -> Self {
+        let rules = raw
+            .split(ALTERNATIVE_SEPARATOR)
+            .filter(|alternative| !alternative.trim().is_empty())
+            .filter_map(|alternative| SelectorRule::parse(alternative).ok())
+            .collect();
+
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn rules(&self) -> &[SelectorRule] {
+        &self.rules
+    }
+
+    /// The first non-blank value, reading the element text unless a rule says otherwise.
+    pub async fn text(&self, page: &dyn Fetcher) -> Option<String> {
+        self.first_value(page, &Source::Text).await
+    }
+
+    /// The first non-blank value, reading the element's outer HTML unless a rule says otherwise.
+    pub async fn html(&self, page: &dyn Fetcher) -> Option<String> {
+        self.first_value(page, &Source::Html).await
+    }
+
+    /// `attr` (or the rule's own `attr:` step) of every element the first matching
+    /// alternative selects, for fields that yield a list such as `path_link`.
+    pub async fn attrs(&self, page: &dyn Fetcher, attr: &str) -> Result<Vec<String>, AnyError> {
+        let mut last_error = None;
+
+        for rule in &self.rules {
+            match rule.attrs(page, attr).await {
+                Ok(values) if !values.is_empty() => return Ok(values),
+                Ok(_) => {}
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        match last_error {
+            Some(e) => Err(e),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Wait until any alternative matches. The first gets the whole `timeout` for the page to
+    /// render; the others are only checked briefly after that.
+    pub async fn wait_for_any(
+        &self,
+        page: &dyn Fetcher,
+        timeout: Duration,
+    ) -> Result<(), AnyError> {
+        let mut first_error = None;
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            let wait = if index == 0 { timeout } else { FALLBACK_WAIT };
+            match page.wait_for_selector(&rule.selector, wait).await {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        Err(first_error.unwrap_or_else(|| "No selector configured".into()))
+    }
+
+    async fn first_value(&self, page: &dyn Fetcher, default: &Source) -> Option<String> {
+        for rule in &self.rules {
+            if let Some(value) = rule.value(page, default).await {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+impl SelectorRule {
+    fn parse(raw: &str) -> Result<Self, String> {
+        let mut parts = raw.split(STEP_SEPARATOR);
+        let selector = parts.next().unwrap_or_default().trim().to_string();
+        if selector.is_empty() {
+            return Err(format!("Missing selector in `{}`", raw.trim()));
+        }
+        if let Some(xpath) = selector.strip_prefix(XPATH_PREFIX) {
+            sxd_xpath::Factory::new()
+                .build(xpath)
+                .map_err(|e| format!("Invalid XPath `{xpath}`: {e}"))?
+                .ok_or_else(|| format!("Invalid XPath `{xpath}`: empty expression"))?;
+        }
+
+        let mut source = None;
+        let mut transforms = Vec::new();
+
+        for step in parts.map(str::trim).filter(|step| !step.is_empty()) {
+            let read = match step {
+                "text" => Some(Source::Text),
+                "html" => Some(Source::Html),
+                "trim" => {
+                    transforms.push(Transform::Trim);
+                    None
+                }
+                _ => {
+                    if let Some(name) = step.strip_prefix("attr:") {
+                        let name = name.trim();
+                        if name.is_empty() {
+                            return Err("`attr:` needs an attribute name".to_string());
+                        }
+                        Some(Source::Attr(name.to_string()))
+                    } else if let Some(pattern) = step.strip_prefix("regex:") {
+                        let regex = Regex::new(pattern)
+                            .map_err(|e| format!("Invalid pattern `{pattern}`: {e}"))?;
+                        transforms.push(Transform::Regex(regex));
+                        None
+                    } else {
+                        return Err(format!(
+                            "Unknown step `{step}` (expected text, html, attr:NAME, regex:PATTERN or trim)"
+                        ));
+                    }
+                }
+            };
+
+            if let Some(read) = read {
+                if source.is_some() || !transforms.is_empty() {
+                    return Err(format!(
+                        "`{step}` must be the first step and appear only once"
+                    ));
+                }
+                source = Some(read);
+            }
+        }
+
+        Ok(Self {
+            selector,
+            source,
+            transforms,
+        })
+    }
+
+    /// Whether the rule is a bare selector, leaving it to the field how to read the element.
+    pub fn is_plain(&self) -> bool {
+        self.source.is_none() && self.transforms.is_empty()
+    }
+
+    /// The attribute named by an `attr:` step.
+    pub fn attr(&self) -> Option<&str> {
+        match &self.source {
+            Some(Source::Attr(name)) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Read the first selected element and run the steps on it. `None` when nothing matched
+    /// or the result is blank.
+    pub async fn value(&self, page: &dyn Fetcher, default: &Source) -> Option<String> {
+        let raw = match self.source.as_ref().unwrap_or(default) {
+            Source::Text => page.get_element_text(&self.selector).await,
+            Source::Html => page.get_element_html(&self.selector).await,
+            Source::Attr(name) => page.get_attr(&self.selector, name).await,
+        }
+        .ok()?;
+
+        self.apply(raw)
+    }
+
+    /// An attribute of every selected element, after the steps. Blank results are dropped.
+    pub async fn attrs(&self, page: &dyn Fetcher, default: &str) -> Result<Vec<String>, AnyError> {
+        let attr = self.attr().unwrap_or(default);
+        let values = page.get_attrs(&self.selector, attr).await?;
+
+        Ok(values
+            .into_iter()
+            .filter_map(|value| self.apply(value))
+            .collect())
+    }
+
+    fn apply(&self, raw: String) -> Option<String> {
+        let mut value = raw;
+        for transform in &self.transforms {
+            value = match transform {
+                Transform::Regex(regex) => {
+                    let captures = regex.captures(&value)?;
+                    captures
+                        .get(1)
+                        .or_else(|| captures.get(0))?
+                        .as_str()
+                        .to_string()
+                }
+                Transform::Trim => value.trim().to_string(),
+            };
+        }
+
+        (!value.trim().is_empty()).then_some(value)
+    }
+}
+
+/// Form validator for selector fields. A blank value clears the field.
+pub fn validate_selector_chain(raw: &str) -> Result<(), ValidationError> {
+    SelectorChain::parse(raw)
+        .map(|_| ())
+        .map_err(|message| ValidationError::new("selector").with_message(Cow::Owned(message)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(raw: &str) -> SelectorRule {
+        SelectorRule::parse(raw).unwrap()
+    }
+
+    #[test]
+    fn alternatives_are_split_on_double_bars_in_order() {
+        let chain = SelectorChain::parse("h1.title || xpath://h2[1] ||  || .headline").unwrap();
+        let selectors: Vec<&str> = chain.rules().iter().map(|r| r.selector.as_str()).collect();
+        assert_eq!(selectors, ["h1.title", "xpath://h2[1]", ".headline"]);
+    }
+
+    #[test]
+    fn blank_field_has_no_rules() {
+        assert!(SelectorChain::parse("").unwrap().is_empty());
+        assert!(SelectorChain::parse("  ||  ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn steps_follow_the_selector() {
+        let parsed =
+            rule("meta[property=\"og:title\"] >> attr:content >> regex:^(.*?) \\| Site$ >> trim");
+        assert_eq!(parsed.selector, "meta[property=\"og:title\"]");
+        assert_eq!(parsed.attr(), Some("content"));
+        assert_eq!(parsed.transforms.len(), 2);
+        assert!(!parsed.is_plain());
+    }
+
+    #[test]
+    fn css_child_combinator_is_not_a_step() {
+        let parsed = rule("article > p");
+        assert_eq!(parsed.selector, "article > p");
+        assert!(parsed.is_plain());
+    }
+
+    #[test]
+    fn read_step_must_come_first_and_once() {
+        assert!(SelectorRule::parse("a >> trim >> attr:href").is_err());
+        assert!(SelectorRule::parse("a >> text >> html").is_err());
+        assert!(SelectorRule::parse("a >> html >> trim").is_ok());
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        assert!(SelectorRule::parse(" >> trim").is_err());
+        assert!(SelectorRule::parse("a >> attr:").is_err());
+        assert!(SelectorRule::parse("a >> regex:(").is_err());
+        assert!(SelectorRule::parse("a >> upper").is_err());
+        assert!(SelectorRule::parse("xpath://div[").is_err());
+    }
+
+    #[test]
+    fn new_skips_rules_that_do_not_parse() {
+        let chain = SelectorChain::new("a >> upper || h1");
+        let selectors: Vec<&str> = chain.rules().iter().map(|r| r.selector.as_str()).collect();
+        assert_eq!(selectors, ["h1"]);
+        assert!(SelectorChain::parse("a >> upper || h1").is_err());
+    }
+
+    #[test]
+    fn regex_keeps_first_group_or_whole_match() {
+        let grouped = rule("title >> regex:^(.*?) \\| Site$");
+        assert_eq!(
+            grouped.apply("Big news | Site".to_string()).as_deref(),
+            Some("Big news")
+        );
+
+        let whole = rule("span >> regex:\\d+");
+        assert_eq!(
+            whole.apply("12 comments".to_string()).as_deref(),
+            Some("12")
+        );
+        assert_eq!(whole.apply("no comments".to_string()), None);
+    }
+
+    #[test]
+    fn steps_run_in_order() {
+        let trim_first = rule("p >> trim >> regex:^x(.*)$");
+        assert_eq!(
+            trim_first.apply("  xyz ".to_string()).as_deref(),
+            Some("yz")
+        );
+
+        let regex_first = rule("p >> regex:^x(.*)$ >> trim");
+        assert_eq!(regex_first.apply("  xyz ".to_string()), None);
+    }
+
+    #[test]
+    fn blank_values_count_as_missing() {
+        assert_eq!(rule("p").apply("   ".to_string()), None);
+        assert_eq!(rule("p").apply(" a ".to_string()).as_deref(), Some(" a "));
+    }
+}
@@ -1,21 +1,64 @@
-use once_cell::sync::Lazy;
-use std::collections::HashMap;
-use tokio::sync::Mutex;
+use crate::core::logger::targets;
+use crate::features::sites::model::site::Model;
+use crate::features::sites::repository::site_repository::SiteRepository;
+use crate::utility::state::app_state;
+use tracing::{error, warn};
 
-// Global error counter for sites
-static SITE_ERROR_COUNTER: Lazy<Mutex<HashMap<i64, u32>>> =
-    Lazy::new(|| Mutex::new(HashMap::new()));
-
-/// Register one error for a site and return the new error count.
-pub async fn register_site_error(site_id: i64) -> u32 {
-    let mut map = SITE_ERROR_COUNTER.lock().await;
-    let counter = map.entry(site_id).or_insert(0);
-    *counter += 1;
-    *counter
+/// Register one error for a site and return the new consecutive error count. If the counter
+/// can't be written, the count the site was loaded with plus one still lets it be disabled.
+pub async fn register_site_error(site: &Model, reason: &str) -> u32 {
+    match SiteRepository::register_error(site.id, reason).await {
+        Ok(count) => count.max(0) as u32,
+        Err(e) => {
+            error!(
+                target: targets::CRAWLER_SITE,
+                site_id = site.id,
+                error = %e,
+                "Failed to register site error"
+            );
+            site.error_count.max(0) as u32 + 1
+        }
+    }
 }
 
 /// Reset error count for a site (e.g. on successful run).
 pub async fn reset_site_error(site_id: i64) {
-    let mut map = SITE_ERROR_COUNTER.lock().await;
-    map.remove(&site_id);
+    if let Err(e) = SiteRepository::reset_errors(site_id).await {
+        error!(
+            target: targets::CRAWLER_SITE,
+            site_id,
+            error = %e,
+            "Failed to reset site errors"
+        );
+    }
+}
+
+/// Count a failure against the site and disable it once it reaches its error threshold.
+pub async fn block(site: &Model, reason: &str) {
+    let count = register_site_error(site, reason).await;
+    let threshold = site
+        .error_threshold
+        .filter(|value| *value > 0)
+        .map(|value| value as u32)
+        .unwrap_or(app_state().config.site_error_threshold);
+
+    if count >= threshold {
+        warn!(
+            target: targets::CRAWLER_SITE,
+            site_id = site.id,
+            error_count = count,
+            threshold,
+            "Site reached error threshold, disabling"
+        );
+
+        let disabled_reason = format!("{count} consecutive errors, last: {reason}");
+        if let Err(e) = SiteRepository::disable(site.id, &disabled_reason).await {
+            error!(
+                target: targets::CRAWLER_SITE,
+                site_id = site.id,
+                error = %e,
+                "Failed to disable site"
+            );
+        }
+    }
 }
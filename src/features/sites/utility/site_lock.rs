@@ -0,0 +1,40 @@
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+static HELD: Lazy<Mutex<HashSet<(SiteWork, i64)>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// What a lock holder is doing with the site. Crawling and extracting don't get in each
+/// other's way, so each has its own lock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SiteWork {
+    /// Reading the listing, sitemap or feed for new post URLs.
+    Crawl,
+    /// Extracting the content of the site's pending posts.
+    Extract,
+}
+
+/// In-process lock on one kind of work for one site, shared by the scheduled jobs and the
+/// on-demand crawl so the same site is never crawled, or its posts extracted, twice at once.
+/// Released on drop.
+#[derive(Debug)]
+pub struct SiteLock {
+    work: SiteWork,
+    site_id: i64,
+}
+
+impl SiteLock {
+    /// `None` while someone else holds it.
+    pub fn try_acquire(work: SiteWork, site_id: i64) -> Option<Self> {
+        let mut held = HELD.lock().unwrap_or_else(|e| e.into_inner());
+        // Built lazily: a guard dropped on contention would release the holder's lock.
+        held.insert((work, site_id)).then(|| Self { work, site_id })
+    }
+}
+
+impl Drop for SiteLock {
+    fn drop(&mut self) {
+        let mut held = HELD.lock().unwrap_or_else(|e| e.into_inner());
+        held.remove(&(self.work, self.site_id));
+    }
+}
@@ -1,5 +1,7 @@
-use crate::features::sites::model::posts::PostStatus;
+use crate::features::sites::model::posts::{BodySource, PostStatus};
+use chrono::NaiveDateTime;
 use serde::Deserialize;
+use serde_json::Value;
 use validator::Validate;
 
 #[derive(Debug, Deserialize, Validate)]
@@ -9,6 +11,14 @@ pub struct PostForm {
     pub image: Option<String>,
     pub video: Option<String>,
     pub status: PostStatus,
+    pub author: Option<String>,
+    pub published_at: Option<NaiveDateTime>,
+    /// Page metadata harvested by the crawler; not settable through the API.
+    #[serde(skip)]
+    pub metadata: Option<Value>,
+    /// What produced `body` when the crawler extracted it.
+    #[serde(skip)]
+    pub body_source: Option<BodySource>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
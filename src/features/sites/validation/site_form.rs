@@ -1,3 +1,7 @@
+use crate::core::schedule::validate_cron;
+use crate::features::sites::model::site::{FetchMode, SourceType};
+use crate::features::sites::utility::listing_pages::validate_page_url_template;
+use crate::features::sites::utility::selector_chain::validate_selector_chain;
 use serde::Deserialize;
 use validator::Validate;
 
@@ -6,16 +10,48 @@ pub struct SiteForm {
     pub name: String,
     pub url: String,
     pub url_list: String,
+    pub source_type: Option<SourceType>,
+    #[validate(range(min = 1))]
+    pub max_age_hours: Option<i32>,
 
+    #[validate(custom(function = "validate_selector_chain"))]
     pub path_link: Option<String>,
+    #[validate(custom(function = "validate_selector_chain"))]
     pub path_title: Option<String>,
+    #[validate(custom(function = "validate_selector_chain"))]
     pub path_content: Option<String>,
+    #[validate(custom(function = "validate_selector_chain"))]
     pub path_image: Option<String>,
+    #[validate(custom(function = "validate_selector_chain"))]
     pub path_video: Option<String>,
     pub path_remove: Option<String>,
+    pub auto_content: Option<bool>,
+    #[validate(custom(function = "validate_selector_chain"))]
+    pub path_date: Option<String>,
+    #[validate(custom(function = "validate_selector_chain"))]
+    pub path_author: Option<String>,
+    #[validate(custom(function = "validate_selector_chain"))]
+    pub path_next_page: Option<String>,
+    #[validate(custom(function = "validate_page_url_template"))]
+    pub page_url_template: Option<String>,
+    #[validate(range(min = 1, max = 100))]
+    pub max_pages: Option<i32>,
 
     pub screenshot: Option<bool>,
+    pub mirror_media: Option<bool>,
+    pub mirror_videos: Option<bool>,
+    pub fetch_mode: Option<FetchMode>,
+    #[validate(range(min = 1))]
+    pub crawl_interval_minutes: Option<i32>,
+    #[validate(custom(function = "validate_cron"))]
+    pub crawl_cron: Option<String>,
+    #[validate(range(min = 1))]
+    pub recheck_window_hours: Option<i32>,
+    #[validate(range(min = 1))]
+    pub recheck_interval_minutes: Option<i32>,
     pub status: Option<bool>,
+    #[validate(range(min = 1))]
+    pub error_threshold: Option<i32>,
 
     pub user_id: Option<i64>,
     pub api_key_id: Option<i64>,
@@ -1,6 +1,9 @@
 use crate::core::response::{json_error, json_success};
+use crate::features::sites::controller::feed_controller::ALL_POSTS_FEED;
+use crate::features::sites::utility::feed_writer::{FeedFormat, feed_url};
 use crate::features::users::repository::repo_api_key::RepositoryApiKey;
 use crate::features::users::repository::users_repo::UsersRepository;
+use crate::features::users::service::api_key_user::ApiKey;
 use crate::features::users::service::auth_user::AuthUser;
 use crate::features::users::utility::hash_key::hash_key;
 use crate::features::users::utility::key_generator::key_generator;
@@ -48,20 +51,38 @@ impl ApiKeyController {
         let raw_key = key_generator();
         let key_hash = hash_key(&raw_key);
 
+        let feed_token = key_generator();
+
         form.user_id = Some(target_user_id);
         form.key = Some(raw_key);
         form.key_hash = Some(key_hash);
+        form.feed_token_hash = Some(hash_key(&feed_token));
 
         match RepositoryApiKey::create(form).await {
             Ok(api_key) => {
                 let mut response = serde_json::json!(api_key.0);
                 response["key"] = serde_json::json!(api_key.1);
+                response["feed_token"] = serde_json::json!(feed_token);
+                response["feeds"] = Self::feed_urls(&feed_token);
                 json_success(response)
             }
             Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
         }
     }
 
+    /// Issue a new feed token for the calling key; feed URLs with the old one stop working.
+    pub async fn rotate_feed_token(ApiKey(api_key): ApiKey) -> Response {
+        let feed_token = key_generator();
+
+        match RepositoryApiKey::set_feed_token(api_key.id, hash_key(&feed_token)).await {
+            Ok(_) => json_success(serde_json::json!({
+                "feed_token": feed_token,
+                "feeds": Self::feed_urls(&feed_token),
+            })),
+            Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+
     pub async fn update(Path(api_key): Path<String>, Form(form): Form<ApiKeyForm>) -> Response {
         if let Err(e) = form.validate() {
             return json_error(StatusCode::BAD_REQUEST, e.to_string());
@@ -84,4 +105,14 @@ impl ApiKeyController {
             Err(message) => json_error(StatusCode::BAD_REQUEST, message.to_string()),
         }
     }
+
+    /// The key's aggregated feed in every format.
+    fn feed_urls(feed_token: &str) -> serde_json::Value {
+        let path = format!("feeds/{ALL_POSTS_FEED}");
+        serde_json::json!({
+            "rss": feed_url(&path, FeedFormat::Rss, feed_token),
+            "atom": feed_url(&path, FeedFormat::Atom, feed_token),
+            "json": feed_url(&path, FeedFormat::Json, feed_token),
+        })
+    }
 }
@@ -11,6 +11,9 @@ pub struct Model {
     pub user_id: i64,
     #[sea_orm(unique)]
     pub key_hash: String,
+    /// Hash of the token that authenticates this key's feed URLs, which can't carry a header.
+    #[sea_orm(unique)]
+    pub feed_token_hash: Option<String>,
     pub status: bool,
     pub created_at: DateTime,
 }
@@ -26,6 +26,7 @@ impl RepositoryApiKey {
 
         let api_key = api_key::ActiveModel {
             key_hash: Set(data.key_hash.expect("key_hash is required")),
+            feed_token_hash: Set(data.feed_token_hash),
             user_id: Set(data.user_id.expect("user_id is required")),
             status: Set(data.status.unwrap_or(true)),
             ..Default::default()
@@ -70,6 +71,30 @@ impl RepositoryApiKey {
             .flatten()
     }
 
+    pub async fn get_by_feed_token(raw_token: &str) -> Option<Model> {
+        let token = hash_key(raw_token);
+        let state = app_state();
+
+        Entity::find()
+            .filter(Column::FeedTokenHash.eq(token))
+            .one(&state._db)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    pub async fn set_feed_token(key_id: i64, token_hash: String) -> Result<Model, DbErr> {
+        let state = app_state();
+
+        let api_key = api_key::ActiveModel {
+            id: Set(key_id),
+            feed_token_hash: Set(Some(token_hash)),
+            ..Default::default()
+        };
+
+        api_key.update(&state._db).await
+    }
+
     pub async fn get_by_id(key_id: i64) -> Option<Model> {
         let state = app_state();
 
@@ -3,7 +3,7 @@ use crate::features::users::controller::user_controller::UserController;
 use crate::middleware::auth::auth;
 use crate::middleware::is_admin::is_admin;
 use crate::utility::state::app_state;
-use axum::routing::{delete, get, put};
+use axum::routing::{delete, get, post, put};
 use axum::{Router, middleware};
 
 pub fn users_route() -> (&'static str, Router) {
@@ -51,6 +51,7 @@ pub fn api_key_route() -> (&'static str, Router) {
         "api/v1/api-keys",
         Router::new()
             .merge(admin_router)
+            .route("/feed-token", post(ApiKeyController::rotate_feed_token))
             .route_layer(middleware_auth),
     )
 }
@@ -2,6 +2,8 @@ use crate::core::state::APP_STATE;
 use crate::features::users::model::user::Model;
 use crate::features::users::model::{api_key, user};
 use crate::features::users::repository::repo_api_key::RepositoryApiKey;
+use crate::features::users::utility::stream_token::StreamToken;
+use chrono::Utc;
 use sea_orm::ModelTrait;
 
 pub struct ServiceApiKey;
@@ -31,4 +33,35 @@ impl ServiceApiKey {
 
         None
     }
+
+    /// The user and key behind a feed token. Disabled keys get no feed.
+    pub async fn feed_auth(raw_token: &str) -> Option<(Model, api_key::Model)> {
+        let api_key = RepositoryApiKey::get_by_feed_token(raw_token)
+            .await
+            .filter(|api_key| api_key.status)?;
+
+        let _state = APP_STATE.get().expect("App state not initialized");
+
+        if let Ok(Some(user)) = api_key.find_related(user::Entity).one(&_state._db).await {
+            return Some((user, api_key));
+        }
+
+        None
+    }
+
+    /// The user and key behind a post stream token. Disabled keys get no stream.
+    pub async fn stream_auth(raw_token: &str) -> Option<(Model, api_key::Model)> {
+        let token = StreamToken::parse(raw_token)?;
+        let api_key = RepositoryApiKey::get_by_id(token.api_key_id)
+            .await
+            .filter(|api_key| api_key.status && token.is_valid_for(api_key, Utc::now()))?;
+
+        let _state = APP_STATE.get().expect("App state not initialized");
+
+        if let Ok(Some(user)) = api_key.find_related(user::Entity).one(&_state._db).await {
+            return Some((user, api_key));
+        }
+
+        None
+    }
 }
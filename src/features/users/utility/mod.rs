@@ -1,2 +1,3 @@
 pub mod hash_key;
 pub mod key_generator;
+pub mod stream_token;
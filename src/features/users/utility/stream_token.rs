@@ -0,0 +1,148 @@
+use crate::features::users::model::api_key;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// How long a stream token can open (and reopen) a connection.
+const STREAM_TOKEN_TTL_MINUTES: i64 = 10;
+
+/// Short-lived credential for the post stream, whose browser clients (`EventSource`,
+/// `WebSocket`) can't send `X-API-Key`: `<api key id>.<expiry, unix seconds>.<hex HMAC>`.
+/// The HMAC is keyed with the API key's stored hash, so only the server can issue one and
+/// deleting the key voids its tokens.
+pub struct StreamToken {
+    pub api_key_id: i64,
+    expires_at: i64,
+    signature: Vec<u8>,
+}
+
+impl StreamToken {
+    /// A new token for `api_key` and when it expires.
+    pub fn issue(api_key: &api_key::Model, now: DateTime<Utc>) -> (String, DateTime<Utc>) {
+        let expires_at = now + Duration::minutes(STREAM_TOKEN_TTL_MINUTES);
+        let signature = Self::mac(api_key, expires_at.timestamp())
+            .finalize()
+            .into_bytes();
+        let token = format!(
+            "{}.{}.{}",
+            api_key.id,
+            expires_at.timestamp(),
+            hex::encode(signature)
+        );
+
+        (token, expires_at)
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut parts = raw.trim().splitn(3, '.');
+        Some(Self {
+            api_key_id: parts.next()?.parse().ok()?,
+            expires_at: parts.next()?.parse().ok()?,
+            signature: hex::decode(parts.next()?).ok()?,
+        })
+    }
+
+    /// Whether the token was issued for `api_key` and hasn't expired.
+    pub fn is_valid_for(&self, api_key: &api_key::Model, now: DateTime<Utc>) -> bool {
+        api_key.id == self.api_key_id
+            && now.timestamp() < self.expires_at
+            && Self::mac(api_key, self.expires_at)
+                .verify_slice(&self.signature)
+                .is_ok()
+    }
+
+    fn mac(api_key: &api_key::Model, expires_at: i64) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(api_key.key_hash.as_bytes())
+            .expect("HMAC accepts any key length");
+        mac.update(format!("stream.{}.{expires_at}", api_key.id).as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn key(id: i64, key_hash: &str) -> api_key::Model {
+        api_key::Model {
+            id,
+            user_id: 1,
+            key_hash: key_hash.to_string(),
+            feed_token_hash: None,
+            status: true,
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn issued_tokens_are_valid_until_they_expire() {
+        let api_key = key(7, "hash");
+        let (raw, expires_at) = StreamToken::issue(&api_key, now());
+        assert_eq!(expires_at, now() + Duration::minutes(10));
+        assert!(raw.starts_with(&format!("7.{}.", expires_at.timestamp())));
+
+        let token = StreamToken::parse(&raw).unwrap();
+        assert_eq!(token.api_key_id, 7);
+        assert!(token.is_valid_for(&api_key, now()));
+        assert!(token.is_valid_for(&api_key, expires_at - Duration::seconds(1)));
+        assert!(!token.is_valid_for(&api_key, expires_at));
+    }
+
+    #[test]
+    fn tokens_are_bound_to_their_key() {
+        let (raw, _) = StreamToken::issue(&key(7, "hash"), now());
+        let token = StreamToken::parse(&raw).unwrap();
+
+        // A rotated key (new hash) and another key both reject it.
+        assert!(!token.is_valid_for(&key(7, "rotated"), now()));
+        assert!(!token.is_valid_for(&key(8, "hash"), now()));
+    }
+
+    #[test]
+    fn tampered_tokens_are_rejected() {
+        let api_key = key(7, "hash");
+        let (raw, expires_at) = StreamToken::issue(&api_key, now());
+        let signature = raw.rsplit('.').next().unwrap();
+
+        // A later expiry with the original signature.
+        let extended = format!("7.{}.{signature}", expires_at.timestamp() + 3600);
+        let extended = StreamToken::parse(&extended).unwrap();
+        assert!(!extended.is_valid_for(&api_key, now()));
+
+        // Another key id with the original signature.
+        let moved = format!("8.{}.{signature}", expires_at.timestamp());
+        assert!(
+            !StreamToken::parse(&moved)
+                .unwrap()
+                .is_valid_for(&key(8, "hash"), now())
+        );
+
+        // A flipped signature byte, and a truncated one.
+        let mut flipped = raw.clone();
+        let last = flipped.pop().unwrap();
+        flipped.push(if last == '0' { '1' } else { '0' });
+        assert!(
+            !StreamToken::parse(&flipped)
+                .unwrap()
+                .is_valid_for(&api_key, now())
+        );
+        let truncated = &raw[..raw.len() - 2];
+        assert!(
+            !StreamToken::parse(truncated)
+                .unwrap()
+                .is_valid_for(&api_key, now())
+        );
+    }
+
+    #[test]
+    fn malformed_tokens_do_not_parse() {
+        for raw in ["", "7", "7.123", "x.123.ab", "7.x.ab", "7.123.not-hex"] {
+            assert!(StreamToken::parse(raw).is_none(), "{raw}");
+        }
+    }
+}
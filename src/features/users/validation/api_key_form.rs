@@ -6,5 +6,6 @@ pub struct ApiKeyForm {
     pub user_id: Option<i64>,
     pub key: Option<String>,
     pub key_hash: Option<String>,
+    pub feed_token_hash: Option<String>,
     pub status: Option<bool>,
 }
@@ -0,0 +1,219 @@
+use crate::core::dto::pagination::PaginationParams;
+use crate::core::response::{json_error, json_success};
+use crate::features::sites::controller::site_controller::SiteController;
+use crate::features::users::model::user;
+use crate::features::users::service::api_key_user::ApiKey;
+use crate::features::users::service::auth_user::AuthUser;
+use crate::features::users::utility::key_generator::key_generator;
+use crate::features::webhooks::model::webhook::Model;
+use crate::features::webhooks::repository::webhook_delivery_repository::WebhookDeliveryRepository;
+use crate::features::webhooks::repository::webhook_repository::WebhookRepository;
+use crate::features::webhooks::utility::public_address::resolve_public;
+use crate::features::webhooks::validation::webhook_form::{WebhookForm, allow_private_networks};
+use axum::Form;
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::response::Response;
+use reqwest::Url;
+use validator::Validate;
+
+pub struct WebhookController;
+
+impl WebhookController {
+    // GET /webhooks
+    pub async fn list(Query(p): Query<PaginationParams>, AuthUser(user): AuthUser) -> Response {
+        match WebhookRepository::list(&user, p.page(), p.per_page()).await {
+            Ok(items) => json_success(items),
+            Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+
+    // POST /webhooks
+    pub async fn create(
+        ApiKey(api_key): ApiKey,
+        AuthUser(user): AuthUser,
+        Form(form): Form<WebhookForm>,
+    ) -> Response {
+        if let Err(e) = form.validate() {
+            return json_error(StatusCode::BAD_REQUEST, e.to_string());
+        }
+
+        if let Err(resp) = Self::check_destination(&form.url).await {
+            return resp;
+        }
+
+        if let Some(site_id) = form.site_id
+            && let Err(resp) = SiteController::check_access(site_id, &user).await
+        {
+            return resp;
+        }
+
+        let secret = key_generator();
+        match WebhookRepository::create(form, user.id, api_key.id, secret.clone()).await {
+            Ok(webhook) => Self::with_secret(&webhook, &secret),
+            Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+
+    // GET /webhooks/:id
+    pub async fn show(AuthUser(user): AuthUser, Path(webhook_id): Path<i64>) -> Response {
+        match Self::check_access(webhook_id, &user).await {
+            Ok(webhook) => json_success(webhook),
+            Err(resp) => resp,
+        }
+    }
+
+    // PUT /webhooks/:id
+    pub async fn update(
+        AuthUser(user): AuthUser,
+        Path(webhook_id): Path<i64>,
+        Form(form): Form<WebhookForm>,
+    ) -> Response {
+        if let Err(resp) = Self::check_access(webhook_id, &user).await {
+            return resp;
+        }
+
+        if let Err(e) = form.validate() {
+            return json_error(StatusCode::BAD_REQUEST, e.to_string());
+        }
+
+        if let Err(resp) = Self::check_destination(&form.url).await {
+            return resp;
+        }
+
+        if let Some(site_id) = form.site_id
+            && let Err(resp) = SiteController::check_access(site_id, &user).await
+        {
+            return resp;
+        }
+
+        match WebhookRepository::update(webhook_id, form).await {
+            Ok(Some(webhook)) => json_success(webhook),
+            Ok(None) => json_error(StatusCode::NOT_FOUND, "Webhook not found".to_string()),
+            Err(e) => json_error(StatusCode::BAD_REQUEST, e.to_string()),
+        }
+    }
+
+    // DELETE /webhooks/:id
+    pub async fn delete(AuthUser(user): AuthUser, Path(webhook_id): Path<i64>) -> Response {
+        if let Err(resp) = Self::check_access(webhook_id, &user).await {
+            return resp;
+        }
+
+        match WebhookRepository::delete(webhook_id).await {
+            Ok(result) => json_success(result),
+            Err(e) => json_error(StatusCode::BAD_REQUEST, e),
+        }
+    }
+
+    // POST /webhooks/:id/secret
+    pub async fn rotate_secret(AuthUser(user): AuthUser, Path(webhook_id): Path<i64>) -> Response {
+        if let Err(resp) = Self::check_access(webhook_id, &user).await {
+            return resp;
+        }
+
+        let secret = key_generator();
+        match WebhookRepository::set_secret(webhook_id, secret.clone()).await {
+            Ok(webhook) => Self::with_secret(&webhook, &secret),
+            Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+
+    // GET /webhooks/:id/deliveries
+    pub async fn deliveries(
+        AuthUser(user): AuthUser,
+        Path(webhook_id): Path<i64>,
+        Query(p): Query<PaginationParams>,
+    ) -> Response {
+        if let Err(resp) = Self::check_access(webhook_id, &user).await {
+            return resp;
+        }
+
+        match WebhookDeliveryRepository::list_by_webhook(webhook_id, p.page(), p.per_page()).await {
+            Ok(mut items) => {
+                // What an endpoint answers may be an internal page; only admins get to read it.
+                if !user.is_admin {
+                    for delivery in &mut items.items {
+                        delivery.response_body = None;
+                    }
+                }
+                json_success(items)
+            }
+            Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+
+    // POST /webhooks/:id/deliveries/:delivery_id/redeliver
+    pub async fn redeliver(
+        AuthUser(user): AuthUser,
+        Path((webhook_id, delivery_id)): Path<(i64, i64)>,
+    ) -> Response {
+        if let Err(resp) = Self::check_access(webhook_id, &user).await {
+            return resp;
+        }
+
+        let delivery = match WebhookDeliveryRepository::find(webhook_id, delivery_id).await {
+            Ok(Some(delivery)) => delivery,
+            Ok(None) => {
+                return json_error(StatusCode::NOT_FOUND, "Delivery not found".to_string());
+            }
+            Err(e) => return json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        };
+
+        match WebhookDeliveryRepository::redeliver(&delivery).await {
+            Ok(queued) => json_success(queued),
+            Err(e) => json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+
+    /// Refuse a name that resolves to an internal address. Deliveries check again when they
+    /// connect, since the name may point elsewhere by then.
+    async fn check_destination(url: &str) -> Result<(), Response> {
+        if allow_private_networks() {
+            return Ok(());
+        }
+
+        let Some((host, port)) = Url::parse(url).ok().and_then(|url| {
+            let host = url.host_str()?.trim_matches(['[', ']']).to_string();
+            Some((host, url.port_or_known_default()?))
+        }) else {
+            return Ok(());
+        };
+
+        resolve_public(&host, port)
+            .await
+            .map(|_| ())
+            .map_err(|e| json_error(StatusCode::BAD_REQUEST, format!("url: {e}")))
+    }
+
+    /// The webhook JSON plus its secret, which is shown only here.
+    fn with_secret(webhook: &Model, secret: &str) -> Response {
+        let mut response = serde_json::json!(webhook);
+        response["secret"] = serde_json::json!(secret);
+        json_success(response)
+    }
+
+    async fn check_access(webhook_id: i64, user: &user::Model) -> Result<Model, Response> {
+        let webhook = match WebhookRepository::find_by_id(webhook_id).await {
+            Ok(Some(webhook)) => webhook,
+            Ok(None) => {
+                return Err(json_error(
+                    StatusCode::NOT_FOUND,
+                    "Webhook not found".to_string(),
+                ));
+            }
+            Err(e) => {
+                return Err(json_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()));
+            }
+        };
+
+        if !user.is_admin && webhook.user_id != user.id {
+            return Err(json_error(
+                StatusCode::FORBIDDEN,
+                "You do not have permission to access this webhook".to_string(),
+            ));
+        }
+
+        Ok(webhook)
+    }
+}
@@ -0,0 +1,20 @@
+use crate::core::cron_manager::{CronDefinition, CronManager, CronSchedule, OverlapPolicy, boxed};
+use crate::core::state::AppState;
+use crate::features::webhooks::jobs::deliver_webhooks::deliver_webhooks;
+use std::time::Duration;
+
+pub struct WebhookCron;
+impl WebhookCron {
+    pub async fn run(app_state: AppState) -> CronManager {
+        let tick = Duration::from_secs(app_state.config.webhook_tick_seconds);
+        let job = CronDefinition {
+            name: "deliver_webhooks",
+            schedule: CronSchedule::Every(tick),
+            // A slow endpoint must not get the same delivery twice.
+            overlap: OverlapPolicy::Skip,
+            jitter: Duration::ZERO,
+            tasks: vec![boxed(|| async { deliver_webhooks().await })],
+        };
+        CronManager::new(vec![job])
+    }
+}
@@ -0,0 +1,203 @@
+use crate::core::logger::targets;
+use crate::features::webhooks::model::{webhook, webhook_delivery};
+use crate::features::webhooks::repository::webhook_delivery_repository::{
+    Attempt, WebhookDeliveryRepository,
+};
+use crate::features::webhooks::utility::public_address::{PublicResolver, check_url_host};
+use crate::features::webhooks::utility::signature::sign;
+use crate::utility::state::app_state;
+use chrono::Utc;
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Client, Response, Url, redirect};
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Deliveries sent per tick; the rest wait for the next one.
+const DELIVERY_BATCH_SIZE: u64 = 100;
+const DELIVERY_CONCURRENCY: usize = 4;
+/// Wait before the first retry; every further retry waits twice as long, up to the cap.
+const RETRY_BASE_SECONDS: i64 = 30;
+const RETRY_MAX_SECONDS: i64 = 6 * 60 * 60;
+/// Bytes of the endpoint's response kept in the delivery log.
+const RESPONSE_BODY_BYTES: usize = 2 * 1024;
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+pub const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+
+static WEBHOOK_CLIENT: Lazy<Client> = Lazy::new(|| {
+    let config = &app_state().config;
+    let timeout = Duration::from_secs(config.webhook_timeout_seconds);
+
+    let builder = Client::builder()
+        .user_agent(concat!("rust-crawler-webhooks/", env!("CARGO_PKG_VERSION")))
+        // A redirect would resend the signed payload somewhere nobody registered.
+        .redirect(redirect::Policy::none())
+        // A proxy would resolve the name itself, out of reach of the address check.
+        .no_proxy()
+        .timeout(timeout);
+
+    let builder = if config.webhook_allow_private_networks {
+        builder
+    } else {
+        builder.dns_resolver(Arc::new(PublicResolver))
+    };
+
+    builder
+        .build()
+        .expect("Failed to build webhook HTTP client")
+});
+
+/// Send the queued webhook deliveries that are due, scheduling retries for the failures.
+pub async fn deliver_webhooks() {
+    let due = match WebhookDeliveryRepository::due(DELIVERY_BATCH_SIZE).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!(target: targets::SYSTEM, error = %e, "Failed to load due webhook deliveries");
+            return;
+        }
+    };
+
+    if due.is_empty() {
+        return;
+    }
+
+    let total = due.len();
+    let delivered = futures::stream::iter(due)
+        .map(|(delivery, webhook)| async move { deliver(&delivery, &webhook).await })
+        .buffer_unordered(DELIVERY_CONCURRENCY)
+        .filter(|delivered| futures::future::ready(*delivered))
+        .count()
+        .await;
+
+    info!(
+        target: targets::SYSTEM,
+        delivered,
+        failed = total - delivered,
+        "[cron:deliver_webhooks] sent webhook deliveries"
+    );
+}
+
+/// One attempt; returns whether the endpoint accepted it.
+async fn deliver(delivery: &webhook_delivery::Model, webhook: &webhook::Model) -> bool {
+    let result = match check_destination(&webhook.url) {
+        Ok(()) => send(delivery, webhook).await.map_err(|e| describe(&e)),
+        Err(e) => Err(e),
+    };
+
+    let (attempt, delivered) = match result {
+        Ok(response) => {
+            let status = response.status();
+            let attempt = Attempt {
+                response_status: Some(status.as_u16() as i32),
+                response_body: read_body(response).await,
+                error: (!status.is_success()).then(|| format!("HTTP {status}")),
+            };
+            (attempt, status.is_success())
+        }
+        Err(e) => {
+            let attempt = Attempt {
+                response_status: None,
+                response_body: None,
+                error: Some(e),
+            };
+            (attempt, false)
+        }
+    };
+
+    let attempts = delivery.attempts + 1;
+    let retry_at = (!delivered && attempts < app_state().config.webhook_max_attempts)
+        .then(|| Utc::now().naive_utc() + chrono::Duration::seconds(backoff_seconds(attempts)));
+
+    if !delivered {
+        warn!(
+            target: targets::SYSTEM,
+            webhook_id = webhook.id,
+            delivery_id = delivery.id,
+            attempts,
+            error = attempt.error.as_deref().unwrap_or_default(),
+            final_attempt = retry_at.is_none(),
+            "Webhook delivery failed"
+        );
+    }
+
+    if let Err(e) =
+        WebhookDeliveryRepository::record_attempt(delivery, attempt, delivered, retry_at).await
+    {
+        error!(
+            target: targets::SYSTEM,
+            delivery_id = delivery.id,
+            error = %e,
+            "Failed to record webhook delivery attempt"
+        );
+    }
+
+    delivered
+}
+
+async fn send(
+    delivery: &webhook_delivery::Model,
+    webhook: &webhook::Model,
+) -> reqwest::Result<Response> {
+    let signature = sign(&webhook.secret, Utc::now().timestamp(), &delivery.payload);
+
+    WEBHOOK_CLIENT
+        .post(&webhook.url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, &delivery.event)
+        .header(DELIVERY_HEADER, delivery.id)
+        .header(SIGNATURE_HEADER, signature)
+        .body(delivery.payload.clone())
+        .send()
+        .await
+}
+
+/// The error with its causes, which reqwest leaves out of its message (a refused address
+/// only shows up there).
+fn describe(error: &reqwest::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// Names are checked by the client's resolver, but IP literals never reach it.
+fn check_destination(url: &str) -> Result<(), String> {
+    if app_state().config.webhook_allow_private_networks {
+        return Ok(());
+    }
+
+    let url = Url::parse(url).map_err(|e| format!("Invalid URL: {e}"))?;
+    check_url_host(&url)
+}
+
+/// 30 s after the first failed attempt, then 1, 2, 4 ... minutes, at most 6 hours.
+fn backoff_seconds(attempts: i32) -> i64 {
+    let doublings = (attempts - 1).clamp(0, 20) as u32;
+    RETRY_BASE_SECONDS
+        .saturating_mul(1 << doublings)
+        .min(RETRY_MAX_SECONDS)
+}
+
+/// The start of the response body, without reading the rest of a large one.
+async fn read_body(mut response: Response) -> Option<String> {
+    let mut body = Vec::new();
+    while body.len() < RESPONSE_BODY_BYTES {
+        match response.chunk().await {
+            Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+            _ => break,
+        }
+    }
+    body.truncate(RESPONSE_BODY_BYTES);
+
+    let text = String::from_utf8_lossy(&body).trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
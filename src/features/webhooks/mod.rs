@@ -0,0 +1,8 @@
+pub mod controller;
+pub mod cron;
+pub mod jobs;
+pub mod model;
+pub mod repository;
+pub mod routes;
+pub mod utility;
+mod validation;
@@ -0,0 +1,71 @@
+use crate::features::sites::model::site;
+use crate::features::users::model::{api_key, user};
+use crate::features::webhooks::utility::post_event::PostEvent;
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "webhooks")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub user_id: i64,
+    /// Posts crawled through this key are delivered, unless `site_id` narrows it to one site.
+    pub api_key_id: i64,
+    pub site_id: Option<i64>,
+    pub url: String,
+    /// Signs every delivery; only shown when created or rotated.
+    #[serde(skip_serializing)]
+    pub secret: String,
+    /// Comma-separated event names, e.g. `post.completed,post.updated`.
+    pub events: String,
+    pub status: bool,
+    pub created_at: DateTime,
+    pub updated_at: Option<DateTime>,
+}
+
+impl Model {
+    pub fn subscribes(&self, event: PostEvent) -> bool {
+        self.events
+            .split(',')
+            .any(|name| PostEvent::parse(name) == Some(event))
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "user::Entity",
+        from = "Column::UserId",
+        to = "user::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "api_key::Entity",
+        from = "Column::ApiKeyId",
+        to = "api_key::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    ApiKey,
+    #[sea_orm(
+        belongs_to = "site::Entity",
+        from = "Column::SiteId",
+        to = "site::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Site,
+    #[sea_orm(has_many = "super::webhook_delivery::Entity")]
+    WebhookDelivery,
+}
+
+impl Related<super::webhook_delivery::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WebhookDelivery.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
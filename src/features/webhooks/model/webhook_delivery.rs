@@ -0,0 +1,62 @@
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+/// One event queued for one webhook, and the outcome of its latest attempt.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "webhook_deliveries")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub webhook_id: i64,
+    pub post_id: Option<i64>,
+    pub event: String,
+    /// The JSON body, fixed when the event happened so retries send the same thing.
+    #[sea_orm(column_type = "Text")]
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime,
+    pub response_status: Option<i32>,
+    /// The start of the endpoint's response, for debugging.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub response_body: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub error: Option<String>,
+    pub last_attempt_at: Option<DateTime>,
+    pub delivered_at: Option<DateTime>,
+    pub created_at: DateTime,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+pub enum DeliveryStatus {
+    #[sea_orm(string_value = "PENDING")]
+    PENDING,
+
+    #[sea_orm(string_value = "DELIVERED")]
+    DELIVERED,
+
+    /// Gave up after `WEBHOOK_MAX_ATTEMPTS`.
+    #[sea_orm(string_value = "FAILED")]
+    FAILED,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::webhook::Entity",
+        from = "Column::WebhookId",
+        to = "super::webhook::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Webhook,
+}
+
+impl Related<super::webhook::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Webhook.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
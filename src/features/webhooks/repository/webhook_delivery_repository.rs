@@ -0,0 +1,174 @@
+use crate::core::dto::pagination::Items;
+use crate::core::repository::paginate::paginate;
+use crate::features::sites::model::posts;
+use crate::features::webhooks::model::prelude::{Webhook, WebhookDelivery};
+use crate::features::webhooks::model::webhook_delivery::{Column, DeliveryStatus, Model};
+use crate::features::webhooks::model::{webhook, webhook_delivery};
+use crate::features::webhooks::utility::post_event::PostEvent;
+use crate::utility::state::app_state;
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+use serde_json::json;
+
+/// What one delivery attempt got back from the endpoint.
+pub struct Attempt {
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+}
+
+pub struct WebhookDeliveryRepository;
+
+impl WebhookDeliveryRepository {
+    /// Queue `event` for every active webhook subscribed to it that covers the post: its
+    /// site's webhooks, and the key-wide webhooks of the API key it was crawled through.
+    /// Called inside the transaction that stores the change, so no event is lost or invented.
+    pub async fn enqueue<C: ConnectionTrait>(
+        db: &C,
+        event: PostEvent,
+        post: &posts::Model,
+    ) -> Result<usize, DbErr> {
+        let webhooks: Vec<webhook::Model> = Webhook::find()
+            .filter(webhook::Column::Status.eq(true))
+            .filter(
+                Condition::any()
+                    .add(webhook::Column::SiteId.eq(post.site_id))
+                    .add(
+                        Condition::all()
+                            .add(webhook::Column::SiteId.is_null())
+                            .add(webhook::Column::ApiKeyId.eq(post.api_key_id)),
+                    ),
+            )
+            .all(db)
+            .await?
+            .into_iter()
+            .filter(|webhook| webhook.subscribes(event))
+            .collect();
+
+        if webhooks.is_empty() {
+            return Ok(0);
+        }
+
+        let now = Utc::now().naive_utc();
+        let payload = json!({
+            "event": event.as_str(),
+            "occurred_at": now,
+            "post": post,
+        })
+        .to_string();
+
+        let rows = webhooks
+            .iter()
+            .map(|webhook| webhook_delivery::ActiveModel {
+                webhook_id: Set(webhook.id),
+                post_id: Set(Some(post.id)),
+                event: Set(event.as_str().to_string()),
+                payload: Set(payload.clone()),
+                status: Set(DeliveryStatus::PENDING),
+                attempts: Set(0),
+                next_attempt_at: Set(now),
+                created_at: Set(now),
+                ..Default::default()
+            });
+        WebhookDelivery::insert_many(rows).exec(db).await?;
+
+        Ok(webhooks.len())
+    }
+
+    /// Pending deliveries whose next attempt is due, oldest first, with their webhook.
+    /// Deliveries of disabled webhooks wait until it is enabled again.
+    pub async fn due(limit: u64) -> Result<Vec<(Model, webhook::Model)>, DbErr> {
+        let state = app_state();
+        let rows = WebhookDelivery::find()
+            .find_also_related(Webhook)
+            .filter(Column::Status.eq(DeliveryStatus::PENDING))
+            .filter(Column::NextAttemptAt.lte(Utc::now().naive_utc()))
+            .filter(webhook::Column::Status.eq(true))
+            .order_by_asc(Column::Id)
+            .limit(limit)
+            .all(&state._db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(delivery, webhook)| webhook.map(|w| (delivery, w)))
+            .collect())
+    }
+
+    /// Store the outcome of an attempt. `retry_at` schedules the next one for a failure;
+    /// without it a failure is final.
+    pub async fn record_attempt(
+        delivery: &Model,
+        attempt: Attempt,
+        delivered: bool,
+        retry_at: Option<NaiveDateTime>,
+    ) -> Result<Model, DbErr> {
+        let state = app_state();
+        let now = Utc::now().naive_utc();
+
+        let status = match (delivered, retry_at) {
+            (true, _) => DeliveryStatus::DELIVERED,
+            (false, Some(_)) => DeliveryStatus::PENDING,
+            (false, None) => DeliveryStatus::FAILED,
+        };
+
+        let am = webhook_delivery::ActiveModel {
+            id: Set(delivery.id),
+            status: Set(status),
+            attempts: Set(delivery.attempts + 1),
+            next_attempt_at: Set(retry_at.unwrap_or(delivery.next_attempt_at)),
+            response_status: Set(attempt.response_status),
+            response_body: Set(attempt.response_body),
+            error: Set(attempt.error),
+            last_attempt_at: Set(Some(now)),
+            delivered_at: Set(delivered.then_some(now)),
+            ..Default::default()
+        };
+
+        am.update(&state._db).await
+    }
+
+    pub async fn list_by_webhook(
+        webhook_id: i64,
+        page: u64,
+        per_page: u64,
+    ) -> Result<Items<Model>, DbErr> {
+        let state = app_state();
+        let q = WebhookDelivery::find()
+            .filter(Column::WebhookId.eq(webhook_id))
+            .order_by_desc(Column::Id);
+        paginate::<webhook_delivery::Entity>(q, &state._db, page, per_page).await
+    }
+
+    pub async fn find(webhook_id: i64, delivery_id: i64) -> Result<Option<Model>, DbErr> {
+        let state = app_state();
+        WebhookDelivery::find_by_id(delivery_id)
+            .filter(Column::WebhookId.eq(webhook_id))
+            .one(&state._db)
+            .await
+    }
+
+    /// Queue the payload of an earlier delivery again as a new delivery, keeping the
+    /// original and its log as they were.
+    pub async fn redeliver(delivery: &Model) -> Result<Model, DbErr> {
+        let state = app_state();
+        let now = Utc::now().naive_utc();
+
+        let am = webhook_delivery::ActiveModel {
+            webhook_id: Set(delivery.webhook_id),
+            post_id: Set(delivery.post_id),
+            event: Set(delivery.event.clone()),
+            payload: Set(delivery.payload.clone()),
+            status: Set(DeliveryStatus::PENDING),
+            attempts: Set(0),
+            next_attempt_at: Set(now),
+            created_at: Set(now),
+            ..Default::default()
+        };
+
+        am.insert(&state._db).await
+    }
+}
@@ -0,0 +1,99 @@
+use crate::core::dto::pagination::Items;
+use crate::core::repository::paginate::paginate;
+use crate::features::users::model::user;
+use crate::features::webhooks::model::prelude::Webhook;
+use crate::features::webhooks::model::webhook;
+use crate::features::webhooks::model::webhook::{Column, Model};
+use crate::features::webhooks::utility::post_event::normalize_events;
+use crate::features::webhooks::validation::webhook_form::WebhookForm;
+use crate::utility::state::app_state;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QueryTrait, Set,
+};
+use std::ops::Not;
+
+pub struct WebhookRepository;
+
+impl WebhookRepository {
+    /// Every webhook for admins, otherwise the user's own.
+    pub async fn list(user: &user::Model, page: u64, per_page: u64) -> Result<Items<Model>, DbErr> {
+        let state = app_state();
+
+        let q = Webhook::find()
+            .apply_if(user.is_admin.not().then_some(user.id), |q, user_id| {
+                q.filter(Column::UserId.eq(user_id))
+            })
+            .order_by_desc(Column::Id);
+
+        paginate::<webhook::Entity>(q, &state._db, page, per_page).await
+    }
+
+    pub async fn find_by_id(webhook_id: i64) -> Result<Option<Model>, DbErr> {
+        let state = app_state();
+        Webhook::find_by_id(webhook_id).one(&state._db).await
+    }
+
+    pub async fn create(
+        data: WebhookForm,
+        user_id: i64,
+        api_key_id: i64,
+        secret: String,
+    ) -> Result<Model, DbErr> {
+        let state = app_state();
+        let am = webhook::ActiveModel {
+            user_id: Set(user_id),
+            api_key_id: Set(api_key_id),
+            site_id: Set(data.site_id),
+            url: Set(data.url),
+            secret: Set(secret),
+            events: Set(normalize_events(data.events.as_deref())),
+            status: Set(data.status.unwrap_or(true)),
+            created_at: Set(Utc::now().naive_utc()),
+            ..Default::default()
+        };
+
+        am.insert(&state._db).await
+    }
+
+    pub async fn update(webhook_id: i64, data: WebhookForm) -> Result<Option<Model>, DbErr> {
+        let state = app_state();
+
+        if Self::find_by_id(webhook_id).await?.is_none() {
+            return Ok(None);
+        }
+
+        let am = webhook::ActiveModel {
+            id: Set(webhook_id),
+            site_id: Set(data.site_id),
+            url: Set(data.url),
+            events: Set(normalize_events(data.events.as_deref())),
+            status: Set(data.status.unwrap_or(true)),
+            updated_at: Set(Some(Utc::now().naive_utc())),
+            ..Default::default()
+        };
+
+        am.update(&state._db).await.map(Some)
+    }
+
+    pub async fn set_secret(webhook_id: i64, secret: String) -> Result<Model, DbErr> {
+        let state = app_state();
+        let am = webhook::ActiveModel {
+            id: Set(webhook_id),
+            secret: Set(secret),
+            updated_at: Set(Some(Utc::now().naive_utc())),
+            ..Default::default()
+        };
+
+        am.update(&state._db).await
+    }
+
+    pub async fn delete(webhook_id: i64) -> Result<bool, String> {
+        let state = app_state();
+
+        match Webhook::delete_by_id(webhook_id).exec(&state._db).await {
+            Ok(result) => Ok(result.rows_affected > 0),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
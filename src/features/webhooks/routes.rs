@@ -0,0 +1,39 @@
+use crate::features::webhooks::controller::webhook_controller::WebhookController;
+use crate::middleware::auth::auth;
+use crate::utility::state::app_state;
+use axum::routing::{get, post};
+use axum::{Router, middleware};
+
+pub fn webhook_route() -> (&'static str, Router) {
+    let state = app_state();
+
+    let mw_auth = middleware::from_fn_with_state(state.clone(), auth);
+
+    (
+        "api/v1/webhooks",
+        Router::new()
+            .route(
+                "/",
+                get(WebhookController::list).post(WebhookController::create),
+            )
+            .route(
+                "/{webhook_id}",
+                get(WebhookController::show)
+                    .put(WebhookController::update)
+                    .delete(WebhookController::delete),
+            )
+            .route(
+                "/{webhook_id}/secret",
+                post(WebhookController::rotate_secret),
+            )
+            .route(
+                "/{webhook_id}/deliveries",
+                get(WebhookController::deliveries),
+            )
+            .route(
+                "/{webhook_id}/deliveries/{delivery_id}/redeliver",
+                post(WebhookController::redeliver),
+            )
+            .route_layer(mw_auth),
+    )
+}
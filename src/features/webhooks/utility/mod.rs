@@ -0,0 +1,3 @@
+pub mod post_event;
+pub mod public_address;
+pub mod signature;
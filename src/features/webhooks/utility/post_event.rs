@@ -0,0 +1,88 @@
+use crate::features::sites::model::posts::PostStatus;
+use std::borrow::Cow;
+use validator::ValidationError;
+
+/// Subscribed to when a webhook doesn't name its events.
+pub const DEFAULT_EVENTS: &str = "post.completed";
+
+/// A change to a post that webhooks can subscribe to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PostEvent {
+    /// The post's content was extracted for the first time (or after failures).
+    Completed,
+    /// A completed post was re-extracted and its content changed.
+    Updated,
+    /// The post ran out of retries.
+    Cancelled,
+}
+
+impl PostEvent {
+    pub const ALL: [PostEvent; 3] = [Self::Completed, Self::Updated, Self::Cancelled];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Completed => "post.completed",
+            Self::Updated => "post.updated",
+            Self::Cancelled => "post.cancelled",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|event| event.as_str() == name.trim())
+    }
+
+    /// The event a stored post change amounts to, from its status before and after the write.
+    /// Only called when the content or status actually changed.
+    pub fn between(before: &PostStatus, after: &PostStatus) -> Option<Self> {
+        match (before, after) {
+            (PostStatus::COMPLETED, PostStatus::COMPLETED) => Some(Self::Updated),
+            (_, PostStatus::COMPLETED) => Some(Self::Completed),
+            (PostStatus::CANCELLED, PostStatus::CANCELLED) => None,
+            (_, PostStatus::CANCELLED) => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// The event list as stored: known names, comma-separated, without duplicates.
+pub fn normalize_events(raw: Option<&str>) -> String {
+    let events: Vec<&str> = PostEvent::ALL
+        .into_iter()
+        .filter(|event| {
+            raw.unwrap_or_default()
+                .split(',')
+                .any(|name| PostEvent::parse(name) == Some(*event))
+        })
+        .map(PostEvent::as_str)
+        .collect();
+
+    if events.is_empty() {
+        DEFAULT_EVENTS.to_string()
+    } else {
+        events.join(",")
+    }
+}
+
+/// Form validator for `events`: comma-separated names such as `post.completed,post.updated`.
+pub fn validate_events(raw: &str) -> Result<(), ValidationError> {
+    let unknown: Vec<&str> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && PostEvent::parse(name).is_none())
+        .collect();
+
+    if unknown.is_empty() {
+        return Ok(());
+    }
+
+    let known: Vec<&str> = PostEvent::ALL.into_iter().map(PostEvent::as_str).collect();
+    Err(
+        ValidationError::new("events").with_message(Cow::Owned(format!(
+            "unknown event {} (expected {})",
+            unknown.join(", "),
+            known.join(", ")
+        ))),
+    )
+}
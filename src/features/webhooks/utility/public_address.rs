@@ -0,0 +1,186 @@
+use reqwest::Url;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// Whether `ip` belongs to the public internet. Loopback, private, link-local, unique-local,
+/// shared (CGNAT), unspecified, broadcast, multicast, documentation and reserved ranges don't,
+/// nor do IPv6 addresses that embed such an IPv4 address.
+pub fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => is_public_v6(ip),
+    }
+}
+
+/// Check the host of a webhook URL without resolving it: IP literals must be public, and
+/// `localhost` names are refused outright. Other names are checked when they resolve.
+pub fn check_url_host(url: &Url) -> Result<(), String> {
+    let host = url.host_str().ok_or("URL has no host")?;
+
+    match host.trim_matches(['[', ']']).parse::<IpAddr>() {
+        Ok(ip) if !is_public(ip) => Err(format!("{ip} is not a public address")),
+        Ok(_) => Ok(()),
+        Err(_) if is_localhost(host) => Err(format!("{host} is not a public host")),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Resolve `host`, failing unless it resolves and every address it resolves to is public.
+/// One private answer is enough to refuse it, so a name can't mix a public and an internal
+/// address and hope the connection picks the latter.
+pub async fn resolve_public(host: &str, port: u16) -> Result<Vec<SocketAddr>, String> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("{host} does not resolve: {e}"))?
+        .collect();
+
+    if addrs.is_empty() {
+        return Err(format!("{host} does not resolve"));
+    }
+    if let Some(addr) = addrs.iter().find(|addr| !is_public(addr.ip())) {
+        return Err(format!(
+            "{host} resolves to {}, which is not public",
+            addr.ip()
+        ));
+    }
+
+    Ok(addrs)
+}
+
+/// DNS resolver for the webhook client. It checks the addresses the connection will
+/// actually use, so a name that resolved to a public address when the webhook was saved
+/// can't be rebound to an internal one later.
+pub struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = resolve_public(name.as_str(), 0).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+fn is_localhost(name: &str) -> bool {
+    let name = name.trim_end_matches('.').to_ascii_lowercase();
+    name == "localhost" || name.ends_with(".localhost")
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // 0.0.0.0/8 "this network"
+        || a == 0
+        // 100.64.0.0/10 shared address space (CGNAT)
+        || (a == 100 && (b & 0xc0) == 64)
+        // 192.0.0.0/24 protocol assignments
+        || (a == 192 && b == 0 && c == 0)
+        // 198.18.0.0/15 benchmarking
+        || (a == 198 && (b & 0xfe) == 18)
+        // 240.0.0.0/4 reserved
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        return is_public_v4(v4);
+    }
+
+    let segments = ip.segments();
+    // 64:ff9b::/96 NAT64 and the deprecated ::a.b.c.d form carry an IPv4 address.
+    if (segments[0] == 0x64 && segments[1] == 0xff9b && segments[2..6] == [0; 4])
+        || (segments[..6] == [0; 6] && !ip.is_unspecified() && !ip.is_loopback())
+    {
+        let [.., a, b, c, d] = ip.octets();
+        return is_public_v4(Ipv4Addr::new(a, b, c, d));
+    }
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // fc00::/7 unique local
+        || (segments[0] & 0xfe00) == 0xfc00
+        // fe80::/10 link-local, fec0::/10 deprecated site-local
+        || (segments[0] & 0xffc0) == 0xfe80
+        || (segments[0] & 0xffc0) == 0xfec0
+        // 2001:db8::/32 documentation
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public(ip: &str) -> bool {
+        is_public(ip.parse().unwrap())
+    }
+
+    #[test]
+    fn internal_ipv4_ranges_are_not_public() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "224.0.0.1",
+            "198.18.0.1",
+        ] {
+            assert!(!public(ip), "{ip}");
+        }
+    }
+
+    #[test]
+    fn internal_ipv6_ranges_are_not_public() {
+        for ip in [
+            "::1",
+            "::",
+            "fd00::1",
+            "fc12::1",
+            "fe80::1",
+            "ff02::1",
+            "::ffff:127.0.0.1",
+            "::ffff:10.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+            "2001:db8::1",
+        ] {
+            assert!(!public(ip), "{ip}");
+        }
+    }
+
+    #[test]
+    fn public_addresses_are_allowed() {
+        for ip in [
+            "93.184.216.34",
+            "8.8.8.8",
+            "172.32.0.1",
+            "100.128.0.1",
+            "2606:4700::1111",
+            "::ffff:8.8.8.8",
+        ] {
+            assert!(public(ip), "{ip}");
+        }
+    }
+
+    #[test]
+    fn url_hosts_are_checked_without_resolving() {
+        let check = |url: &str| check_url_host(&Url::parse(url).unwrap());
+        assert!(check("http://127.0.0.1:8080/hook").is_err());
+        assert!(check("http://[::1]/hook").is_err());
+        assert!(check("http://[::ffff:192.168.0.1]/hook").is_err());
+        assert!(check("http://localhost/hook").is_err());
+        assert!(check("http://api.LOCALHOST./hook").is_err());
+        assert!(check("https://example.com/hook").is_ok());
+        assert!(check("https://8.8.8.8/hook").is_ok());
+    }
+}
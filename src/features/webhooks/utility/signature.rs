@@ -0,0 +1,45 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// `t=<unix seconds>,v1=<hex HMAC-SHA256 of "{t}.{body}">`, keyed with the webhook secret.
+/// Signing the timestamp with the body lets receivers reject replayed deliveries.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+
+    format!(
+        "t={timestamp},v1={}",
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = r#"{"event":"post.created","post":{"id":1}}"#;
+
+    #[test]
+    fn known_answer() {
+        // printf '1760000000.%s' "$BODY" | openssl dgst -sha256 -hmac whsec_test
+        assert_eq!(
+            sign("whsec_test", 1_760_000_000, BODY),
+            "t=1760000000,v1=1f2214f94bd4b37671a8875e15f704adae1c5a3c27d3a9695a2896230cabdd45"
+        );
+    }
+
+    #[test]
+    fn the_timestamp_is_part_of_the_signature() {
+        assert_eq!(
+            sign("whsec_test", 1_760_000_001, BODY),
+            "t=1760000001,v1=11ffb22cd46b2284cce0a62bfaaa37ed97e99bd4ff272b0a42103420c64ed168"
+        );
+        assert_ne!(
+            sign("other", 1_760_000_000, BODY),
+            sign("whsec_test", 1_760_000_000, BODY)
+        );
+    }
+}
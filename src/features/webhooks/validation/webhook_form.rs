@@ -0,0 +1,43 @@
+use crate::core::state::APP_STATE;
+use crate::features::webhooks::utility::post_event::validate_events;
+use crate::features::webhooks::utility::public_address::check_url_host;
+use reqwest::Url;
+use serde::Deserialize;
+use std::borrow::Cow;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Deserialize, Validate, Clone)]
+pub struct WebhookForm {
+    #[validate(custom(function = "validate_webhook_url"))]
+    pub url: String,
+    /// Comma-separated event names; `post.completed` when blank.
+    #[validate(custom(function = "validate_events"))]
+    pub events: Option<String>,
+    /// Only deliver this site's posts instead of every post of the API key.
+    pub site_id: Option<i64>,
+    pub status: Option<bool>,
+}
+
+/// An absolute http(s) URL that, unless `WEBHOOK_ALLOW_PRIVATE_NETWORKS` is on, doesn't
+/// point at a loopback, private, link-local or unique-local address.
+fn validate_webhook_url(url: &str) -> Result<(), ValidationError> {
+    let url = match Url::parse(url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.host().is_some() => url,
+        _ => {
+            return Err(ValidationError::new("url")
+                .with_message(Cow::Borrowed("must be an absolute http(s) URL")));
+        }
+    };
+
+    if allow_private_networks() {
+        return Ok(());
+    }
+    check_url_host(&url)
+        .map_err(|message| ValidationError::new("url").with_message(Cow::Owned(message)))
+}
+
+pub fn allow_private_networks() -> bool {
+    APP_STATE
+        .get()
+        .is_some_and(|state| state.config.webhook_allow_private_networks)
+}
@@ -1,10 +1,11 @@
 use crate::core::response::json_error;
 use crate::core::state::AppState;
 use crate::features::users::service::srv_api_key::ServiceApiKey;
-use axum::extract::{Request, State};
+use axum::extract::{Query, Request, State};
 use axum::http::StatusCode;
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
 
 pub async fn auth(State(_state): State<AppState>, mut req: Request, next: Next) -> Response {
     let key = match req.headers().get("X-API-Key").and_then(|v| v.to_str().ok()) {
@@ -25,3 +26,34 @@ pub async fn auth(State(_state): State<AppState>, mut req: Request, next: Next)
 
     next.run(req).await
 }
+
+#[derive(Deserialize)]
+struct TokenParams {
+    token: Option<String>,
+}
+
+/// `auth`, or a `?token=` from `POST /api/v1/posts/stream/token`, for the post stream only:
+/// browsers' `EventSource` and `WebSocket` can't send `X-API-Key`.
+pub async fn stream_auth(State(state): State<AppState>, mut req: Request, next: Next) -> Response {
+    if req.headers().contains_key("X-API-Key") {
+        return auth(State(state), req, next).await;
+    }
+
+    let token = Query::<TokenParams>::try_from_uri(req.uri())
+        .ok()
+        .and_then(|Query(params)| params.token)
+        .filter(|token| !token.is_empty());
+    let Some(token) = token else {
+        return json_error(StatusCode::UNAUTHORIZED, "Missing API key").into_response();
+    };
+
+    if let Some((user, api_key)) = ServiceApiKey::stream_auth(&token).await {
+        req.extensions_mut().insert(std::sync::Arc::new(user));
+        req.extensions_mut().insert(std::sync::Arc::new(api_key));
+    } else {
+        return json_error(StatusCode::UNAUTHORIZED, "Invalid or expired stream token")
+            .into_response();
+    }
+
+    next.run(req).await
+}
@@ -1,8 +1,10 @@
 use crate::core::router::Router as MyRouter;
 use crate::core::state::AppState;
 use crate::features::home::controller::HomeController;
-use crate::features::sites::routes::{post_route, site_route};
+use crate::features::jobs::routes::jobs_route;
+use crate::features::sites::routes::{feed_route, media_route, post_route, site_route};
 use crate::features::users::routes::{api_key_route, users_route};
+use crate::features::webhooks::routes::webhook_route;
 use axum::routing::get;
 use axum::{Router as AxumRouter, Router};
 
@@ -15,6 +17,10 @@ impl Routes {
             api_key_route(),
             site_route(),
             post_route(),
+            media_route(),
+            feed_route(),
+            webhook_route(),
+            jobs_route(),
         ];
 
         MyRouter::routes(app_state, routers_list)
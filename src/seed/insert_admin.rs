@@ -18,6 +18,8 @@ pub async fn run(db: &DatabaseConnection) -> anyhow::Result<()> {
 
     let key_hash = hash_key(&raw_key);
 
+    let raw_feed_token: String = key_generator();
+
     let admin = user::ActiveModel {
         name: Set(name.into()),
         is_admin: Set(true),
@@ -30,6 +32,7 @@ pub async fn run(db: &DatabaseConnection) -> anyhow::Result<()> {
     let api_key = api_key::ActiveModel {
         user_id: Set(admin.id),
         key_hash: Set(key_hash),
+        feed_token_hash: Set(Some(hash_key(&raw_feed_token))),
         status: Set(true),
         ..Default::default()
     };
@@ -38,5 +41,6 @@ pub async fn run(db: &DatabaseConnection) -> anyhow::Result<()> {
 
     info!(target: "system", "Admin user seeded (name: {name})");
     info!(target: "system", "Api key generated: {}", raw_key);
+    info!(target: "system", "Feed token generated: {}", raw_feed_token);
     Ok(())
 }